@@ -0,0 +1,108 @@
+//! CDCE913 on an ESP32-C3 (GPIO4 = SDA, GPIO5 = SCL): finds the device, has
+//! the planner set Y2 to 48 MHz from a 27 MHz crystal and commits the result
+//! to the EEPROM. Bus errors are told apart by the driver's error variants and,
+//! where those can't, by esp-hal's own error type.
+
+#![no_std]
+#![no_main]
+
+use cdcx913::family::{DeviceKind, detect};
+use cdcx913::output::OutputMask;
+use cdcx913::timing::EmbassyTimer;
+use cdcx913::{CDCE913, Error};
+use embassy_executor::Spawner;
+use embassy_time::{Duration, Timer};
+use esp_backtrace as _;
+use esp_hal::i2c::master::{Config, Error as I2cError, I2c};
+use esp_hal::interrupt::software::SoftwareInterruptControl;
+use esp_hal::time::Rate;
+use esp_hal::timer::timg::TimerGroup;
+use log::{error, info};
+
+esp_bootloader_esp_idf::esp_app_desc!();
+
+#[esp_rtos::main]
+async fn main(_spawner: Spawner) {
+    esp_println::logger::init_logger_from_env();
+
+    let peripherals = esp_hal::init(esp_hal::Config::default());
+    let timg0 = TimerGroup::new(peripherals.TIMG0);
+    let sw_int = SoftwareInterruptControl::new(peripherals.SW_INTERRUPT);
+    esp_rtos::start(timg0.timer0, sw_int.software_interrupt0);
+
+    // The CDCE913 takes up to 400 kHz; fit 4.7 kOhm pull-ups to 3.3 V
+    let mut i2c = I2c::new(
+        peripherals.I2C0,
+        Config::default().with_frequency(Rate::from_khz(400)),
+    )
+    .unwrap()
+    .with_sda(peripherals.GPIO4)
+    .with_scl(peripherals.GPIO5)
+    .into_async();
+
+    // Retry until the board is powered, rather than failing for good
+    let detected = loop {
+        match detect(&mut i2c).await {
+            Ok(detected) => break detected,
+            Err(error) => error!("{}", explain(&error)),
+        }
+
+        Timer::after(Duration::from_secs(1)).await;
+    };
+    info!("found {:?} at {:#04x}", detected.kind, detected.address);
+
+    if !matches!(detected.kind, DeviceKind::CDCE913 | DeviceKind::CDCEL913) {
+        error!("this example drives a CDCE(L)913");
+        return;
+    }
+
+    let mut cdcx913 = CDCE913::new_with_delay(i2c, EmbassyTimer);
+
+    match configure(&mut cdcx913).await {
+        Ok(true) => info!("Y2 at 48 MHz, committed to the EEPROM"),
+        Ok(false) => info!("Y2 at 48 MHz, the EEPROM already held it"),
+        Err(error) => error!("{}", explain(&error)),
+    }
+}
+
+/// Sets Y2 to 48 MHz and commits the registers to the EEPROM, unless it holds
+/// them already. Returns whether it committed.
+async fn configure(
+    cdcx913: &mut CDCE913<I2c<'static, esp_hal::Async>, EmbassyTimer>,
+) -> Result<bool, Error<I2cError>> {
+    // Right after power-up the registers are what the EEPROM holds
+    let stored = cdcx913.read_config().await?;
+
+    cdcx913.set_reference_frequency_hz(Some(27_000_000));
+    cdcx913.set_output_frequency(2, 48_000_000).await?;
+    cdcx913.enable_outputs(OutputMask::Y2Y3).await?;
+
+    // The EEPROM wears out, so don't rewrite it on every boot
+    if cdcx913.read_config().await? == stored {
+        return Ok(false);
+    }
+
+    cdcx913.initiate_eeprom_write().await?;
+
+    Ok(true)
+}
+
+fn explain(error: &Error<I2cError>) -> &'static str {
+    match error {
+        // The driver sorts out what the embedded-hal error kind tells apart
+        Error::AddressNack(_) => "no device answered: check its supply and the address",
+        Error::DataNack(_) => "the device refused a byte, e.g. while it programs its EEPROM",
+        Error::ArbitrationLoss(_) => "another master took the bus",
+        // The C3 can't always tell which byte went unacknowledged
+        Error::I2c(I2cError::AcknowledgeCheckFailed(_)) => "the device didn't acknowledge",
+        // Unlike most HALs, esp-hal times out instead of hanging on a bus
+        // without pull-ups or with SCL held low
+        Error::I2c(I2cError::Timeout) => "bus timeout: check the pull-ups",
+        Error::I2c(_) => "I2C error",
+        Error::NoDeviceFound => "no CDCx913 on any default address",
+        Error::EepromTimeout => "the EEPROM write didn't finish in time",
+        Error::EepromLocked => "the EEPROM is locked",
+        Error::FrequencyUnachievable(_) => "no PLL setting gives 48 MHz exactly",
+        _ => "driver error",
+    }
+}
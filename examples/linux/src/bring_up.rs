@@ -0,0 +1,19 @@
+//! The part of the example an MCU would run as well. tests/linux_example.rs
+//! in the driver crate runs it against the simulated device.
+
+use cdcx913::{CDCE913, Error, output::OutputMask};
+use embedded_hal_async::{delay::DelayNs, i2c::I2c};
+
+/// Sets Y2 to 48 MHz from a 27 MHz crystal and commits it to the EEPROM
+pub async fn bring_up<I2C: I2c, DELAY: DelayNs>(
+    pll: &mut CDCE913<I2C, DELAY>,
+) -> Result<(), Error<I2C::Error>> {
+    log::info!("found a {:?}", pll.device_identification().await?);
+
+    pll.set_reference_frequency_hz(Some(27_000_000));
+    pll.set_output_frequency(2, 48_000_000).await?;
+    // Y2 and Y3 share their state definitions, so they're enabled together
+    pll.enable_outputs(OutputMask::Y2Y3).await?;
+
+    pll.initiate_eeprom_write().await
+}
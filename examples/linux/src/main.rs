@@ -0,0 +1,43 @@
+//! Sets Y2 of a CDCE913 on a Linux host, e.g. a Raspberry Pi gateway, to
+//! 48 MHz from a 27 MHz crystal and commits it to the EEPROM.
+//!
+//! `bring_up` is the same code an MCU would run; only `main` is specific to
+//! Linux. Run with e.g. `RUST_LOG=debug cargo run -- /dev/i2c-1`.
+
+mod bring_up;
+
+use std::{env, process::ExitCode};
+
+use bring_up::bring_up;
+use cdcx913::{
+    CDCE913,
+    blocking::block_on,
+    linux::{LinuxI2c, StdDelay},
+};
+
+fn main() -> ExitCode {
+    env_logger::init();
+
+    let path = env::args().nth(1).unwrap_or("/dev/i2c-1".into());
+    // A linux_embedded_hal::I2cdev (0.4 or later) works as well, wrapped in
+    // cdcx913::blocking::Blocking
+    let i2c = match LinuxI2c::open(&path) {
+        Ok(i2c) => i2c,
+        Err(error) => {
+            eprintln!("can't open {path}: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut pll = CDCE913::new_with_delay(i2c, StdDelay);
+
+    match block_on(bring_up(&mut pll)) {
+        Ok(()) => {
+            println!("Y2 runs at 48 MHz, also after the next power-up");
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("{error}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,82 @@
+//! CDCE913 on an nRF52840-DK (P0.26 = SDA, P0.27 = SCL). Y2 is set to
+//! 12 MHz from a 27 MHz crystal, and button 1 gates Y2 and Y3 on and off, the
+//! way firmware would stop a clock while its consumer sleeps.
+//!
+//! By default the driver uses the async TWIM methods, so the CPU sleeps while
+//! EasyDMA moves the bytes. `cargo run --features blocking` uses the blocking
+//! ones through `cdcx913::blocking::Blocking` instead; the driver code stays
+//! the same either way.
+
+#![no_std]
+#![no_main]
+
+use cdcx913::output::{DisableMode, OutputMask};
+use cdcx913::timing::EmbassyTimer;
+use cdcx913::{CDCE913, Error};
+use embassy_executor::Spawner;
+use embassy_nrf::gpio::{Input, Pull};
+use embassy_nrf::twim::{self, Frequency, Twim};
+use embassy_nrf::{bind_interrupts, peripherals};
+use embedded_hal_async::i2c::I2c;
+use {defmt_rtt as _, panic_probe as _};
+
+bind_interrupts!(struct Irqs {
+    TWISPI0 => twim::InterruptHandler<peripherals::TWISPI0>;
+});
+
+#[embassy_executor::main]
+async fn main(_spawner: Spawner) {
+    defmt::info!("Hello world!");
+
+    let p = embassy_nrf::init(Default::default());
+
+    // The internal pull-ups (13 kOhm) are too weak for 400 kHz; the DK has
+    // none on these pins, so fit 4.7 kOhm to VDD
+    let mut config = twim::Config::default();
+    config.frequency = Frequency::K400;
+
+    // EasyDMA can't read flash, so writes from there go through this buffer
+    let mut tx_buffer = [0; 16];
+    let twim = Twim::new(p.TWISPI0, Irqs, p.P0_26, p.P0_27, config, &mut tx_buffer);
+
+    #[cfg(not(feature = "blocking"))]
+    let i2c = twim;
+    #[cfg(feature = "blocking")]
+    let i2c = cdcx913::blocking::Blocking(twim);
+
+    let button = Input::new(p.P0_11, Pull::Up);
+
+    if let Err(error) = run(CDCE913::new_with_delay(i2c, EmbassyTimer), button).await {
+        defmt::error!("CDCE913 error: {}", error);
+    }
+}
+
+async fn run<I2C: I2c>(
+    mut cdcx913: CDCE913<I2C, EmbassyTimer>,
+    mut button: Input<'_>,
+) -> Result<(), Error<I2C::Error>> {
+    cdcx913.set_reference_frequency_hz(Some(27_000_000));
+    cdcx913.set_output_frequency(2, 12_000_000).await?;
+    cdcx913.enable_outputs(OutputMask::Y2Y3).await?;
+
+    let mut enabled = true;
+
+    loop {
+        button.wait_for_falling_edge().await;
+        enabled = !enabled;
+
+        // Gating the outputs keeps the PLL locked, so they come back at once
+        // and in phase. Powered-down output buffers draw the least; PWDN
+        // would save more, but leaving it reloads the EEPROM and loses the
+        // frequency set above unless it was committed.
+        if enabled {
+            cdcx913.enable_outputs(OutputMask::Y2Y3).await?;
+        } else {
+            cdcx913
+                .disable_outputs(OutputMask::Y2Y3, DisableMode::PowerDown)
+                .await?;
+        }
+
+        defmt::info!("Y2/Y3 {}", if enabled { "on" } else { "off" });
+    }
+}
@@ -0,0 +1,117 @@
+//! CDCE913 on a Raspberry Pi Pico, sharing I2C0 (GP4 = SDA, GP5 = SCL) with
+//! a TMP102 temperature sensor. Y2 and Y3 are set to 48 MHz from a 27 MHz
+//! crystal, then one task keeps an eye on the clock generator and another
+//! reads the sensor, each through its own device on the shared bus.
+
+#![no_std]
+#![no_main]
+
+use cdcx913::CDCE913;
+use cdcx913::control::ControlInputState;
+use cdcx913::family::Cdce913;
+use cdcx913::output::OutputMask;
+use cdcx913::shared::SharedCdcx913;
+use cdcx913::timing::EmbassyTimer;
+use embassy_embedded_hal::shared_bus::asynch::i2c::I2cDevice;
+use embassy_executor::Spawner;
+use embassy_rp::bind_interrupts;
+use embassy_rp::i2c::{self, Config, I2c};
+use embassy_rp::peripherals::I2C0;
+use embassy_sync::blocking_mutex::raw::NoopRawMutex;
+use embassy_sync::mutex::Mutex;
+use embassy_time::{Duration, Timer};
+use embedded_hal_async::i2c::I2c as _;
+use static_cell::StaticCell;
+use {defmt_rtt as _, panic_probe as _};
+
+bind_interrupts!(struct Irqs {
+    I2C0_IRQ => i2c::InterruptHandler<I2C0>;
+});
+
+const TMP102_ADDRESS: u8 = 0x48;
+
+type Bus = Mutex<NoopRawMutex, I2c<'static, I2C0, i2c::Async>>;
+type Device = I2cDevice<'static, NoopRawMutex, I2c<'static, I2C0, i2c::Async>>;
+type Pll = SharedCdcx913<NoopRawMutex, Device, Cdce913, EmbassyTimer>;
+
+static BUS: StaticCell<Bus> = StaticCell::new();
+static PLL: StaticCell<Pll> = StaticCell::new();
+
+#[embassy_executor::main]
+async fn main(spawner: Spawner) {
+    defmt::info!("Hello world!");
+
+    let p = embassy_rp::init(Default::default());
+
+    // The CDCE913 takes up to 400 kHz. The Pico has no I2C pull-ups and the
+    // RP2040's internal ones (50-80 kOhm) are too weak for that, so fit 4.7 kOhm
+    // to 3.3 V on SDA and SCL. The internal ones are only enough for 100 kHz
+    // with short wires on a breadboard.
+    let i2c = I2c::new_async(p.I2C0, p.PIN_5, p.PIN_4, Irqs, {
+        let mut cfg = Config::default();
+        cfg.frequency = 400_000;
+        cfg.scl_pullup = false;
+        cfg.sda_pullup = false;
+        cfg
+    });
+    let bus = BUS.init(Mutex::new(i2c));
+
+    defmt::info!("Initializing");
+
+    let mut cdcx913 = CDCE913::new_with_delay(I2cDevice::new(bus), EmbassyTimer);
+
+    // The outputs are only stable once the device has loaded its EEPROM
+    cdcx913.wait_for_startup().await;
+
+    defmt::debug!(
+        "Device Identification = {}",
+        cdcx913.device_identification().await.unwrap()
+    );
+
+    cdcx913.set_reference_frequency_hz(Some(27_000_000));
+    cdcx913.set_output_frequency(2, 48_000_000).await.unwrap();
+    cdcx913.enable_outputs(OutputMask::Y2Y3).await.unwrap();
+
+    let pll = PLL.init(SharedCdcx913::new(cdcx913));
+
+    spawner.spawn(monitor(pll)).unwrap();
+    spawner.spawn(temperature(I2cDevice::new(bus))).unwrap();
+}
+
+/// Logs what the clock generator is doing once a second
+#[embassy_executor::task]
+async fn monitor(pll: &'static Pll) {
+    loop {
+        // The lock is only held for the one read, not while this task sleeps.
+        // S0 is tied high here, and S1 and S2 count as high while they serve
+        // as SDA and SCL.
+        match pll
+            .lock()
+            .await
+            .effective_config_for(ControlInputState::S111)
+            .await
+        {
+            Ok(effective) => defmt::info!("clocks: {}", effective.frequencies),
+            Err(error) => defmt::warn!("reading the CDCE913 failed: {}", error),
+        }
+
+        Timer::after(Duration::from_secs(1)).await;
+    }
+}
+
+/// Another device on the same bus, which it gets to use between the
+/// transfers of the clock generator
+#[embassy_executor::task]
+async fn temperature(mut tmp102: Device) {
+    loop {
+        let mut raw = [0; 2];
+
+        match tmp102.write_read(TMP102_ADDRESS, &[0x00], &mut raw).await {
+            // 12 bits in 1/16 degrees
+            Ok(()) => defmt::info!("temperature: {} / 16 C", i16::from_be_bytes(raw) >> 4),
+            Err(error) => defmt::warn!("reading the TMP102 failed: {}", error),
+        }
+
+        Timer::after(Duration::from_secs(1)).await;
+    }
+}
@@ -0,0 +1,98 @@
+//! CDCE913 on a "blue pill" STM32F103 (PB7 = SDA, PB6 = SCL) with the
+//! blocking stm32f1xx-hal I2C and no executor. Every driver call runs to
+//! completion through `block_on`, since `Blocking` never lets it wait.
+//!
+//! Y2 is set to 24 MHz from a 27 MHz crystal at start-up. Pulling PA0 low
+//! commits the registers to the EEPROM and reads them back from it, so the
+//! next power-up starts at 24 MHz as well.
+
+#![no_std]
+#![no_main]
+
+use cdcx913::CDCE913;
+use cdcx913::blocking::{Blocking, block_on};
+use cdcx913::output::OutputMask;
+use cortex_m_rt::entry;
+use stm32f1xx_hal::i2c::{BlockingI2c, DutyCycle, Mode};
+use stm32f1xx_hal::pac;
+use stm32f1xx_hal::prelude::*;
+use {defmt_rtt as _, panic_probe as _};
+
+#[entry]
+fn main() -> ! {
+    defmt::info!("Hello world!");
+
+    let cp = cortex_m::Peripherals::take().unwrap();
+    let dp = pac::Peripherals::take().unwrap();
+
+    let mut flash = dp.FLASH.constrain();
+    let rcc = dp.RCC.constrain();
+    let clocks = rcc
+        .cfgr
+        .use_hse(8.MHz())
+        .sysclk(72.MHz())
+        .pclk1(36.MHz())
+        .freeze(&mut flash.acr);
+
+    let mut afio = dp.AFIO.constrain();
+    let mut gpioa = dp.GPIOA.split();
+    let mut gpiob = dp.GPIOB.split();
+    let mut gpioc = dp.GPIOC.split();
+
+    let scl = gpiob.pb6.into_alternate_open_drain(&mut gpiob.crl);
+    let sda = gpiob.pb7.into_alternate_open_drain(&mut gpiob.crl);
+    let i2c = BlockingI2c::i2c1(
+        dp.I2C1,
+        (scl, sda),
+        &mut afio.mapr,
+        Mode::Fast {
+            frequency: 400.kHz(),
+            duty_cycle: DutyCycle::Ratio2to1,
+        },
+        clocks,
+        1000,
+        10,
+        1000,
+        1000,
+    );
+    let delay = cp.SYST.delay(&clocks);
+
+    let button = gpioa.pa0.into_pull_up_input(&mut gpioa.crl);
+    let mut led = gpioc.pc13.into_push_pull_output(&mut gpioc.crh);
+
+    defmt::info!("Initializing");
+
+    let mut cdcx913 = CDCE913::new_with_delay(Blocking(i2c), Blocking(delay));
+
+    defmt::debug!(
+        "Device Identification = {}",
+        block_on(cdcx913.device_identification()).unwrap()
+    );
+
+    cdcx913.set_reference_frequency_hz(Some(27_000_000));
+    block_on(cdcx913.set_output_frequency(2, 24_000_000)).unwrap();
+    block_on(cdcx913.enable_outputs(OutputMask::Y2Y3)).unwrap();
+
+    let mut committed = false;
+
+    loop {
+        if button.is_low() && !committed {
+            led.set_low();
+
+            // Waits out the EEPROM write with the delay, then reloads the
+            // registers from the EEPROM and compares
+            let config = block_on(cdcx913.read_config()).unwrap();
+            block_on(cdcx913.initiate_eeprom_write()).unwrap();
+            let diff = block_on(cdcx913.verify_eeprom_commit(&config)).unwrap();
+
+            if diff.is_empty() {
+                defmt::info!("EEPROM programmed");
+            } else {
+                defmt::error!("EEPROM verification failed: {}", diff);
+            }
+
+            committed = true;
+            led.set_high();
+        }
+    }
+}
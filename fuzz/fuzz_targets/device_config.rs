@@ -0,0 +1,24 @@
+#![no_main]
+
+use cdcx913::{
+    config::DeviceConfig, control::ControlInputState,
+    registers::generic_configuration::DeviceIdentification,
+};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: ([u8; 32], [u8; 32], u32)| {
+    let (bytes, other, reference_hz) = input;
+    let config = DeviceConfig::from_bytes(bytes);
+
+    for state in ControlInputState::ALL {
+        let _ = config.effective_config(state, Some(reference_hz));
+        let _ = config.effective_config(state, None);
+    }
+
+    let _ = config.variant();
+    let _ = config.validate(DeviceIdentification::CDCE913, reference_hz);
+    let _ = config.validate(DeviceIdentification::CDCEL913, reference_hz);
+
+    let diff = config.diff(&DeviceConfig::from_bytes(other));
+    assert_eq!(diff.is_empty(), diff.mismatches().next().is_none());
+});
@@ -0,0 +1,33 @@
+#![no_main]
+
+use cdcx913::{
+    planner::{ConfigRequest, Limits, plan},
+    registers::generic_configuration::DeviceIdentification,
+};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: (u32, u32, u32, u32, bool)| {
+    let (reference_hz, y1_hz, y2_hz, y3_hz, low_voltage) = input;
+    let variant = if low_voltage {
+        DeviceIdentification::CDCEL913
+    } else {
+        DeviceIdentification::CDCE913
+    };
+    let limits = Limits::for_variant(variant);
+
+    if let Ok(pll) = plan(reference_hz, y2_hz, &limits) {
+        assert_eq!(pll.settings.vco_frequency_hz(reference_hz), Some(pll.vco_hz));
+        assert_eq!(pll.vco_hz / pll.pdiv as u32, y2_hz);
+    }
+
+    let request = ConfigRequest::new()
+        .with_reference_hz(reference_hz)
+        .with_y1_hz(y1_hz)
+        .with_y2_hz(y2_hz)
+        .with_y3_hz(y3_hz)
+        .with_variant(variant);
+
+    if let Ok(config) = request.build() {
+        assert_eq!(config.validate(variant, reference_hz), Ok(()));
+    }
+});
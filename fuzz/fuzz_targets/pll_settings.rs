@@ -0,0 +1,25 @@
+#![no_main]
+
+use cdcx913::registers::pll1_configuration::PllSettings;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: (u32, u32)| {
+    let (raw, input_hz) = input;
+    let settings = PllSettings(raw);
+
+    let _ = settings.pllx_yn();
+    let _ = settings.pllx_yr();
+    let _ = settings.pllx_yq();
+    let _ = settings.pllx_yp();
+    let _ = settings.vco_range_selection();
+    let _ = settings.m();
+    let _ = settings.vco_frequency_hz(input_hz);
+
+    let mut copy = settings;
+    copy.set_pllx_yn(settings.pllx_yn());
+    copy.set_pllx_yr(settings.pllx_yr());
+    copy.set_pllx_yq(settings.pllx_yq());
+    copy.set_pllx_yp(settings.pllx_yp());
+    copy.set_vco_range_selection(settings.vco_range_selection());
+    assert_eq!(copy, settings);
+});
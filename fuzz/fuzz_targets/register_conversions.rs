@@ -0,0 +1,61 @@
+#![no_main]
+
+use cdcx913::{
+    u2, u3,
+    registers::{OutputStateDefinition, generic_configuration::*, pll1_configuration::*},
+    report::decode_register,
+};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|raw: u8| {
+    let _ = OutputStateDefinition::from(u2::new(raw & 0b11));
+    let _ = VcoRangeSelection::from(u2::new(raw & 0b11));
+    let _ = SscModulationAmountDown::from(u3::new(raw & 0b111));
+    let _ = SscModulationAmountCenter::from(u3::new(raw & 0b111));
+
+    let reg = GenericConfigurationRegister0::from(raw);
+    let _ = reg.device_identification();
+
+    let reg = GenericConfigurationRegister1::from(raw);
+    let _ = reg.eeprom_programming_status();
+    let _ = reg.input_clock_selection();
+
+    let reg2 = GenericConfigurationRegister2::from(raw);
+    let _ = reg2.y1_clock_source();
+    let _ = reg2.serial_interface_pin_mode();
+    let _ = reg2.y1_state0_definition();
+    let _ = reg2.y1_state1_definition();
+
+    let _ = GenericConfigurationRegister3::from(raw).pdiv1_full_value(&reg2);
+    let _ = GenericConfigurationRegister5::from(raw).crystal_load_capacitance();
+    assert_eq!(CrystalLoadCapacitance::from_xcsel(raw).xcsel(), (raw & 0x1F).min(20));
+
+    let reg = Pll1ConfigurationRegister4::from(raw);
+    let _ = reg.pll1_multiplexer();
+    let _ = reg.output_y2_multiplexer();
+    let _ = reg.output_y3_multiplexer();
+    let _ = reg.output_y3_source();
+    let _ = reg.y2y3_state0_definition();
+    let _ = reg.y2y3_state1_definition();
+
+    let _ = Pll1ConfigurationRegister6::from(raw).pll1_ssc_down_center_selection();
+    let _ = Pll1ConfigurationRegisterB::from(raw).vco1_0_range_selection();
+
+    for index in 0..8 {
+        let index = u3::new(index);
+
+        let _ = GenericConfigurationRegister4::from(raw).y1_state_selection(index);
+        let _ = Pll1ConfigurationRegister3::from(raw).fs1_selection(index);
+        let _ = Pll1ConfigurationRegister5::from(raw).y2y3_state_selection(index);
+    }
+
+    for offset in 0..0x20 {
+        let _ = decode_register(offset, raw).to_string();
+    }
+
+    let codes = unpack_ssc1_selections([raw, raw.rotate_left(3), raw.rotate_left(5)]);
+    assert_eq!(
+        unpack_ssc1_selections(pack_ssc1_selections(codes)),
+        codes
+    );
+});
@@ -0,0 +1,177 @@
+//! Host tool for CDCx913 devices on a Linux I2C bus, e.g. on a Raspberry Pi
+//! test fixture. Run without arguments for usage.
+
+use std::{env, fs, path::Path, process::ExitCode};
+
+use cdcx913::{
+    CDCx913, Error,
+    blocking::block_on,
+    config::{ChangeReport, DeviceConfig},
+    control::ControlInputState,
+    family::Cdce913,
+    linux::{LinuxI2c, LinuxI2cError, StdDelay},
+    report::{write_register_diff, write_register_map},
+};
+
+const USAGE: &str = "\
+usage: cdcx913 [options] <command>
+
+commands:
+  dump                   print the registers of the device
+  diff <file>            compare the device against a configuration file,
+                         side by side with the differing fields marked
+  apply <file>           program a configuration into the registers
+  eeprom-program <file>  apply a configuration, commit it to the EEPROM and
+                         verify the commit
+  decode <file>          print the registers of a configuration file, no
+                         device needed
+
+options:
+  --bus <path>           I2C bus device, default /dev/i2c-1
+  --reference <hz>       input clock frequency, prints the outputs as well
+
+Configuration files are JSON (.json), TOML (.toml) or ClockPro register
+exports (anything else).";
+
+type Driver = CDCx913<LinuxI2c, Cdce913, StdDelay>;
+
+fn main() -> ExitCode {
+    match run(env::args().skip(1).collect()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("{message}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+struct Options {
+    bus: String,
+    reference_hz: Option<u32>,
+}
+
+fn run(args: Vec<String>) -> Result<(), String> {
+    let mut options = Options {
+        bus: "/dev/i2c-1".into(),
+        reference_hz: None,
+    };
+    let mut positional = Vec::new();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{arg} needs a value"));
+
+        match arg.as_str() {
+            "--bus" => options.bus = value()?,
+            "--reference" => options.reference_hz = Some(number(&value()?)?),
+            "-h" | "--help" => return Err(USAGE.into()),
+            _ if arg.starts_with("--") => return Err(format!("unknown option {arg}\n\n{USAGE}")),
+            _ => positional.push(arg),
+        }
+    }
+
+    match positional.as_slice() {
+        [command] if command == "dump" => {
+            let config = block_on(driver(&options)?.read_config()).map_err(bus_error)?;
+            print_config(&config, options.reference_hz);
+        }
+        [command, file] if command == "diff" => {
+            let expected = load(file)?;
+            let actual = block_on(driver(&options)?.read_config()).map_err(bus_error)?;
+            let diff = expected.diff(&actual);
+
+            if diff.is_empty() {
+                println!("device matches {file}");
+            } else {
+                let mut table = String::new();
+                let _ = write_register_diff(&mut table, &expected, &actual);
+                print!("{table}");
+            }
+        }
+        [command, file] if command == "apply" => {
+            let config = load(file)?;
+            let report = block_on(driver(&options)?.apply_config(&config)).map_err(bus_error)?;
+
+            print_changes(&report);
+        }
+        [command, file] if command == "eeprom-program" => {
+            let config = load(file)?;
+            let mut driver = driver(&options)?;
+
+            let report = block_on(driver.write_config_to_eeprom(&config)).map_err(bus_error)?;
+            print_changes(&report);
+
+            let diff = block_on(driver.verify_eeprom_commit(&config)).map_err(bus_error)?;
+            if !diff.is_empty() {
+                return Err(format!(
+                    "EEPROM verification failed at {} registers",
+                    diff.mismatches().count()
+                ));
+            }
+            println!("EEPROM programmed and verified");
+        }
+        [command, file] if command == "decode" => {
+            print_config(&load(file)?, options.reference_hz);
+        }
+        _ => return Err(USAGE.into()),
+    }
+
+    Ok(())
+}
+
+fn driver(options: &Options) -> Result<Driver, String> {
+    let bus = LinuxI2c::open(&options.bus)
+        .map_err(|error| format!("can't open {}: {error}", options.bus))?;
+
+    Ok(CDCx913::new_with_delay(bus, StdDelay))
+}
+
+// Loads a configuration file, picking the format by extension
+fn load(file: &str) -> Result<DeviceConfig, String> {
+    let text = fs::read_to_string(file).map_err(|error| format!("can't read {file}: {error}"))?;
+
+    match Path::new(file).extension().and_then(|ext| ext.to_str()) {
+        Some("json") => DeviceConfig::from_json(&text).map_err(|error| error.to_string()),
+        Some("toml") => cdcx913::toml_config::parse(&text).map_err(|error| error.to_string()),
+        _ => cdcx913::clockpro::parse(&text).map_err(|error| error.to_string()),
+    }
+    .map_err(|error| format!("{file}: {error}"))
+}
+
+fn print_config(config: &DeviceConfig, reference_hz: Option<u32>) {
+    let mut map = String::new();
+    let _ = write_register_map(&mut map, config);
+    print!("{map}");
+
+    if let Some(reference_hz) = reference_hz {
+        for state in ControlInputState::ALL {
+            println!("\n{}", config.decoded(state, reference_hz));
+        }
+    }
+}
+
+fn print_changes(report: &ChangeReport) {
+    if report.is_unchanged() {
+        println!("nothing to change");
+    }
+    for change in report.changed_fields() {
+        println!(
+            "0x{:02X} {}: {} -> {}",
+            change.offset, change.field.name, change.before, change.after
+        );
+    }
+    for skipped in report.skipped_fields() {
+        println!(
+            "0x{:02X} {}: kept at {}, not {}",
+            skipped.offset, skipped.field.name, skipped.written, skipped.requested
+        );
+    }
+}
+
+fn number(text: &str) -> Result<u32, String> {
+    text.parse().map_err(|_| format!("invalid number {text}"))
+}
+
+fn bus_error(error: Error<LinuxI2cError>) -> String {
+    format!("device error: {error}")
+}
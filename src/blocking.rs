@@ -0,0 +1,80 @@
+//! Running the async driver on blocking HALs. [`Blocking`] turns a blocking
+//! `embedded-hal` bus or delay, e.g. `linux_embedded_hal::I2cdev`, into the
+//! async one the driver takes, and [`block_on`] runs the driver's futures
+//! without an executor:
+//!
+//! ```ignore
+//! let mut pll = CDCE913::new_with_delay(Blocking(i2c), Blocking(delay));
+//! let config = block_on(pll.read_config())?;
+//! ```
+
+use core::{
+    future::Future,
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+
+use embedded_hal::{delay::DelayNs as BlockingDelayNs, i2c::I2c as BlockingI2c};
+use embedded_hal_async::{
+    delay::DelayNs,
+    i2c::{ErrorType, I2c, Operation},
+};
+
+/// Async view of a blocking bus or delay. Every call completes before its
+/// future is first polled, so it never yields.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Blocking<T>(pub T);
+
+impl<T: BlockingI2c> ErrorType for Blocking<T> {
+    type Error = T::Error;
+}
+
+// Each method goes to its blocking counterpart, as HALs often implement those
+// more efficiently than through a transaction
+impl<T: BlockingI2c> I2c for Blocking<T> {
+    async fn read(&mut self, address: u8, read: &mut [u8]) -> Result<(), Self::Error> {
+        self.0.read(address, read)
+    }
+
+    async fn write(&mut self, address: u8, write: &[u8]) -> Result<(), Self::Error> {
+        self.0.write(address, write)
+    }
+
+    async fn write_read(
+        &mut self,
+        address: u8,
+        write: &[u8],
+        read: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.0.write_read(address, write, read)
+    }
+
+    async fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.0.transaction(address, operations)
+    }
+}
+
+impl<T: BlockingDelayNs> DelayNs for Blocking<T> {
+    async fn delay_ns(&mut self, ns: u32) {
+        self.0.delay_ns(ns);
+    }
+}
+
+/// Runs `future` to completion by polling it in a loop. Meant for driver calls
+/// on [`Blocking`] buses and delays, which never wait on a waker; a future
+/// that does spins until it's ready.
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut context = Context::from_waker(Waker::noop());
+
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+    }
+}
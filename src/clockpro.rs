@@ -0,0 +1,108 @@
+//! Parser for the register tables TI's ClockPro configuration tool exports.
+//! The exports come as CSV or whitespace separated text with one register per
+//! line, e.g. `0x10,0x00`, `10h 00h` or `Register 0x14 = 0x6D`. The first two
+//! hexadecimal fields of each line are taken as offset and value; headers and
+//! other lines without two such fields are skipped, as is everything after a
+//! `#` or `//`.
+
+use core::fmt;
+
+use crate::config::{DeviceConfig, REGISTER_WINDOW_LEN};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ParseErrorKind {
+    /// The offset lies outside of the register window of a [`DeviceConfig`]
+    OffsetOutOfRange(u8),
+    /// The value field doesn't fit a byte
+    ValueOutOfRange,
+    /// The export doesn't contain a single register
+    Empty,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ParseError {
+    /// 1-based line number the error was found on, 0 for [`ParseErrorKind::Empty`]
+    pub line: usize,
+    pub kind: ParseErrorKind,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            ParseErrorKind::OffsetOutOfRange(offset) => write!(
+                f,
+                "line {}: register 0x{offset:02X} is outside of the register window",
+                self.line
+            ),
+            ParseErrorKind::ValueOutOfRange => {
+                write!(f, "line {}: value does not fit a byte", self.line)
+            }
+            ParseErrorKind::Empty => f.write_str("export contains no registers"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+fn parse_hex(token: &str) -> Option<u32> {
+    let digits = token
+        .strip_prefix("0x")
+        .or_else(|| token.strip_prefix("0X"))
+        .or_else(|| token.strip_suffix('h'))
+        .or_else(|| token.strip_suffix('H'))
+        .unwrap_or(token);
+
+    if digits.is_empty() {
+        return None;
+    }
+
+    u32::from_str_radix(digits, 16).ok()
+}
+
+/// Parses a ClockPro register export into a [`DeviceConfig`]. Registers the
+/// export doesn't mention are left at 0.
+pub fn parse(text: &str) -> Result<DeviceConfig, ParseError> {
+    let mut registers = [0u8; REGISTER_WINDOW_LEN];
+    let mut any = false;
+
+    for (index, line) in text.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.split('#').next().unwrap_or_default();
+        let line = line.split("//").next().unwrap_or_default();
+
+        let mut fields = line
+            .split(|c: char| c.is_whitespace() || matches!(c, ',' | ';' | ':' | '='))
+            .filter_map(parse_hex);
+
+        let (Some(offset), Some(value)) = (fields.next(), fields.next()) else {
+            continue;
+        };
+
+        let error = |kind| ParseError {
+            line: line_number,
+            kind,
+        };
+
+        let offset = u8::try_from(offset)
+            .ok()
+            .filter(|&offset| (offset as usize) < REGISTER_WINDOW_LEN)
+            .ok_or(error(ParseErrorKind::OffsetOutOfRange(
+                offset.min(u8::MAX as u32) as u8,
+            )))?;
+        let value = u8::try_from(value).map_err(|_| error(ParseErrorKind::ValueOutOfRange))?;
+
+        registers[offset as usize] = value;
+        any = true;
+    }
+
+    if !any {
+        return Err(ParseError {
+            line: 0,
+            kind: ParseErrorKind::Empty,
+        });
+    }
+
+    Ok(DeviceConfig::from_bytes(registers))
+}
@@ -0,0 +1,186 @@
+//! Compact binary encoding of a [`DeviceConfig`] for storing it in flash or
+//! sending it over a provisioning link. Only the configuration registers of
+//! [`WRITABLE_OFFSETS`] are kept, framed by a format version and a CRC:
+//!
+//! | Byte  | Content                                    |
+//! |-------|--------------------------------------------|
+//! | 0     | Format version ([`FORMAT_VERSION`])        |
+//! | 1-22  | Registers in the order of `WRITABLE_OFFSETS` |
+//! | 23-24 | CRC-16/CCITT-FALSE of bytes 0-22, big endian |
+//!
+//! Snapshots ([`DeviceConfig::to_snapshot`]) are the self-describing variant
+//! for data that has to outlive crate upgrades, e.g. in MCU internal flash.
+//! They keep the whole register window and say what they hold, so a later
+//! version of the crate can still read them:
+//!
+//! | Byte      | Content                                     |
+//! |-----------|---------------------------------------------|
+//! | 0-3       | Magic `CDCX`                                |
+//! | 4         | Snapshot version ([`SNAPSHOT_VERSION`])     |
+//! | 5         | Device kind, CDCE913 = 0 up to CDCEL949 = 7 in the order of [`DeviceKind`] |
+//! | 6         | Number of registers `n`, from offset 0x00   |
+//! | 7..7+n    | Registers                                   |
+//! | 7+n..9+n  | CRC-16/CCITT-FALSE of all bytes before, big endian |
+
+use crate::{
+    config::{DeviceConfig, REGISTER_WINDOW_LEN, WRITABLE_OFFSETS},
+    family::DeviceKind,
+};
+
+pub const FORMAT_VERSION: u8 = 1;
+
+/// Length of an encoded configuration
+pub const ENCODED_LEN: usize = 1 + WRITABLE_OFFSETS.len() + 2;
+
+pub const SNAPSHOT_MAGIC: [u8; 4] = *b"CDCX";
+pub const SNAPSHOT_VERSION: u8 = 1;
+
+/// Length of a snapshot of a [`DeviceConfig`]
+pub const SNAPSHOT_LEN: usize = 7 + REGISTER_WINDOW_LEN + 2;
+
+// Stable codes of the device kinds in snapshots
+const DEVICE_KINDS: [DeviceKind; 8] = [
+    DeviceKind::CDCE913,
+    DeviceKind::CDCEL913,
+    DeviceKind::CDCE925,
+    DeviceKind::CDCEL925,
+    DeviceKind::CDCE937,
+    DeviceKind::CDCEL937,
+    DeviceKind::CDCE949,
+    DeviceKind::CDCEL949,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum DecodeError {
+    /// The input isn't [`ENCODED_LEN`] bytes long
+    Length(usize),
+    /// The input was encoded with an unknown format version
+    Version(u8),
+    /// The CRC doesn't match, the data got corrupted
+    Checksum,
+    /// The input doesn't start with [`SNAPSHOT_MAGIC`], it isn't a snapshot
+    Magic,
+    /// The snapshot names a device kind this version doesn't know
+    DeviceKind(u8),
+    /// The snapshot holds a different number of registers than a
+    /// [`DeviceConfig`]
+    RegisterCount(u8),
+}
+
+const fn crc16(data: &[u8]) -> u16 {
+    let mut crc = 0xFFFFu16;
+    let mut index = 0;
+
+    while index < data.len() {
+        crc ^= (data[index] as u16) << 8;
+
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+            bit += 1;
+        }
+
+        index += 1;
+    }
+
+    crc
+}
+
+impl DeviceConfig {
+    pub fn to_compact(&self) -> [u8; ENCODED_LEN] {
+        let mut out = [0u8; ENCODED_LEN];
+
+        out[0] = FORMAT_VERSION;
+        for (byte, offset) in out[1..].iter_mut().zip(WRITABLE_OFFSETS) {
+            *byte = self.register(offset);
+        }
+
+        let crc = crc16(&out[..ENCODED_LEN - 2]);
+        out[ENCODED_LEN - 2..].copy_from_slice(&crc.to_be_bytes());
+
+        out
+    }
+
+    /// Decodes a configuration encoded by [`Self::to_compact`]. Registers
+    /// outside of `WRITABLE_OFFSETS` are 0.
+    pub fn from_compact(bytes: &[u8]) -> Result<Self, DecodeError> {
+        if bytes.len() != ENCODED_LEN {
+            return Err(DecodeError::Length(bytes.len()));
+        }
+
+        let (payload, crc) = bytes.split_at(ENCODED_LEN - 2);
+
+        if crc16(payload).to_be_bytes() != crc {
+            return Err(DecodeError::Checksum);
+        }
+        if payload[0] != FORMAT_VERSION {
+            return Err(DecodeError::Version(payload[0]));
+        }
+
+        let mut registers = [0u8; REGISTER_WINDOW_LEN];
+        for (offset, byte) in WRITABLE_OFFSETS.into_iter().zip(&payload[1..]) {
+            registers[offset as usize] = *byte;
+        }
+
+        Ok(Self::from_bytes(registers))
+    }
+
+    /// Encodes the configuration of a `kind` device as a snapshot, see the
+    /// module documentation
+    pub fn to_snapshot(&self, kind: DeviceKind) -> [u8; SNAPSHOT_LEN] {
+        let mut out = [0u8; SNAPSHOT_LEN];
+
+        out[..4].copy_from_slice(&SNAPSHOT_MAGIC);
+        out[4] = SNAPSHOT_VERSION;
+        out[5] = DEVICE_KINDS
+            .iter()
+            .position(|other| *other == kind)
+            .unwrap_or_default() as u8;
+        out[6] = REGISTER_WINDOW_LEN as u8;
+        out[7..SNAPSHOT_LEN - 2].copy_from_slice(self.as_bytes());
+
+        let crc = crc16(&out[..SNAPSHOT_LEN - 2]);
+        out[SNAPSHOT_LEN - 2..].copy_from_slice(&crc.to_be_bytes());
+
+        out
+    }
+
+    /// Decodes a snapshot written by [`Self::to_snapshot`], along with the
+    /// kind of device it was taken for
+    pub fn from_snapshot(bytes: &[u8]) -> Result<(DeviceKind, Self), DecodeError> {
+        if bytes.len() < 9 {
+            return Err(DecodeError::Length(bytes.len()));
+        }
+        if bytes[..4] != SNAPSHOT_MAGIC {
+            return Err(DecodeError::Magic);
+        }
+
+        let count = bytes[6];
+        if bytes.len() != 7 + count as usize + 2 {
+            return Err(DecodeError::Length(bytes.len()));
+        }
+
+        let (payload, crc) = bytes.split_at(bytes.len() - 2);
+
+        if crc16(payload).to_be_bytes() != crc {
+            return Err(DecodeError::Checksum);
+        }
+        if payload[4] != SNAPSHOT_VERSION {
+            return Err(DecodeError::Version(payload[4]));
+        }
+
+        let kind = *DEVICE_KINDS
+            .get(payload[5] as usize)
+            .ok_or(DecodeError::DeviceKind(payload[5]))?;
+        let registers = payload[7..]
+            .try_into()
+            .map_err(|_| DecodeError::RegisterCount(count))?;
+
+        Ok((kind, Self::from_bytes(registers)))
+    }
+}
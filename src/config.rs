@@ -0,0 +1,1247 @@
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use core::fmt::Write;
+
+#[cfg(feature = "alloc")]
+use crate::report::write_fields;
+
+use crate::{
+    Register,
+    control::ControlInputState,
+    planner::{InputRange, Limits, vco_range},
+    registers::{
+        OutputStateDefinition, OutputStateSelection, ReservedEncoding,
+        generic_configuration::{
+            DeviceIdentification, GenericConfigurationRegister0, GenericConfigurationRegister1,
+            GenericConfigurationRegister2, GenericConfigurationRegister3,
+            GenericConfigurationRegister4, GenericConfigurationRegister6, InputClockSelection,
+            Y1ClockSource,
+        },
+        pll1_configuration::{
+            Fs1Selection, OutputY2Multiplexer, OutputY3Multiplexer, Pll1ConfigurationRegister3,
+            Pll1ConfigurationRegister4, Pll1ConfigurationRegister5, Pll1ConfigurationRegister6,
+            Pll1ConfigurationRegister7, Pll1Multiplexer, PllSettings, SscDownCenterSelection,
+            SscModulationAmountCenter, SscModulationAmountDown, VcoRangeSelection,
+            unpack_ssc1_selections,
+        },
+    },
+    report::{Field, FieldValue, register_fields},
+};
+
+/// Size of the register window covered by a [`DeviceConfig`]. This matches
+/// the default BCOUNT of the CDCx913 and spans the generic configuration
+/// (0x00-0x06), the reserved gap (0x07-0x0F) and the PLL1 configuration
+/// (0x10-0x1F). Every family member starts with this window, the PLL blocks
+/// of the larger devices follow it (up to
+/// [`crate::family::Device::REGISTER_MAP_LEN`]) and are reached through
+/// [`crate::CDCx913::pll`] only.
+pub const REGISTER_WINDOW_LEN: usize = 0x20;
+
+/// Offsets within the register window that hold configuration and get written
+/// by [`crate::CDCx913::apply_config`]. Register 0x00 is read-only and 0x07-0x0F
+/// are reserved.
+pub const WRITABLE_OFFSETS: [u8; 22] = [
+    0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19,
+    0x1A, 0x1B, 0x1C, 0x1D, 0x1E, 0x1F,
+];
+
+/// Bits of each register that are part of the configuration. Status and
+/// command bits (EEPIP, EELOCK, EEWRITE) are excluded so they don't show up as
+/// differences between otherwise identical configurations.
+const fn configuration_mask(offset: u8) -> u8 {
+    match offset {
+        0x01 => 0b1001_1111,
+        0x06 => 0b1111_1110,
+        _ => 0xFF,
+    }
+}
+
+/// An offset passed to [`DeviceConfig`] lies outside of the register window
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct OffsetOutOfRange(pub u8);
+
+/// A complete snapshot of the device configuration, stored as the raw bytes of
+/// the register window. Use [`crate::CDCx913::read_config`] to capture the
+/// current state of a device and [`crate::CDCx913::apply_config`] to program
+/// one.
+///
+/// On the CDCE(L)925, 937 and 949 this covers the generic and the PLL1
+/// registers only. Their PLL2-PLL4 blocks aren't captured, applied, compared
+/// or part of the EEPROM tooling built on it, so set those up through
+/// [`crate::CDCx913::pll`] and [`crate::CDCx913::output`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DeviceConfig {
+    registers: [u8; REGISTER_WINDOW_LEN],
+}
+
+impl DeviceConfig {
+    /// Configuration held by the raw register window, byte `n` being register
+    /// `n`. This is the inverse of [`Self::to_bytes`] and accepts any bytes,
+    /// so a window saved to flash restores without serde or a decoder.
+    #[doc(alias = "from_array")]
+    pub const fn from_bytes(registers: [u8; REGISTER_WINDOW_LEN]) -> Self {
+        Self { registers }
+    }
+
+    /// The raw register window, byte `n` being register `n`. Storing these
+    /// 32 bytes, e.g. in a bootloader's flash page, is enough to restore the
+    /// configuration with [`Self::from_bytes`]; use the `compact` feature for
+    /// a versioned, CRC-protected encoding instead.
+    #[doc(alias = "to_array")]
+    pub const fn to_bytes(&self) -> [u8; REGISTER_WINDOW_LEN] {
+        self.registers
+    }
+
+    pub const fn as_bytes(&self) -> &[u8; REGISTER_WINDOW_LEN] {
+        &self.registers
+    }
+
+    /// Raw byte at the given offset
+    pub fn byte(&self, offset: u8) -> Result<u8, OffsetOutOfRange> {
+        self.registers
+            .get(offset as usize)
+            .copied()
+            .ok_or(OffsetOutOfRange(offset))
+    }
+
+    /// Overwrites the raw byte at the given offset
+    pub fn set_byte(&mut self, offset: u8, value: u8) -> Result<(), OffsetOutOfRange> {
+        let byte = self
+            .registers
+            .get_mut(offset as usize)
+            .ok_or(OffsetOutOfRange(offset))?;
+
+        *byte = value;
+
+        Ok(())
+    }
+
+    /// Decodes the byte at the given offset as register `T`
+    pub fn with<T: From<u8>, R>(
+        &self,
+        offset: u8,
+        f: impl FnOnce(&T) -> R,
+    ) -> Result<R, OffsetOutOfRange> {
+        Ok(f(&T::from(self.byte(offset)?)))
+    }
+
+    /// Decodes the byte at the given offset as register `T`, lets `f` modify it
+    /// and stores the result back
+    pub fn modify<T: From<u8> + Into<u8>, R>(
+        &mut self,
+        offset: u8,
+        f: impl FnOnce(&mut T) -> R,
+    ) -> Result<R, OffsetOutOfRange> {
+        let mut reg = T::from(self.byte(offset)?);
+
+        let r = f(&mut reg);
+
+        self.set_byte(offset, reg.into())?;
+
+        Ok(r)
+    }
+
+    /// Raw byte at an offset the crate knows to be inside the register window,
+    /// such as the entries of [`WRITABLE_OFFSETS`]
+    pub(crate) fn register(&self, offset: u8) -> u8 {
+        self.registers[offset as usize]
+    }
+
+    /// [`Self::modify`] for an offset the crate knows to be inside the
+    /// register window
+    #[cfg(feature = "driver")]
+    pub(crate) fn modify_register<T: From<u8> + Into<u8>, R>(
+        &mut self,
+        offset: u8,
+        f: impl FnOnce(&mut T) -> R,
+    ) -> R {
+        let mut reg = T::from(self.register(offset));
+
+        let r = f(&mut reg);
+
+        self.registers[offset as usize] = reg.into();
+
+        r
+    }
+
+    fn generic<T: From<u8>>(&self, index: u8) -> T {
+        T::from(self.register(Register::GenericConfiguration as u8 + index))
+    }
+
+    fn pll1<T: From<u8>>(&self, index: u8) -> T {
+        T::from(self.register(Register::Pll1Configuration as u8 + index))
+    }
+
+    /// The N/R/Q/P settings of one of the two PLL1 banks
+    pub fn pll1_settings(&self, bank: Fs1Selection) -> PllSettings {
+        let base = match bank {
+            Fs1Selection::Fvcxo0 => 0x8,
+            Fs1Selection::Fvcxo1 => 0xC,
+        };
+
+        PllSettings(u32::from_be_bytes(core::array::from_fn(|index| {
+            self.pll1(base + index as u8)
+        })))
+    }
+
+    /// Resolves everything that applies while the control inputs are in
+    /// `state`. Output frequencies are only computed when the frequency of the
+    /// input clock is known.
+    pub fn effective_config(
+        &self,
+        state: ControlInputState,
+        reference_hz: Option<u32>,
+    ) -> EffectiveConfig {
+        let generic2: GenericConfigurationRegister2 = self.generic(2);
+        let generic4: GenericConfigurationRegister4 = self.generic(4);
+        let pll3: Pll1ConfigurationRegister3 = self.pll1(3);
+        let pll4: Pll1ConfigurationRegister4 = self.pll1(4);
+        let pll5: Pll1ConfigurationRegister5 = self.pll1(5);
+        let pll6: Pll1ConfigurationRegister6 = self.pll1(6);
+
+        let pll_bank = pll3.fs1_selection(state);
+        let pll_settings = self.pll1_settings(pll_bank);
+
+        let ssc_code =
+            unpack_ssc1_selections([self.pll1(0), self.pll1(1), self.pll1(2)])[state as usize];
+        let ssc = match pll6.pll1_ssc_down_center_selection() {
+            SscDownCenterSelection::Down => SscSetting::Down(ssc_code.into()),
+            SscDownCenterSelection::Center => SscSetting::Center(ssc_code.into()),
+        };
+
+        let y1 = match generic4.y1_state_selection(state) {
+            OutputStateSelection::State0 => generic2.y1_state0_definition(),
+            OutputStateSelection::State1 => generic2.y1_state1_definition(),
+        };
+        let y2y3 = match pll5.y2y3_state_selection(state) {
+            OutputStateSelection::State0 => pll4.y2y3_state0_definition(),
+            OutputStateSelection::State1 => pll4.y2y3_state1_definition(),
+        };
+
+        EffectiveConfig {
+            state,
+            pll_bank,
+            pll_settings,
+            ssc,
+            y1,
+            y2y3,
+            frequencies: reference_hz.map(|reference_hz| self.frequencies(pll_bank, reference_hz)),
+        }
+    }
+
+    /// Describes how the clocks are routed while the control inputs are in
+    /// `state`, from the input through PLL1 and the dividers to the outputs.
+    /// Frequencies are only computed when the frequency of the input clock is
+    /// known.
+    pub fn clock_tree(&self, state: ControlInputState, reference_hz: Option<u32>) -> ClockTree {
+        let generic1: GenericConfigurationRegister1 = self.generic(1);
+        let generic2: GenericConfigurationRegister2 = self.generic(2);
+        let generic3: GenericConfigurationRegister3 = self.generic(3);
+        let pll4: Pll1ConfigurationRegister4 = self.pll1(4);
+        let pll6: Pll1ConfigurationRegister6 = self.pll1(6);
+        let pll7: Pll1ConfigurationRegister7 = self.pll1(7);
+
+        let effective = self.effective_config(state, None);
+        let (pll, vco_hz) = match pll4.pll1_multiplexer() {
+            Pll1Multiplexer::Pll1 => (
+                ClockSource::Pll1(effective.pll_bank),
+                reference_hz.and_then(|hz| effective.pll_settings.vco_frequency_hz(hz)),
+            ),
+            Pll1Multiplexer::Pll1Bypass => (ClockSource::Pll1Bypass, None),
+        };
+        let pll_hz = match pll {
+            ClockSource::Pll1(_) => vco_hz,
+            _ => reference_hz,
+        };
+
+        let (pdiv1_source, pdiv1_input_hz) = match generic2.y1_clock_source() {
+            Y1ClockSource::InputClock => (ClockSource::Input, reference_hz),
+            Y1ClockSource::Pll1Clock => (pll, pll_hz),
+        };
+        let divider = |source, input_hz: Option<u32>, divider: u16| DividerNode {
+            source,
+            divider,
+            hz: input_hz.and_then(|hz| hz.checked_div(divider as u32)),
+        };
+        let dividers = [
+            divider(
+                pdiv1_source,
+                pdiv1_input_hz,
+                generic3.pdiv1_full_value(&generic2),
+            ),
+            divider(pll, pll_hz, pll6.pdiv2() as u16),
+            divider(pll, pll_hz, pll7.pdiv3() as u16),
+        ];
+
+        let output = |divider: Option<u8>, state| OutputNode {
+            divider,
+            state,
+            hz: divider.and_then(|n| dividers[n as usize - 1].hz),
+        };
+        let y2 = match pll4.output_y2_multiplexer() {
+            OutputY2Multiplexer::Pdiv1 => Some(1),
+            OutputY2Multiplexer::Pdiv2 => Some(2),
+        };
+        let y3 = match pll4.output_y3_multiplexer() {
+            OutputY3Multiplexer::Pdiv1 => Some(1),
+            OutputY3Multiplexer::Pdiv2 => Some(2),
+            OutputY3Multiplexer::Pdiv3 => Some(3),
+            OutputY3Multiplexer::Reserved => None,
+        };
+
+        ClockTree {
+            state,
+            input: generic1.input_clock_selection(),
+            reference_hz,
+            pll,
+            vco_hz,
+            dividers,
+            outputs: [
+                output(Some(1), effective.y1),
+                output(y2, effective.y2y3),
+                output(y3, effective.y2y3),
+            ],
+        }
+    }
+
+    pub(crate) fn frequencies(&self, bank: Fs1Selection, reference_hz: u32) -> OutputFrequencies {
+        let generic2: GenericConfigurationRegister2 = self.generic(2);
+        let generic3: GenericConfigurationRegister3 = self.generic(3);
+        let pll4: Pll1ConfigurationRegister4 = self.pll1(4);
+        let pll6: Pll1ConfigurationRegister6 = self.pll1(6);
+        let pll7: Pll1ConfigurationRegister7 = self.pll1(7);
+
+        let vco_hz = self.pll1_settings(bank).vco_frequency_hz(reference_hz);
+
+        let pll_hz = match pll4.pll1_multiplexer() {
+            Pll1Multiplexer::Pll1 => vco_hz,
+            Pll1Multiplexer::Pll1Bypass => Some(reference_hz),
+        };
+        let pdiv1_input_hz = match generic2.y1_clock_source() {
+            Y1ClockSource::InputClock => Some(reference_hz),
+            Y1ClockSource::Pll1Clock => pll_hz,
+        };
+
+        // A divider of 0 holds the divider in reset, so there's no output
+        let divide = |input_hz: Option<u32>, divider: u16| {
+            input_hz.and_then(|input_hz| input_hz.checked_div(divider as u32))
+        };
+
+        let pdiv1_hz = divide(pdiv1_input_hz, generic3.pdiv1_full_value(&generic2));
+        let pdiv2_hz = divide(pll_hz, pll6.pdiv2() as u16);
+        let pdiv3_hz = divide(pll_hz, pll7.pdiv3() as u16);
+
+        OutputFrequencies {
+            vco_hz,
+            y1_hz: pdiv1_hz,
+            y2_hz: match pll4.output_y2_multiplexer() {
+                OutputY2Multiplexer::Pdiv1 => pdiv1_hz,
+                OutputY2Multiplexer::Pdiv2 => pdiv2_hz,
+            },
+            y3_hz: match pll4.output_y3_multiplexer() {
+                OutputY3Multiplexer::Pdiv1 => pdiv1_hz,
+                OutputY3Multiplexer::Pdiv2 => pdiv2_hz,
+                OutputY3Multiplexer::Pdiv3 => pdiv3_hz,
+                OutputY3Multiplexer::Reserved => None,
+            },
+        }
+    }
+
+    /// Voltage variant recorded in the read-only E_EL bit. Only meaningful for
+    /// configurations read back from a device.
+    pub fn variant(&self) -> DeviceIdentification {
+        self.generic::<GenericConfigurationRegister0>(0)
+            .device_identification()
+    }
+
+    /// Checks the configuration against the limits of `variant`, for every
+    /// control input state: the reference must suit the selected input, the
+    /// VCO of each PLL bank in use must be in range and match its VCO range
+    /// bits, and no enabled output may run faster than the variant allows.
+    pub fn validate(
+        &self,
+        variant: DeviceIdentification,
+        reference_hz: u32,
+    ) -> Result<(), ValidationError> {
+        let limits = Limits::for_variant(variant);
+        let generic1: GenericConfigurationRegister1 = self.generic(1);
+        let pll4: Pll1ConfigurationRegister4 = self.pll1(4);
+
+        if let Ok(input) = generic1.input_clock_selection()
+            && !InputRange::for_input(input).contains(reference_hz)
+        {
+            return Err(ValidationError::ReferenceOutOfRange {
+                input,
+                reference_hz,
+            });
+        }
+
+        for state in ControlInputState::ALL {
+            let effective = self.effective_config(state, Some(reference_hz));
+            let bank = effective.pll_bank;
+
+            if pll4.pll1_multiplexer() == Pll1Multiplexer::Pll1 {
+                let vco_hz = effective
+                    .pll_settings
+                    .vco_frequency_hz(reference_hz)
+                    .ok_or(ValidationError::InvalidPllSettings(bank))?;
+
+                if !limits.vco_in_range(vco_hz) {
+                    return Err(ValidationError::VcoOutOfRange { bank, vco_hz });
+                }
+                if effective.pll_settings.vco_range_selection() != vco_range(vco_hz) {
+                    return Err(ValidationError::VcoRangeMismatch(bank));
+                }
+            }
+
+            let Some(frequencies) = effective.frequencies else {
+                continue;
+            };
+
+            let outputs = [
+                (1, effective.y1, frequencies.y1_hz),
+                (2, effective.y2y3, frequencies.y2_hz),
+                (3, effective.y2y3, frequencies.y3_hz),
+            ];
+
+            for (output, definition, hz) in outputs {
+                if definition == OutputStateDefinition::Enabled
+                    && let Some(hz) = hz
+                    && hz > limits.max_output_hz
+                {
+                    return Err(ValidationError::OutputTooFast { output, hz });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Pairs the configuration with the control input state and reference
+    /// frequency needed to decode it, for logging. Each output is rendered as
+    /// e.g. `Y2: PLL1_0 ÷4 = 45.1584 MHz, SSC ±0.5% center`.
+    pub fn decoded(&self, state: ControlInputState, reference_hz: u32) -> DecodedConfig<'_> {
+        DecodedConfig {
+            config: self,
+            state,
+            reference_hz,
+        }
+    }
+
+    /// Compares `self` (the expected configuration) against `actual`, looking
+    /// only at the configuration bits of [`WRITABLE_OFFSETS`]
+    pub fn diff(&self, actual: &DeviceConfig) -> ConfigDiff {
+        ConfigDiff {
+            expected: *self,
+            actual: *actual,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl DeviceConfig {
+    /// Renders the configuration as a Rust byte array constant, with each
+    /// register's decoded fields as a comment
+    pub fn to_rust_const(&self, name: &str) -> String {
+        let mut out = String::new();
+
+        out.push_str("// Generated by cdcx913 from a DeviceConfig\n");
+        let _ = writeln!(out, "pub const {name}: [u8; {REGISTER_WINDOW_LEN}] = [");
+        self.write_array_body(&mut out);
+        out.push_str("];\n");
+
+        out
+    }
+
+    /// Renders the configuration as a C header declaring a byte array, with
+    /// each register's decoded fields as a comment
+    pub fn to_c_header(&self, name: &str) -> String {
+        let mut out = String::new();
+        let guard = name.to_ascii_uppercase();
+
+        out.push_str("/* Generated by cdcx913 from a DeviceConfig */\n");
+        let _ = writeln!(out, "#ifndef {guard}_H");
+        let _ = writeln!(out, "#define {guard}_H\n");
+        out.push_str("#include <stdint.h>\n\n");
+        let _ = writeln!(
+            out,
+            "static const uint8_t {name}[{REGISTER_WINDOW_LEN}] = {{"
+        );
+        self.write_array_body(&mut out);
+        out.push_str("};\n\n");
+        let _ = writeln!(out, "#endif /* {guard}_H */");
+
+        out
+    }
+
+    fn write_array_body(&self, out: &mut String) {
+        for (offset, byte) in self.registers.iter().enumerate() {
+            let _ = write!(out, "    0x{byte:02X}, // 0x{offset:02X}: ");
+            let _ = write_fields(out, offset as u8, *byte);
+            out.push('\n');
+        }
+    }
+}
+
+impl From<[u8; REGISTER_WINDOW_LEN]> for DeviceConfig {
+    fn from(registers: [u8; REGISTER_WINDOW_LEN]) -> Self {
+        Self::from_bytes(registers)
+    }
+}
+
+impl From<DeviceConfig> for [u8; REGISTER_WINDOW_LEN] {
+    fn from(config: DeviceConfig) -> Self {
+        config.to_bytes()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RegisterMismatch {
+    pub offset: u8,
+    pub expected: u8,
+    pub actual: u8,
+}
+
+/// Result of comparing two [`DeviceConfig`]s, see [`DeviceConfig::diff`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ConfigDiff {
+    expected: DeviceConfig,
+    actual: DeviceConfig,
+}
+
+impl ConfigDiff {
+    pub fn expected(&self) -> &DeviceConfig {
+        &self.expected
+    }
+
+    pub fn actual(&self) -> &DeviceConfig {
+        &self.actual
+    }
+
+    /// Every writable register whose configuration bits differ
+    pub fn mismatches(&self) -> impl Iterator<Item = RegisterMismatch> + '_ {
+        WRITABLE_OFFSETS.into_iter().filter_map(|offset| {
+            let mask = configuration_mask(offset);
+            let expected = self.expected.register(offset);
+            let actual = self.actual.register(offset);
+
+            (expected & mask != actual & mask).then_some(RegisterMismatch {
+                offset,
+                expected,
+                actual,
+            })
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.mismatches().next().is_none()
+    }
+}
+
+/// A register of the window before and after [`crate::CDCx913::apply_config`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RegisterChange {
+    pub offset: u8,
+    pub before: u8,
+    pub after: u8,
+}
+
+/// A field whose value was changed by [`crate::CDCx913::apply_config`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FieldChange {
+    pub offset: u8,
+    pub field: Field,
+    pub before: FieldValue,
+    pub after: FieldValue,
+}
+
+/// A field of the requested configuration that
+/// [`crate::CDCx913::apply_config`] deliberately didn't program, e.g. EELOCK,
+/// which is kept as it is on the device
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SkippedField {
+    pub offset: u8,
+    pub field: Field,
+    pub requested: FieldValue,
+    pub written: FieldValue,
+}
+
+/// Auditable record of what [`crate::CDCx913::apply_config`] programmed into a
+/// device, or what [`crate::CDCx913::verify_config`] found it would program.
+/// Holds the register window as it was read beforehand, the requested
+/// configuration and the values that were (or would be) written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ChangeReport {
+    before: DeviceConfig,
+    requested: DeviceConfig,
+    after: DeviceConfig,
+    written: bool,
+}
+
+impl ChangeReport {
+    // Works out what applying `requested` on top of `before` writes: every
+    // writable register from `requested`, except that EELOCK and TARGET_ADR
+    // are carried over from the device and EEWRITE is cleared
+    #[cfg_attr(not(feature = "driver"), allow(dead_code))]
+    pub(crate) fn new(before: DeviceConfig, requested: DeviceConfig, written: bool) -> Self {
+        let mut after = before;
+
+        for offset in WRITABLE_OFFSETS {
+            after.registers[offset as usize] = requested.register(offset);
+        }
+
+        let mut reg1: GenericConfigurationRegister1 = requested.generic(1);
+        let before1: GenericConfigurationRegister1 = before.generic(1);
+        reg1.set_eelock(before1.eelock());
+        // A new address would take effect half way through the writes
+        reg1.set_target_adr(before1.target_adr());
+        after.registers[0x01] = reg1.into();
+
+        let mut reg6: GenericConfigurationRegister6 = requested.generic(6);
+        reg6.set_eewrite(false);
+        after.registers[0x06] = reg6.into();
+
+        Self {
+            before,
+            requested,
+            after,
+            written,
+        }
+    }
+
+    /// The register window as read before writing
+    pub fn before(&self) -> &DeviceConfig {
+        &self.before
+    }
+
+    /// The configuration that was passed in
+    pub fn requested(&self) -> &DeviceConfig {
+        &self.requested
+    }
+
+    /// The register window after writing, assuming every write was accepted
+    pub fn after(&self) -> &DeviceConfig {
+        &self.after
+    }
+
+    /// `false` for a report of [`crate::CDCx913::verify_config`], which only
+    /// compares
+    pub fn is_written(&self) -> bool {
+        self.written
+    }
+
+    /// Every register that was written, whether or not its value changed
+    pub fn writes(&self) -> impl Iterator<Item = RegisterChange> + '_ {
+        self.register_changes().filter(|_| self.written)
+    }
+
+    /// Every writable register whose configuration bits changed
+    pub fn changed_registers(&self) -> impl Iterator<Item = RegisterChange> + '_ {
+        self.register_changes().filter(|change| {
+            let mask = configuration_mask(change.offset);
+
+            change.before & mask != change.after & mask
+        })
+    }
+
+    /// Every configuration field whose value changed, by register
+    pub fn changed_fields(&self) -> impl Iterator<Item = FieldChange> + '_ {
+        self.changed_registers().flat_map(|change| {
+            let mask = configuration_mask(change.offset);
+
+            register_fields(change.offset)
+                .iter()
+                .filter(move |field| {
+                    field.mask() & mask != 0
+                        && field.value(change.before) != field.value(change.after)
+                })
+                .map(move |field| FieldChange {
+                    offset: change.offset,
+                    field: *field,
+                    before: field.decode(change.before),
+                    after: field.decode(change.after),
+                })
+        })
+    }
+
+    /// Fields of the requested configuration that weren't programmed as
+    /// requested, see [`crate::CDCx913::apply_config`] for which ones
+    pub fn skipped_fields(&self) -> impl Iterator<Item = SkippedField> + '_ {
+        WRITABLE_OFFSETS.into_iter().flat_map(|offset| {
+            let requested = self.requested.register(offset);
+            let written = self.after.register(offset);
+
+            register_fields(offset)
+                .iter()
+                .filter(move |field| field.value(requested) != field.value(written))
+                .map(move |field| SkippedField {
+                    offset,
+                    field: *field,
+                    requested: field.decode(requested),
+                    written: field.decode(written),
+                })
+        })
+    }
+
+    /// `true` if the device already held the configuration
+    pub fn is_unchanged(&self) -> bool {
+        self.changed_registers().next().is_none()
+    }
+
+    fn register_changes(&self) -> impl Iterator<Item = RegisterChange> + '_ {
+        WRITABLE_OFFSETS.into_iter().map(|offset| RegisterChange {
+            offset,
+            before: self.before.register(offset),
+            after: self.after.register(offset),
+        })
+    }
+}
+
+/// Spread spectrum modulation, decoded according to SSC1DC
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SscSetting {
+    Down(SscModulationAmountDown),
+    Center(SscModulationAmountCenter),
+}
+
+impl SscSetting {
+    /// Modulation amount in hundredths of a percent, the inverse of
+    /// [`crate::planner::ssc_center`] and [`crate::planner::ssc_down`]
+    pub const fn hundredths(&self) -> u32 {
+        match self {
+            Self::Down(amount) => match amount {
+                SscModulationAmountDown::Off => 0,
+                SscModulationAmountDown::Minus025Percent => 25,
+                SscModulationAmountDown::Minus05Percent => 50,
+                SscModulationAmountDown::Minus075Percent => 75,
+                SscModulationAmountDown::Minus1Percent => 100,
+                SscModulationAmountDown::Minus125Percent => 125,
+                SscModulationAmountDown::Minus15Percent => 150,
+                SscModulationAmountDown::Minus2Percent => 200,
+            },
+            Self::Center(amount) => match amount {
+                SscModulationAmountCenter::Off => 0,
+                SscModulationAmountCenter::PlusMinus025Percent => 25,
+                SscModulationAmountCenter::PlusMinus05Percent => 50,
+                SscModulationAmountCenter::PlusMinus075Percent => 75,
+                SscModulationAmountCenter::PlusMinus1Percent => 100,
+                SscModulationAmountCenter::PlusMinus125Percent => 125,
+                SscModulationAmountCenter::PlusMinus15Percent => 150,
+                SscModulationAmountCenter::PlusMinus2Percent => 200,
+            },
+        }
+    }
+}
+
+/// Where a clock along the path from the input to the outputs comes from,
+/// see [`ClockTree`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ClockSource {
+    /// The input clock directly
+    Input,
+    /// PLL1 running on the given settings bank
+    Pll1(Fs1Selection),
+    /// The input clock through the PLL1 bypass (MUX1)
+    Pll1Bypass,
+}
+
+/// One of the output dividers PDIV1..PDIV3 in a [`ClockTree`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DividerNode {
+    pub source: ClockSource,
+    /// 0 holds the divider in reset
+    pub divider: u16,
+    /// Only available when the input clock frequency is known and the
+    /// divider isn't in reset
+    pub hz: Option<u32>,
+}
+
+/// One of the outputs Y1..Y3 in a [`ClockTree`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct OutputNode {
+    /// Which PDIVn drives the output, 1 to 3. `None` if Y3 is routed
+    /// through the reserved multiplexer setting.
+    pub divider: Option<u8>,
+    pub state: OutputStateDefinition,
+    /// Frequency of the divider, whether or not the output is enabled
+    pub hz: Option<u32>,
+}
+
+/// The routing of the window's clocks for one control input combination:
+/// input clock, PLL1 or its bypass (MUX1), the output dividers with their
+/// sources (M1) and which divider drives each output (M2, M3). See
+/// [`DeviceConfig::clock_tree`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ClockTree {
+    pub state: ControlInputState,
+    pub input: Result<InputClockSelection, ReservedEncoding>,
+    pub reference_hz: Option<u32>,
+    /// [`ClockSource::Pll1`] with the bank FS1 picks, or
+    /// [`ClockSource::Pll1Bypass`]
+    pub pll: ClockSource,
+    /// Only available when PLL1 is in use and the input clock frequency is
+    /// known
+    pub vco_hz: Option<u32>,
+    /// PDIV1, PDIV2 and PDIV3
+    pub dividers: [DividerNode; 3],
+    /// Y1, Y2 and Y3
+    pub outputs: [OutputNode; 3],
+}
+
+/// What the device does for one control input combination, see
+/// [`DeviceConfig::effective_config`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct EffectiveConfig {
+    pub state: ControlInputState,
+    pub pll_bank: Fs1Selection,
+    pub pll_settings: PllSettings,
+    pub ssc: SscSetting,
+    pub y1: OutputStateDefinition,
+    pub y2y3: OutputStateDefinition,
+    /// Only available when the input clock frequency is known
+    pub frequencies: Option<OutputFrequencies>,
+}
+
+/// Frequencies in Hz, `None` where they can't be determined (e.g. a divider set
+/// to 0 or nonsensical PLL settings). Output frequencies are what the divider
+/// produces, regardless of whether the output is actually enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct OutputFrequencies {
+    pub vco_hz: Option<u32>,
+    pub y1_hz: Option<u32>,
+    pub y2_hz: Option<u32>,
+    pub y3_hz: Option<u32>,
+}
+
+/// VCO frequency implied by the N/R/Q/P settings of a PLL bank, together with
+/// the VCO range the bank is programmed for, see
+/// [`crate::CDCx913::vco_frequency`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct VcoFrequency {
+    pub vco_hz: u32,
+    /// The range code held by the settings
+    pub programmed_range: VcoRangeSelection,
+    /// The range code `vco_hz` falls into
+    pub expected_range: VcoRangeSelection,
+}
+
+impl VcoFrequency {
+    pub fn new(settings: PllSettings, reference_hz: u32) -> Option<Self> {
+        let vco_hz = settings.vco_frequency_hz(reference_hz)?;
+
+        Some(Self {
+            vco_hz,
+            programmed_range: settings.vco_range_selection(),
+            expected_range: vco_range(vco_hz),
+        })
+    }
+
+    /// `false` if the programmed range code disagrees with the VCO frequency,
+    /// in which case the VCO may not lock
+    pub fn range_matches(&self) -> bool {
+        self.programmed_range == self.expected_range
+    }
+}
+
+/// Reason a [`DeviceConfig`] was rejected by [`DeviceConfig::validate`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ValidationError {
+    /// The N/R/Q/P settings of the bank don't describe a valid divider
+    InvalidPllSettings(Fs1Selection),
+    /// The VCO of the bank runs outside of the range the variant supports
+    VcoOutOfRange { bank: Fs1Selection, vco_hz: u32 },
+    /// The VCO range bits of the bank don't match its VCO frequency
+    VcoRangeMismatch(Fs1Selection),
+    /// An enabled output runs faster than the variant supports
+    OutputTooFast { output: u8, hz: u32 },
+    /// The reference is outside of the [`InputRange`] of the selected input
+    ReferenceOutOfRange {
+        input: InputClockSelection,
+        reference_hz: u32,
+    },
+}
+
+/// A [`DeviceConfig`] together with what's needed to decode it, see
+/// [`DeviceConfig::decoded`]. Formats as one line per output, with
+/// [`core::fmt::Display`] and, with the `defmt` feature, [`defmt::Format`].
+#[derive(Debug, Clone, Copy)]
+pub struct DecodedConfig<'a> {
+    config: &'a DeviceConfig,
+    state: ControlInputState,
+    reference_hz: u32,
+}
+
+#[derive(Clone, Copy)]
+enum OutputSummary {
+    Off(OutputStateDefinition),
+    /// The divider is 0, which holds it in reset
+    Reset,
+    /// Y3 is routed through the reserved multiplexer setting
+    Reserved,
+    Running {
+        source: ClockSource,
+        divider: u16,
+        hz: Option<u32>,
+        ssc: Option<SscSetting>,
+    },
+}
+
+impl DecodedConfig<'_> {
+    fn vco(&self) -> Option<(Fs1Selection, Option<u32>)> {
+        let pll4: Pll1ConfigurationRegister4 = self.config.pll1(4);
+        let bank = self.config.effective_config(self.state, None).pll_bank;
+
+        (pll4.pll1_multiplexer() == Pll1Multiplexer::Pll1).then(|| {
+            (
+                bank,
+                self.config
+                    .pll1_settings(bank)
+                    .vco_frequency_hz(self.reference_hz),
+            )
+        })
+    }
+
+    fn outputs(&self) -> [OutputSummary; 3] {
+        let config = self.config;
+        let effective = config.effective_config(self.state, Some(self.reference_hz));
+        let frequencies = effective.frequencies;
+
+        let generic2: GenericConfigurationRegister2 = config.generic(2);
+        let generic3: GenericConfigurationRegister3 = config.generic(3);
+        let pll4: Pll1ConfigurationRegister4 = config.pll1(4);
+        let pll6: Pll1ConfigurationRegister6 = config.pll1(6);
+        let pll7: Pll1ConfigurationRegister7 = config.pll1(7);
+
+        let pll_source = match pll4.pll1_multiplexer() {
+            Pll1Multiplexer::Pll1 => ClockSource::Pll1(effective.pll_bank),
+            Pll1Multiplexer::Pll1Bypass => ClockSource::Pll1Bypass,
+        };
+        let pdiv1_source = match generic2.y1_clock_source() {
+            Y1ClockSource::InputClock => ClockSource::Input,
+            Y1ClockSource::Pll1Clock => pll_source,
+        };
+
+        let pdiv1 = (pdiv1_source, generic3.pdiv1_full_value(&generic2));
+        let pdiv2 = (pll_source, pll6.pdiv2() as u16);
+        let pdiv3 = (pll_source, pll7.pdiv3() as u16);
+
+        let y2 = match pll4.output_y2_multiplexer() {
+            OutputY2Multiplexer::Pdiv1 => Some(pdiv1),
+            OutputY2Multiplexer::Pdiv2 => Some(pdiv2),
+        };
+        let y3 = match pll4.output_y3_multiplexer() {
+            OutputY3Multiplexer::Pdiv1 => Some(pdiv1),
+            OutputY3Multiplexer::Pdiv2 => Some(pdiv2),
+            OutputY3Multiplexer::Pdiv3 => Some(pdiv3),
+            OutputY3Multiplexer::Reserved => None,
+        };
+
+        let summarize = |definition, path: Option<(ClockSource, u16)>, hz: Option<u32>| {
+            if definition != OutputStateDefinition::Enabled {
+                return OutputSummary::Off(definition);
+            }
+
+            match path {
+                None => OutputSummary::Reserved,
+                Some((_, 0)) => OutputSummary::Reset,
+                Some((source, divider)) => OutputSummary::Running {
+                    source,
+                    divider,
+                    hz,
+                    ssc: match source {
+                        ClockSource::Pll1(_) if effective.ssc.hundredths() != 0 => {
+                            Some(effective.ssc)
+                        }
+                        _ => None,
+                    },
+                },
+            }
+        };
+
+        [
+            summarize(
+                effective.y1,
+                Some(pdiv1),
+                frequencies.and_then(|frequencies| frequencies.y1_hz),
+            ),
+            summarize(
+                effective.y2y3,
+                y2,
+                frequencies.and_then(|frequencies| frequencies.y2_hz),
+            ),
+            summarize(
+                effective.y2y3,
+                y3,
+                frequencies.and_then(|frequencies| frequencies.y3_hz),
+            ),
+        ]
+    }
+}
+
+/// Splits a fixed-point `value` with `digits` fractional digits into its whole
+/// part, fractional part and the number of fractional digits left after
+/// dropping trailing zeros
+const fn split_decimal(value: u32, digits: usize) -> (u32, u32, usize) {
+    let scale = 10u32.pow(digits as u32);
+    let mut fraction = value % scale;
+    let mut digits = digits;
+
+    while digits > 0 && fraction.is_multiple_of(10) {
+        fraction /= 10;
+        digits -= 1;
+    }
+
+    (value / scale, fraction, digits)
+}
+
+/// A frequency in Hz, rendered in MHz without trailing zeros
+struct Megahertz(u32);
+
+impl Megahertz {
+    fn parts(&self) -> (u32, u32, usize) {
+        split_decimal(self.0, 6)
+    }
+}
+
+impl core::fmt::Display for Megahertz {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.parts() {
+            (whole, _, 0) => write!(f, "{whole} MHz"),
+            (whole, fraction, digits) => write!(f, "{whole}.{fraction:0digits$} MHz"),
+        }
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for Megahertz {
+    fn format(&self, f: defmt::Formatter) {
+        match self.parts() {
+            (whole, _, 0) => defmt::write!(f, "{} MHz", whole),
+            (whole, fraction, 1) => defmt::write!(f, "{}.{=u32:01} MHz", whole, fraction),
+            (whole, fraction, 2) => defmt::write!(f, "{}.{=u32:02} MHz", whole, fraction),
+            (whole, fraction, 3) => defmt::write!(f, "{}.{=u32:03} MHz", whole, fraction),
+            (whole, fraction, 4) => defmt::write!(f, "{}.{=u32:04} MHz", whole, fraction),
+            (whole, fraction, 5) => defmt::write!(f, "{}.{=u32:05} MHz", whole, fraction),
+            (whole, fraction, _) => defmt::write!(f, "{}.{=u32:06} MHz", whole, fraction),
+        }
+    }
+}
+
+impl ClockSource {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Input => "input",
+            Self::Pll1(Fs1Selection::Fvcxo0) => "PLL1_0",
+            Self::Pll1(Fs1Selection::Fvcxo1) => "PLL1_1",
+            Self::Pll1Bypass => "PLL1 bypass",
+        }
+    }
+}
+
+fn state_name(definition: OutputStateDefinition) -> &'static str {
+    match definition {
+        OutputStateDefinition::DevicePowerDown => "device power down",
+        OutputStateDefinition::Disabled3State => "disabled (3-state)",
+        OutputStateDefinition::DisabledLow => "disabled (low)",
+        OutputStateDefinition::Enabled => "enabled",
+    }
+}
+
+impl SscSetting {
+    fn kind_name(&self) -> &'static str {
+        match self {
+            Self::Down(_) => "down",
+            Self::Center(_) => "center",
+        }
+    }
+
+    fn sign(&self) -> &'static str {
+        match self {
+            Self::Down(_) => "-",
+            Self::Center(_) => "±",
+        }
+    }
+}
+
+impl core::fmt::Display for DecodedConfig<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let state = self.state as u8;
+
+        write!(
+            f,
+            "S2S1S0={state:03b}, reference {}",
+            Megahertz(self.reference_hz)
+        )?;
+        match self.vco() {
+            Some((bank, Some(vco_hz))) => write!(
+                f,
+                ", {} VCO {}",
+                ClockSource::Pll1(bank).name(),
+                Megahertz(vco_hz)
+            )?,
+            Some((bank, None)) => write!(f, ", {} invalid", ClockSource::Pll1(bank).name())?,
+            None => {}
+        }
+
+        for (index, output) in self.outputs().into_iter().enumerate() {
+            write!(f, "\nY{}: ", index + 1)?;
+
+            match output {
+                OutputSummary::Off(definition) => f.write_str(state_name(definition))?,
+                OutputSummary::Reset => f.write_str("divider in reset")?,
+                OutputSummary::Reserved => f.write_str("reserved multiplexer setting")?,
+                OutputSummary::Running {
+                    source,
+                    divider,
+                    hz,
+                    ssc,
+                } => {
+                    write!(f, "{} ÷{divider} = ", source.name())?;
+                    match hz {
+                        Some(hz) => write!(f, "{}", Megahertz(hz))?,
+                        None => f.write_str("unknown")?,
+                    }
+                    if let Some(ssc) = ssc {
+                        write!(f, ", SSC {}", ssc.sign())?;
+                        match split_decimal(ssc.hundredths(), 2) {
+                            (whole, _, 0) => write!(f, "{whole}")?,
+                            (whole, fraction, digits) => write!(f, "{whole}.{fraction:0digits$}")?,
+                        }
+                        write!(f, "% {}", ssc.kind_name())?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// One line per node, e.g. `PDIV2: PLL1_0 ÷4 = 45 MHz` and `Y2: PDIV2,
+/// enabled, 45 MHz`
+impl core::fmt::Display for ClockTree {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.input {
+            Ok(InputClockSelection::Xtal) => f.write_str("input: crystal")?,
+            Ok(InputClockSelection::Vcxo) => f.write_str("input: VCXO")?,
+            Ok(InputClockSelection::LvCmos) => f.write_str("input: LVCMOS")?,
+            Err(ReservedEncoding(raw)) => write!(f, "input: reserved({raw})")?,
+        }
+        if let Some(hz) = self.reference_hz {
+            write!(f, ", {}", Megahertz(hz))?;
+        }
+
+        write!(f, "\nPLL1: {}", self.pll.name())?;
+        if let Some(hz) = self.vco_hz {
+            write!(f, ", VCO {}", Megahertz(hz))?;
+        }
+
+        for (index, divider) in self.dividers.iter().enumerate() {
+            write!(
+                f,
+                "\nPDIV{}: {} ÷{}",
+                index + 1,
+                divider.source.name(),
+                divider.divider
+            )?;
+            match (divider.divider, divider.hz) {
+                (0, _) => f.write_str(" (reset)")?,
+                (_, Some(hz)) => write!(f, " = {}", Megahertz(hz))?,
+                (_, None) => {}
+            }
+        }
+
+        for (index, output) in self.outputs.iter().enumerate() {
+            write!(f, "\nY{}: ", index + 1)?;
+            match output.divider {
+                Some(n) => write!(f, "PDIV{n}")?,
+                None => f.write_str("reserved")?,
+            }
+            write!(f, ", {}", state_name(output.state))?;
+            if let Some(hz) = output.hz {
+                write!(f, ", {}", Megahertz(hz))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for DecodedConfig<'_> {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(
+            f,
+            "S2S1S0={=u8:03b}, reference {}",
+            self.state as u8,
+            Megahertz(self.reference_hz)
+        );
+        match self.vco() {
+            Some((bank, Some(vco_hz))) => defmt::write!(
+                f,
+                ", {=str} VCO {}",
+                ClockSource::Pll1(bank).name(),
+                Megahertz(vco_hz)
+            ),
+            Some((bank, None)) => {
+                defmt::write!(f, ", {=str} invalid", ClockSource::Pll1(bank).name())
+            }
+            None => {}
+        }
+
+        for (index, output) in self.outputs().into_iter().enumerate() {
+            defmt::write!(f, "\nY{=usize}: ", index + 1);
+
+            match output {
+                OutputSummary::Off(definition) => {
+                    defmt::write!(f, "{=str}", state_name(definition))
+                }
+                OutputSummary::Reset => defmt::write!(f, "divider in reset"),
+                OutputSummary::Reserved => defmt::write!(f, "reserved multiplexer setting"),
+                OutputSummary::Running {
+                    source,
+                    divider,
+                    hz,
+                    ssc,
+                } => {
+                    defmt::write!(f, "{=str} ÷{=u16} = ", source.name(), divider);
+                    match hz {
+                        Some(hz) => defmt::write!(f, "{}", Megahertz(hz)),
+                        None => defmt::write!(f, "unknown"),
+                    }
+                    if let Some(ssc) = ssc {
+                        defmt::write!(f, ", SSC {=str}", ssc.sign());
+                        match split_decimal(ssc.hundredths(), 2) {
+                            (whole, _, 0) => defmt::write!(f, "{=u32}", whole),
+                            (whole, fraction, 1) => {
+                                defmt::write!(f, "{=u32}.{=u32:01}", whole, fraction)
+                            }
+                            (whole, fraction, _) => {
+                                defmt::write!(f, "{=u32}.{=u32:02}", whole, fraction)
+                            }
+                        }
+                        defmt::write!(f, "% {=str}", ssc.kind_name());
+                    }
+                }
+            }
+        }
+    }
+}
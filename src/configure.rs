@@ -0,0 +1,382 @@
+//! Fluent configuration of several settings at once, see
+//! [`crate::CDCx913::configure`]:
+//!
+//! ```ignore
+//! driver
+//!     .configure()
+//!     .input(InputClockSelection::LvCmos)
+//!     .y2(|y| y.from_pll().divider(4).enabled())
+//!     .ssc(SscSetting::Center(SscModulationAmountCenter::PlusMinus05Percent))
+//!     .apply()
+//!     .await?;
+//! ```
+//!
+//! Nothing is sent before [`Configure::apply`], which reads the device once,
+//! works out the new register contents and writes only the registers that
+//! change. Settings that aren't given stay as they are on the device.
+
+use embedded_hal_async::i2c::I2c;
+
+use crate::{
+    CDCx913, Error, Register,
+    config::{DeviceConfig, REGISTER_WINDOW_LEN, WRITABLE_OFFSETS},
+    family::Device,
+    output::DisableMode,
+    planner::{PDIV_MAX, PDIV1_MAX},
+    registers::{
+        OutputStateDefinition, ReservedEncoding,
+        generic_configuration::{
+            CrystalLoadCapacitance, GenericConfigurationRegister1, GenericConfigurationRegister2,
+            GenericConfigurationRegister3, GenericConfigurationRegister5, InputClockSelection,
+        },
+        pll1_configuration::{
+            Pll1ConfigurationRegister4, Pll1ConfigurationRegister6, Pll1ConfigurationRegister7,
+            Pll1Multiplexer,
+        },
+    },
+    routing::{Divider, Route, Routing, RoutingError, Source},
+    timing::NoDelay,
+};
+#[cfg(feature = "ssc")]
+use crate::{
+    config::SscSetting,
+    registers::pll1_configuration::{SscDownCenterSelection, pack_ssc1_selections},
+    u3,
+};
+
+/// Changes to one output staged by [`Configure`]. Anything not set stays as it
+/// is on the device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct OutputSettings {
+    source: Option<Source>,
+    through: Option<Divider>,
+    divider: Option<u16>,
+    state: Option<OutputStateDefinition>,
+}
+
+impl OutputSettings {
+    /// Feeds the output from the input clock directly, which only PDIV1 can do
+    pub fn from_input(self) -> Self {
+        self.from(Source::Input)
+    }
+
+    pub fn from_pll(self) -> Self {
+        self.from(Source::Pll1)
+    }
+
+    pub fn from_pll_bypass(self) -> Self {
+        self.from(Source::Pll1Bypass)
+    }
+
+    pub fn from(mut self, source: Source) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    /// Routes the output through `divider`. Giving only a source routes Y2
+    /// and Y3 through their own dividers PDIV2 and PDIV3.
+    pub fn through(mut self, divider: Divider) -> Self {
+        self.through = Some(divider);
+        self
+    }
+
+    /// Value of the divider the output ends up behind, 0 holds it in reset.
+    /// Values that don't fit are handled according to the
+    /// [`crate::ValidationPolicy`].
+    pub fn divider(mut self, value: u16) -> Self {
+        self.divider = Some(value);
+        self
+    }
+
+    /// Enables the output whatever the state of the control inputs
+    pub fn enabled(self) -> Self {
+        self.state(OutputStateDefinition::Enabled)
+    }
+
+    /// Disables the output whatever the state of the control inputs
+    pub fn disabled(self, mode: DisableMode) -> Self {
+        self.state(mode.into())
+    }
+
+    /// Uses `state` for both state definitions, so the state selections no
+    /// longer matter. Y2 and Y3 share their definitions, so setting it for one
+    /// sets it for the other.
+    pub fn state(mut self, state: OutputStateDefinition) -> Self {
+        self.state = Some(state);
+        self
+    }
+}
+
+/// Settings staged for [`Self::apply`], see the module documentation
+pub struct Configure<'a, I2C, D, DELAY = NoDelay> {
+    driver: &'a mut CDCx913<I2C, D, DELAY>,
+    input: Option<InputClockSelection>,
+    crystal_load_capacitance: Option<CrystalLoadCapacitance>,
+    outputs: [OutputSettings; 3],
+    #[cfg(feature = "ssc")]
+    ssc: Option<SscSetting>,
+}
+
+impl<'a, I2C: I2c, D: Device, DELAY> Configure<'a, I2C, D, DELAY> {
+    pub(crate) fn new(driver: &'a mut CDCx913<I2C, D, DELAY>) -> Self {
+        Self {
+            driver,
+            input: None,
+            crystal_load_capacitance: None,
+            outputs: [OutputSettings::default(); 3],
+            #[cfg(feature = "ssc")]
+            ssc: None,
+        }
+    }
+
+    pub fn input(mut self, input: InputClockSelection) -> Self {
+        self.input = Some(input);
+        self
+    }
+
+    pub fn crystal_load_capacitance(mut self, capacitance: CrystalLoadCapacitance) -> Self {
+        self.crystal_load_capacitance = Some(capacitance);
+        self
+    }
+
+    pub fn y1(self, f: impl FnOnce(OutputSettings) -> OutputSettings) -> Self {
+        self.output(0, f)
+    }
+
+    pub fn y2(self, f: impl FnOnce(OutputSettings) -> OutputSettings) -> Self {
+        self.output(1, f)
+    }
+
+    pub fn y3(self, f: impl FnOnce(OutputSettings) -> OutputSettings) -> Self {
+        self.output(2, f)
+    }
+
+    fn output(mut self, index: usize, f: impl FnOnce(OutputSettings) -> OutputSettings) -> Self {
+        self.outputs[index] = f(self.outputs[index]);
+        self
+    }
+
+    /// Spread spectrum modulation applied in every control input state
+    #[cfg(feature = "ssc")]
+    pub fn ssc(mut self, ssc: SscSetting) -> Self {
+        self.ssc = Some(ssc);
+        self
+    }
+
+    /// Reads the device, applies the staged settings on top and writes every
+    /// run of consecutive registers that changed with one block write. Fails
+    /// with [`Error::InvalidRouting`], [`Error::SharedOutputState`],
+    /// [`Error::DividerOutOfRange`] or [`Error::ReferenceOutOfRange`] before
+    /// anything is written if the settings contradict each other, the routing
+    /// left on the device or the reference frequency. As with
+    /// [`CDCx913::apply_config`], MUX1 isn't held to [`crate::Sequencing`],
+    /// and a dropped future can stop between two block writes.
+    pub async fn apply(mut self) -> Result<(), Error<I2C::Error>> {
+        let before = DeviceConfig::from_bytes(
+            self.driver
+                .read_block::<REGISTER_WINDOW_LEN>(Register::GenericConfiguration as u8)
+                .await?,
+        );
+        let after = self.stage(before)?;
+
+        let mut changed = WRITABLE_OFFSETS
+            .into_iter()
+            .filter(|&offset| before.register(offset) != after.register(offset))
+            .peekable();
+
+        while let Some(first) = changed.next() {
+            let mut last = first;
+            while changed.next_if_eq(&(last + 1)).is_some() {
+                last += 1;
+            }
+
+            self.driver
+                .write_block(first, &after.as_bytes()[first as usize..=last as usize])
+                .await?;
+        }
+
+        #[cfg(feature = "ssc")]
+        self.driver.stash_ssc_amounts(&after);
+
+        Ok(())
+    }
+
+    // Applies the staged settings to `before`
+    fn stage(&mut self, before: DeviceConfig) -> Result<DeviceConfig, Error<I2C::Error>> {
+        let generic = |index| Register::GenericConfiguration as u8 + index;
+        let pll1 = |index| Register::Pll1Configuration as u8 + index;
+        let mut after = before;
+
+        if let Some(input) = self.input {
+            self.driver.check_reference(input)?;
+            after.modify_register(generic(1), |reg: &mut GenericConfigurationRegister1| {
+                reg.set_input_clock_selection(input)
+            });
+        }
+        if let Some(capacitance) = self.crystal_load_capacitance {
+            after.modify_register(generic(5), |reg: &mut GenericConfigurationRegister5| {
+                reg.set_crystal_load_capacitance(capacitance)
+            });
+        }
+
+        let [y1, y2, y3] = self.outputs;
+
+        if [y1, y2, y3].iter().any(|output| {
+            output.source.is_some() || output.through.is_some() || output.divider.is_some()
+        }) {
+            let routing = self.routing(&before)?;
+            let settings = routing.mux_settings().map_err(Error::InvalidRouting)?;
+
+            after.modify_register(generic(2), |reg: &mut GenericConfigurationRegister2| {
+                reg.set_y1_clock_source(settings.m1)
+            });
+            after.modify_register(pll1(4), |reg: &mut Pll1ConfigurationRegister4| {
+                if let Some(mux1) = settings.mux1 {
+                    reg.set_pll1_multiplexer(mux1);
+                }
+                reg.set_output_y2_multiplexer(settings.m2);
+                reg.set_output_y3_source(settings.m3);
+            });
+
+            // Requested value of each divider, by divider
+            let mut values: [Option<u16>; 3] = [None; 3];
+            for (divider, value) in [
+                (Divider::Pdiv1, y1.divider),
+                (routing.y2.divider, y2.divider),
+                (routing.y3.divider, y3.divider),
+            ] {
+                let Some(value) = value else { continue };
+
+                match &mut values[divider as usize] {
+                    Some(other) if *other != value => {
+                        return Err(Error::InvalidRouting(RoutingError::DividerConflict(
+                            divider,
+                        )));
+                    }
+                    slot => *slot = Some(value),
+                }
+            }
+
+            if let Some(value) = values[0] {
+                let max = PDIV1_MAX;
+                let value = self.driver.check_value(
+                    value <= max,
+                    value.min(max),
+                    Error::DividerOutOfRange,
+                )?;
+
+                after.modify_register(generic(2), |reg: &mut GenericConfigurationRegister2| {
+                    reg.set_pdiv1_9_8((value >> 8) as _)
+                });
+                after.modify_register(generic(3), |reg: &mut GenericConfigurationRegister3| {
+                    reg.set_pdiv1_7_0((value & 0xFF) as _)
+                });
+            }
+            for (index, value) in values.into_iter().enumerate().skip(1) {
+                let Some(value) = value else { continue };
+
+                let max = PDIV_MAX as u16;
+                let value = self.driver.check_value(
+                    value <= max,
+                    value.min(max) as u8,
+                    Error::DividerOutOfRange,
+                )?;
+
+                if index == 1 {
+                    after.modify_register(pll1(6), |reg: &mut Pll1ConfigurationRegister6| {
+                        reg.set_pdiv2(value)
+                    });
+                } else {
+                    after.modify_register(pll1(7), |reg: &mut Pll1ConfigurationRegister7| {
+                        reg.set_pdiv3(value)
+                    });
+                }
+            }
+        }
+
+        if let Some(state) = y1.state {
+            after.modify_register(generic(2), |reg: &mut GenericConfigurationRegister2| {
+                reg.set_y1_state0_definition(state);
+                reg.set_y1_state1_definition(state);
+            });
+        }
+        let y2y3 = match (y2.state, y3.state) {
+            (Some(y2), Some(y3)) if y2 != y3 => return Err(Error::SharedOutputState),
+            (Some(state), _) | (None, Some(state)) => Some(state),
+            (None, None) => None,
+        };
+        if let Some(state) = y2y3 {
+            after.modify_register(pll1(4), |reg: &mut Pll1ConfigurationRegister4| {
+                reg.set_y2y3_state0_definition(state);
+                reg.set_y2y3_state1_definition(state);
+            });
+        }
+
+        #[cfg(feature = "ssc")]
+        if let Some(ssc) = self.ssc {
+            let (selection, code) = match ssc {
+                SscSetting::Down(amount) => (SscDownCenterSelection::Down, amount as u8),
+                SscSetting::Center(amount) => (SscDownCenterSelection::Center, amount as u8),
+            };
+
+            after.modify_register(pll1(6), |reg: &mut Pll1ConfigurationRegister6| {
+                reg.set_pll1_ssc_down_center_selection(selection)
+            });
+            for (index, byte) in pack_ssc1_selections([u3::new(code); 8])
+                .into_iter()
+                .enumerate()
+            {
+                after.modify_register(pll1(index as u8), |reg: &mut u8| *reg = byte);
+            }
+        }
+
+        Ok(after)
+    }
+
+    // Routing with the staged sources and dividers, the rest as on the device.
+    // An output given only a source goes through its own divider.
+    fn routing(&self, before: &DeviceConfig) -> Result<Routing, Error<I2C::Error>> {
+        let current = Routing::from_config(before)
+            .map_err(|ReservedEncoding(raw)| Error::ReservedEncoding(raw))?;
+        let mux1 = match Pll1ConfigurationRegister4::from(
+            before.register(Register::Pll1Configuration as u8 + 4),
+        )
+        .pll1_multiplexer()
+        {
+            Pll1Multiplexer::Pll1 => Source::Pll1,
+            Pll1Multiplexer::Pll1Bypass => Source::Pll1Bypass,
+        };
+
+        let [y1, y2, y3] = self.outputs;
+        if let Some(divider) = y1.through
+            && divider != Divider::Pdiv1
+        {
+            return Err(Error::InvalidRouting(RoutingError::Unreachable {
+                output: 1,
+                divider,
+            }));
+        }
+
+        let y1_source = y1.source.unwrap_or(current.y1);
+        let route = |settings: OutputSettings, own: Divider, current: Route| {
+            let divider = match (settings.through, settings.source) {
+                (Some(divider), _) => divider,
+                (None, Some(_)) => own,
+                (None, None) => current.divider,
+            };
+            let source = settings.source.unwrap_or(match divider {
+                Divider::Pdiv1 => y1_source,
+                _ => mux1,
+            });
+
+            Route::new(source, divider)
+        };
+
+        Ok(Routing {
+            y1: y1_source,
+            y2: route(y2, Divider::Pdiv2, current.y2),
+            y3: route(y3, Divider::Pdiv3, current.y3),
+        })
+    }
+}
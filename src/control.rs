@@ -0,0 +1,178 @@
+use arbitrary_int::u3;
+#[cfg(feature = "driver")]
+use embedded_hal::digital::OutputPin;
+
+use crate::registers::{OutputStateSelection, pll1_configuration::Fs1Selection};
+
+/// One of the eight combinations of the S2/S1/S0 control inputs. The
+/// per-control-input registers (FS1_x, SSC1_x, Y1_x, Y2Y3_x) hold one entry for
+/// each of these, with `x` being the value of `S2 S1 S0` read as a binary
+/// number. Every API indexed by control input takes this type, or anything
+/// converting into it such as a raw [`u3`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[repr(u8)]
+pub enum ControlInputState {
+    S000 = 0b000,
+    S001 = 0b001,
+    S010 = 0b010,
+    S011 = 0b011,
+    S100 = 0b100,
+    S101 = 0b101,
+    S110 = 0b110,
+    S111 = 0b111,
+}
+
+impl ControlInputState {
+    pub const ALL: [Self; 8] = [
+        Self::S000,
+        Self::S001,
+        Self::S010,
+        Self::S011,
+        Self::S100,
+        Self::S101,
+        Self::S110,
+        Self::S111,
+    ];
+
+    pub const fn s0(self) -> bool {
+        self as u8 & 0b001 != 0
+    }
+
+    pub const fn s1(self) -> bool {
+        self as u8 & 0b010 != 0
+    }
+
+    pub const fn s2(self) -> bool {
+        self as u8 & 0b100 != 0
+    }
+
+    pub const fn index(self) -> u3 {
+        u3::new(self as u8)
+    }
+}
+
+impl From<u3> for ControlInputState {
+    fn from(value: u3) -> Self {
+        match value.value() {
+            0b000 => Self::S000,
+            0b001 => Self::S001,
+            0b010 => Self::S010,
+            0b011 => Self::S011,
+            0b100 => Self::S100,
+            0b101 => Self::S101,
+            0b110 => Self::S110,
+            _ => Self::S111,
+        }
+    }
+}
+
+impl From<ControlInputState> for u3 {
+    fn from(value: ControlInputState) -> Self {
+        value.index()
+    }
+}
+
+/// The S0, S1 and S2 control pins, driven from GPIOs. Only meaningful once
+/// SPICON selects [`crate::registers::generic_configuration::SerialInterfacePinMode::ControlS1S2`],
+/// at which point the pins pick which of the eight truth table entries
+/// programmed through the driver is active.
+#[cfg(feature = "driver")]
+pub struct ControlPins<S0, S1, S2> {
+    s0: S0,
+    s1: S1,
+    s2: S2,
+}
+
+#[cfg(feature = "driver")]
+impl<S0, S1, S2> ControlPins<S0, S1, S2>
+where
+    S0: OutputPin,
+    S1: OutputPin<Error = S0::Error>,
+    S2: OutputPin<Error = S0::Error>,
+{
+    pub fn new(s0: S0, s1: S1, s2: S2) -> Self {
+        Self { s0, s1, s2 }
+    }
+
+    /// Drives the pins to the given combination
+    pub fn select(&mut self, state: ControlInputState) -> Result<(), S0::Error> {
+        self.s0.set_state(state.s0().into())?;
+        self.s1.set_state(state.s1().into())?;
+        self.s2.set_state(state.s2().into())
+    }
+
+    pub fn release(self) -> (S0, S1, S2) {
+        (self.s0, self.s1, self.s2)
+    }
+}
+
+/// Everything the device does differently for one control input combination,
+/// gathered from the FS1_x, SSC1_x, Y1_x and Y2Y3_x registers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ControlProfile {
+    /// Which PLL1 settings bank (PLL1_0 or PLL1_1) is used
+    pub pll_bank: Fs1Selection,
+    /// Raw SSC1_x modulation code, interpreted as down or center spread
+    /// depending on SSC1DC
+    pub ssc: u3,
+    /// Which of Y1_ST0/Y1_ST1 applies to Y1
+    pub y1: OutputStateSelection,
+    /// Which of Y2Y3_ST0/Y2Y3_ST1 applies to Y2 and Y3
+    pub y2y3: OutputStateSelection,
+}
+
+impl Default for ControlProfile {
+    fn default() -> Self {
+        Self {
+            pll_bank: Fs1Selection::Fvcxo0,
+            ssc: u3::new(0),
+            y1: OutputStateSelection::State0,
+            y2y3: OutputStateSelection::State0,
+        }
+    }
+}
+
+/// The full control input truth table: one [`ControlProfile`] per
+/// [`ControlInputState`]. Written and read in one go with
+/// [`crate::CDCx913::set_control_profiles`] and
+/// [`crate::CDCx913::control_profiles`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ControlProfiles {
+    profiles: [ControlProfile; 8],
+}
+
+impl ControlProfiles {
+    /// Starts out with `profile` for every control input combination
+    pub fn new(profile: ControlProfile) -> Self {
+        Self {
+            profiles: [profile; 8],
+        }
+    }
+
+    /// Replaces the profile for one control input combination
+    pub fn with(mut self, state: ControlInputState, profile: ControlProfile) -> Self {
+        self.set(state, profile);
+        self
+    }
+
+    pub fn set(&mut self, state: ControlInputState, profile: ControlProfile) {
+        self.profiles[state as usize] = profile;
+    }
+
+    pub fn get(&self, state: ControlInputState) -> ControlProfile {
+        self.profiles[state as usize]
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (ControlInputState, ControlProfile)> + '_ {
+        ControlInputState::ALL.into_iter().zip(self.profiles)
+    }
+}
+
+impl Default for ControlProfiles {
+    fn default() -> Self {
+        Self::new(ControlProfile::default())
+    }
+}
@@ -0,0 +1,95 @@
+//! Matching the on-chip load capacitors to a crystal. A crystal only runs at
+//! its nominal frequency with the load capacitance it was specified for (CL
+//! in its datasheet). The crystal sees XCSEL plus what the XIN/XOUT pins and
+//! the board add, so picking XCSEL = CL leaves it overloaded and running
+//! slow, typically by tens of ppm.
+//!
+//! Capacitances are in femtofarads so fractional picofarads, e.g. a 12.5 pF
+//! crystal, stay exact without floating point.
+
+use crate::registers::generic_configuration::CrystalLoadCapacitance;
+
+/// Capacitance the XIN/XOUT pins contribute on their own, typical value
+pub const DEVICE_PARASITIC_CAPACITANCE_FF: u32 = 1_500;
+
+/// The parts of a crystal and its layout that decide the load capacitance
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CrystalSpec {
+    pub frequency_hz: u32,
+    /// Load capacitance the crystal is specified for (CL)
+    pub load_capacitance_ff: u32,
+    /// Stray capacitance of the pads and traces across the crystal. Around
+    /// 1-3 pF on a compact layout.
+    pub board_capacitance_ff: u32,
+}
+
+impl CrystalSpec {
+    /// A crystal on a board without stray capacitance, see
+    /// [`Self::with_board_capacitance_ff`]
+    pub const fn new(frequency_hz: u32, load_capacitance_ff: u32) -> Self {
+        Self {
+            frequency_hz,
+            load_capacitance_ff,
+            board_capacitance_ff: 0,
+        }
+    }
+
+    pub const fn with_board_capacitance_ff(mut self, board_capacitance_ff: u32) -> Self {
+        self.board_capacitance_ff = board_capacitance_ff;
+        self
+    }
+
+    /// Load capacitance the crystal sees with `capacitance` programmed
+    pub const fn total_load_ff(&self, capacitance: CrystalLoadCapacitance) -> u32 {
+        capacitance.pf() as u32 * 1_000
+            + DEVICE_PARASITIC_CAPACITANCE_FF
+            + self.board_capacitance_ff
+    }
+
+    /// The XCSEL setting that brings the total load closest to CL, rounding
+    /// half a picofarad up. A CL below what the pins and the board add on
+    /// their own gives 0 pF, one beyond reach 20 pF; the match tells how far
+    /// off the result is either way.
+    pub const fn recommended_load_capacitance(&self) -> LoadCapacitanceMatch {
+        let fixed_ff = DEVICE_PARASITIC_CAPACITANCE_FF + self.board_capacitance_ff;
+        let pf = (self.load_capacitance_ff.saturating_sub(fixed_ff) + 500) / 1_000;
+        let capacitance = if pf > CrystalLoadCapacitance::MAX_PF as u32 {
+            CrystalLoadCapacitance::from_xcsel(CrystalLoadCapacitance::MAX_PF)
+        } else {
+            CrystalLoadCapacitance::from_xcsel(pf as u8)
+        };
+
+        LoadCapacitanceMatch {
+            capacitance,
+            total_load_ff: self.total_load_ff(capacitance),
+            specified_load_ff: self.load_capacitance_ff,
+        }
+    }
+}
+
+/// XCSEL setting for a [`CrystalSpec`], along with the load the crystal ends
+/// up with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LoadCapacitanceMatch {
+    pub capacitance: CrystalLoadCapacitance,
+    /// XCSEL plus the pins and the board
+    pub total_load_ff: u32,
+    /// CL of the crystal
+    pub specified_load_ff: u32,
+}
+
+impl LoadCapacitanceMatch {
+    /// How far the load is off CL, positive when the crystal is overloaded
+    /// and runs slow
+    pub const fn error_ff(&self) -> i32 {
+        self.total_load_ff as i32 - self.specified_load_ff as i32
+    }
+
+    /// Whether the 1 pF steps of XCSEL got within half a step of CL, which
+    /// fails only for a CL out of reach
+    pub const fn is_within_step(&self) -> bool {
+        self.error_ff().unsigned_abs() <= 500
+    }
+}
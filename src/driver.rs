@@ -0,0 +1,2676 @@
+//! The async I2C driver, a thin layer over the register model in
+//! [`crate::registers`], [`crate::config`] and [`crate::planner`].
+
+use arbitrary_int::{u2, u3, u4, u7, u10};
+use core::marker::PhantomData;
+
+use embedded_hal_async::{
+    delay::DelayNs,
+    i2c::{I2c, Operation},
+};
+
+use crate::{
+    Error, Register,
+    config::{
+        ChangeReport, ClockTree, DeviceConfig, EffectiveConfig, REGISTER_WINDOW_LEN, VcoFrequency,
+        WRITABLE_OFFSETS,
+    },
+    configure::Configure,
+    control::{ControlInputState, ControlProfile, ControlProfiles},
+    crystal::{CrystalSpec, LoadCapacitanceMatch},
+    family::{Cdce913, Cdce925, Device},
+    i2c::{CommandCode, MAX_BATCHED_READS, OpCode},
+    output::{DisableMode, Output, OutputConfig, OutputMask, ShutdownOptions},
+    planner::{InputRange, SupplyVoltage},
+    pll_block::PllBlock,
+    read_only::ReadOnlyCdcx913,
+    register_interface::RegisterInterface,
+    registers::{
+        OutputStateDefinition, OutputStateSelection, Pll1Register, Pll1RegisterIndex,
+        RegisterOffset, ReservedEncoding, TypedRegister,
+        generic_configuration::{
+            CrystalLoadCapacitance, DeviceIdentification, EepromProgrammingStatus,
+            GenericConfigurationRegister0, GenericConfigurationRegister1,
+            GenericConfigurationRegister2, GenericConfigurationRegister3,
+            GenericConfigurationRegister4, GenericConfigurationRegister5,
+            GenericConfigurationRegister6, InputClockSelection, SerialInterfacePinMode,
+            Y1ClockSource,
+        },
+        pll1_configuration::{
+            Fs1Selection, OutputY2Multiplexer, OutputY3Multiplexer, OutputY3Source,
+            Pll1ConfigurationRegister0, Pll1ConfigurationRegister1, Pll1ConfigurationRegister2,
+            Pll1ConfigurationRegister3, Pll1ConfigurationRegister4, Pll1ConfigurationRegister5,
+            Pll1ConfigurationRegister6, Pll1ConfigurationRegister7, Pll1ConfigurationRegisterB,
+            Pll1ConfigurationRegisterF, Pll1Multiplexer, PllSettings, VcoRangeSelection,
+            pack_ssc1_selections, unpack_ssc1_selections,
+        },
+    },
+    routing::Routing,
+    timing::{NoDelay, POLL_INTERVAL_MS, STABILIZATION_TIME_US},
+};
+
+#[cfg(feature = "dyn-i2c")]
+use crate::dyn_i2c::{DynI2c, ErasedI2c};
+#[cfg(feature = "planner")]
+use crate::planner::{Limits, PllPlan, plan, plan_nearest};
+#[cfg(feature = "ssc")]
+use crate::registers::pll1_configuration::{
+    SscDownCenterSelection, SscModulationAmountCenter, SscModulationAmountDown,
+};
+#[cfg(feature = "eeprom")]
+use crate::{
+    config::ConfigDiff,
+    eeprom::{
+        EepromLockConfirmation, EepromWriteHandle, ProvisionOptions, ProvisionOutcome,
+        ProvisionReport,
+    },
+    timing::{EEPROM_POLL_INTERVAL_MS, EEPROM_WRITE_TIMEOUT_MS},
+};
+
+// Offsets of the PLL1_0 and PLL1_1 settings words
+const PLL1_0_OFFSET: u8 = Register::Pll1Configuration as u8 + 0x8;
+const PLL1_1_OFFSET: u8 = Register::Pll1Configuration as u8 + 0xC;
+
+// Largest byte count the 7 bits of BCOUNT can hold
+const MAX_BCOUNT: usize = 0x7F;
+
+/// Driver for a device of the family `D`. Setters that touch several
+/// registers stage them and commit them with one block write, so dropping the
+/// future part-way, e.g. in `select!` or on a timeout, leaves the registers
+/// either untouched or fully updated. Whole-device operations such as
+/// [`Self::apply_config`] document where they can stop.
+pub struct CDCx913<I2C, D = Cdce913, DELAY = NoDelay> {
+    i2c: I2C,
+    address: u8,
+    _device: PhantomData<D>,
+    // Set once SPICON has been switched to control pin mode. From then on the
+    // serial interface pins are S1/S2 inputs and any further bus traffic would
+    // reconfigure the outputs instead of talking to the device.
+    serial_interface_released: bool,
+    sequencing: Sequencing,
+    validation: ValidationPolicy,
+    // Set when a setter clamped a value under ValidationPolicy::Lenient,
+    // cleared by take_clamped
+    clamped: bool,
+    reference_hz: Option<u32>,
+    supply_voltage: Option<SupplyVoltage>,
+    delay: DELAY,
+    // Last non-zero SSC modulation code seen per control input state, which
+    // re-enabling spread spectrum restores. 0 if none was seen yet.
+    #[cfg(feature = "ssc")]
+    ssc_stash: [u8; 8],
+    // BCOUNT as last read or written: block reads have to fetch exactly this
+    // many registers. None until read, and again after leaving power-down,
+    // which may have reloaded it from the EEPROM.
+    bcount: Option<u8>,
+    #[cfg(feature = "stats")]
+    stats: BusStats,
+}
+
+/// How careful the driver is about the order in which the device gets
+/// reconfigured, see [`CDCx913::set_sequencing`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Sequencing {
+    /// Every write is issued as requested
+    #[default]
+    Relaxed,
+    /// Writes to the PLL1 N/R/Q/P settings are rejected with
+    /// [`Error::OutputsEnabled`] while any output fed by PLL1 is enabled, since
+    /// retuning a running PLL glitches its outputs
+    Strict,
+}
+
+/// What setters taking a plain number do with a value the field can't hold,
+/// see [`CDCx913::set_validation_policy`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ValidationPolicy {
+    /// The setter fails with [`Error::ValueOutOfRange`] or
+    /// [`Error::DividerOutOfRange`] before anything is written
+    #[default]
+    Strict,
+    /// The value is clamped to the nearest one the field can hold below it, a
+    /// warning is logged and [`CDCx913::take_clamped`] reports it
+    Lenient,
+}
+
+/// Bus traffic caused by a driver since it was created or
+/// [`CDCx913::reset_stats`] was last called. All counters wrap around.
+#[cfg(feature = "stats")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BusStats {
+    /// I2C transactions issued, successful or not
+    pub transactions: u32,
+    /// Bytes on the bus, counting command codes, byte counts and data in both
+    /// directions but not the address bytes
+    pub bytes: u32,
+    /// EEPROM status reads that found the write still in progress and had to
+    /// be repeated
+    pub retries: u32,
+    /// EEPROM commits that didn't read back as expected, see
+    /// [`CDCx913::verify_eeprom_commit`]
+    pub verify_failures: u32,
+}
+
+macro_rules! read {
+    ($self:expr, $register:ident, $offset:expr, $fn:expr) => {
+        paste::paste! {
+            $self.with::<[<$register Register $offset>], _>(
+                Register::$register as u8 + $offset,
+                $fn
+            )
+            .await
+        }
+    };
+}
+
+macro_rules! modify {
+    ($self:expr, $register:ident, $offset:expr, $fn:expr) => {
+        paste::paste! {
+            $self.modify_byte_unchecked::<[<$register Register $offset>], _>(
+                Register::$register as u8 + $offset,
+                $fn
+            )
+            .await
+        }
+    };
+}
+
+impl<I2C: I2c> CDCx913<I2C> {
+    pub fn new(i2c: I2C) -> Self {
+        Self::for_device(i2c, Cdce913)
+    }
+
+    /// Creates a driver for a device known to hold `config` already, e.g.
+    /// loaded from its EEPROM at power-up, without touching the bus. See
+    /// [`Self::assuming_config`].
+    pub fn assume_config(i2c: I2C, config: &DeviceConfig) -> Self {
+        Self::new(i2c).assuming_config(config)
+    }
+}
+
+impl<I2C: I2c, DELAY: DelayNs> CDCx913<I2C, Cdce913, DELAY> {
+    /// Creates a driver that keeps `delay` for everything that has to wait for
+    /// the device, such as EEPROM writes. Other family members take
+    /// `CDCx913::for_device(i2c, device).with_delay(delay)`.
+    pub fn new_with_delay(i2c: I2C, delay: DELAY) -> Self {
+        CDCx913::new(i2c).with_delay(delay)
+    }
+}
+
+#[cfg(feature = "dyn-i2c")]
+impl<'a> CDCx913<DynI2c<'a>> {
+    /// Creates a driver on a type-erased bus, so drivers on different bus
+    /// types share one copy of the driver code, see [`crate::dyn_i2c`]. Other
+    /// family members take `CDCx913::for_device(DynI2c::new(bus), device)`.
+    pub fn new_dyn(bus: &'a mut dyn ErasedI2c) -> Self {
+        Self::new(DynI2c::new(bus))
+    }
+}
+
+impl<I2C: I2c, DELAY> CDCx913<I2C, Cdce925, DELAY> {
+    /// Configuration block of the second PLL, which also drives Y4 and Y5
+    pub fn pll2(&mut self) -> PllBlock<'_, I2C, Cdce925, DELAY> {
+        PllBlock::new(self, Register::Pll2Configuration as u8)
+    }
+}
+
+impl<I2C: I2c, D: Device> CDCx913<I2C, D> {
+    /// Creates a driver for any member of the family, e.g.
+    /// `CDCx913::for_device(i2c, Cdce925)`
+    pub fn for_device(i2c: I2C, _device: D) -> Self {
+        Self {
+            i2c,
+            address: D::DEFAULT_ADDRESS,
+            _device: PhantomData,
+            serial_interface_released: false,
+            sequencing: Sequencing::Relaxed,
+            validation: ValidationPolicy::Strict,
+            clamped: false,
+            reference_hz: None,
+            supply_voltage: None,
+            delay: NoDelay,
+            #[cfg(feature = "ssc")]
+            ssc_stash: [0; 8],
+            bcount: None,
+            #[cfg(feature = "stats")]
+            stats: BusStats::default(),
+        }
+    }
+}
+
+impl<I2C: I2c, D: Device, DELAY> CDCx913<I2C, D, DELAY> {
+    /// Hands the driver `delay` for everything that has to wait for the
+    /// device, replacing the one it had
+    pub fn with_delay<T: DelayNs>(self, delay: T) -> CDCx913<I2C, D, T> {
+        CDCx913 {
+            i2c: self.i2c,
+            address: self.address,
+            _device: PhantomData,
+            serial_interface_released: self.serial_interface_released,
+            sequencing: self.sequencing,
+            validation: self.validation,
+            clamped: self.clamped,
+            reference_hz: self.reference_hz,
+            supply_voltage: self.supply_voltage,
+            delay,
+            #[cfg(feature = "ssc")]
+            ssc_stash: self.ssc_stash,
+            bcount: self.bcount,
+            #[cfg(feature = "stats")]
+            stats: self.stats,
+        }
+    }
+
+    /// Seeds the state the driver otherwise picks up from the bus as it goes
+    /// with what the device holds according to `config`: the address set by
+    /// TARGET_ADR, whether SPICON has released the serial interface, the
+    /// BCOUNT block reads have to honour and the SSC modulation amounts that
+    /// `set_spread_spectrum_enabled` restores.
+    /// Nothing is read or written, so this is for fast-boot paths that can't
+    /// afford to read the device back. Other family members take
+    /// `CDCx913::for_device(i2c, device).assuming_config(&config)`.
+    pub fn assuming_config(mut self, config: &DeviceConfig) -> Self {
+        let reg1 = GenericConfigurationRegister1::from(
+            config.register(Register::GenericConfiguration as u8 + 1),
+        );
+        let reg2 = GenericConfigurationRegister2::from(
+            config.register(Register::GenericConfiguration as u8 + 2),
+        );
+        let reg6 = GenericConfigurationRegister6::from(
+            config.register(Register::GenericConfiguration as u8 + 6),
+        );
+        // TARGET_ADR replaces the two low address bits
+        self.address = (D::DEFAULT_ADDRESS & !0b11) | reg1.target_adr();
+        self.serial_interface_released =
+            reg2.serial_interface_pin_mode() == SerialInterfacePinMode::ControlS1S2;
+        self.bcount = Some(reg6.bcount());
+
+        #[cfg(feature = "ssc")]
+        self.stash_ssc_amounts(config);
+
+        self
+    }
+
+    /// Remembers the non-zero SSC modulation amounts `config` holds for
+    /// `set_spread_spectrum_enabled`
+    #[cfg(feature = "ssc")]
+    pub(crate) fn stash_ssc_amounts(&mut self, config: &DeviceConfig) {
+        let codes = unpack_ssc1_selections(core::array::from_fn(|index| {
+            config.register(Register::Pll1Configuration as u8 + index as u8)
+        }));
+
+        for (stash, code) in self.ssc_stash.iter_mut().zip(codes) {
+            if code.value() != 0 {
+                *stash = code.value();
+            }
+        }
+    }
+
+    /// Stages settings for several registers, which are then written together
+    /// by [`Configure::apply`], see [`crate::configure`]
+    pub fn configure(&mut self) -> Configure<'_, I2C, D, DELAY> {
+        Configure::new(self)
+    }
+
+    /// Configuration block of PLL `n`, counting from 1
+    pub fn pll(&mut self, n: u8) -> Result<PllBlock<'_, I2C, D, DELAY>, Error<I2C::Error>> {
+        if n == 0 || n > D::PLLS {
+            return Err(Error::NoSuchPll(n));
+        }
+
+        Ok(PllBlock::new(
+            self,
+            Register::Pll1Configuration as u8 + 0x10 * (n - 1),
+        ))
+    }
+
+    /// Like [`Self::pll`], but with the PLL number checked at compile time
+    pub fn static_pll<const N: u8>(&mut self) -> PllBlock<'_, I2C, D, DELAY> {
+        const { assert!(N >= 1 && N <= D::PLLS, "the device has no such PLL") };
+
+        PllBlock::new(self, Register::Pll1Configuration as u8 + 0x10 * (N - 1))
+    }
+
+    /// Like [`Self::output`], but with the output number checked at compile
+    /// time
+    pub fn static_output<const N: u8>(&mut self) -> Output<'_, I2C, D, DELAY> {
+        const { assert!(N >= 1 && N <= D::OUTPUTS, "the device has no such output") };
+
+        Output::new(self, N)
+    }
+
+    /// Typed and raw access to single registers, bypassing the checks of the
+    /// high-level methods
+    pub fn registers(&mut self) -> RegisterInterface<'_, I2C, D, DELAY> {
+        RegisterInterface::new(self)
+    }
+
+    /// Output Y`n`, counting from 1
+    pub fn output(&mut self, n: u8) -> Result<Output<'_, I2C, D, DELAY>, Error<I2C::Error>> {
+        if n == 0 || n > D::OUTPUTS {
+            return Err(Error::NoSuchOutput(n));
+        }
+
+        Ok(Output::new(self, n))
+    }
+
+    /// Whether output Y`n` is enabled while the control inputs are in
+    /// `state`, together with the state definition that decides it, see
+    /// [`Output::state_definition`]
+    pub async fn is_output_enabled(
+        &mut self,
+        n: u8,
+        state: ControlInputState,
+    ) -> Result<(bool, OutputStateDefinition), Error<I2C::Error>> {
+        let definition = self.output(n)?.state_definition(state).await?;
+
+        Ok((definition.is_enabled(), definition))
+    }
+
+    /// Source, divider and states of output Y`n`, see [`Output::config`]
+    pub async fn output_config(&mut self, n: u8) -> Result<OutputConfig, Error<I2C::Error>> {
+        self.output(n)?.config().await
+    }
+
+    /// See [`Output::set_config`]
+    pub async fn set_output_config(
+        &mut self,
+        n: u8,
+        config: &OutputConfig,
+    ) -> Result<(), Error<I2C::Error>> {
+        self.output(n)?.set_config(config).await
+    }
+
+    /// Turns off the outputs in `mask` for every control input combination by
+    /// setting both of their state definitions to `mode`, with one
+    /// read-modify-write per register involved. The previous definitions are
+    /// not kept: [`Self::enable_outputs`] sets both to enabled.
+    pub async fn disable_outputs(
+        &mut self,
+        mask: OutputMask,
+        mode: DisableMode,
+    ) -> Result<(), Error<I2C::Error>> {
+        self.set_output_states(mask, mode.into()).await
+    }
+
+    /// Turns on the outputs in `mask` for every control input combination
+    pub async fn enable_outputs(&mut self, mask: OutputMask) -> Result<(), Error<I2C::Error>> {
+        self.set_output_states(mask, OutputStateDefinition::Enabled)
+            .await
+    }
+
+    async fn set_output_states(
+        &mut self,
+        mask: OutputMask,
+        definition: OutputStateDefinition,
+    ) -> Result<(), Error<I2C::Error>> {
+        let y2y3 = mask.intersects(OutputMask::Y2Y3);
+
+        if y2y3 && !mask.contains(OutputMask::Y2Y3) {
+            return Err(Error::SharedOutputState);
+        }
+
+        if mask.contains(OutputMask::Y1) {
+            modify!(self, GenericConfiguration, 2, |reg| {
+                reg.set_y1_state0_definition(definition);
+                reg.set_y1_state1_definition(definition);
+            })?;
+        }
+
+        if y2y3 {
+            modify!(self, Pll1Configuration, 4, |reg| {
+                reg.set_y2y3_state0_definition(definition);
+                reg.set_y2y3_state1_definition(definition);
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Reads the register at `offset`. Fails with [`Error::NoSuchPll`] if the
+    /// offset lies in the block of a PLL the device doesn't have.
+    pub async fn read_byte(&mut self, offset: RegisterOffset) -> Result<u8, Error<I2C::Error>> {
+        self.ensure_offset_exists(offset)?;
+
+        self.read_byte_unchecked(offset.value()).await
+    }
+
+    /// Writes the register at `offset`, with the same check as
+    /// [`Self::read_byte`]
+    pub async fn write_byte(
+        &mut self,
+        offset: RegisterOffset,
+        value: u8,
+    ) -> Result<(), Error<I2C::Error>> {
+        self.ensure_offset_exists(offset)?;
+
+        self.write_byte_unchecked(offset.value(), value).await
+    }
+
+    fn ensure_offset_exists(&self, offset: RegisterOffset) -> Result<(), Error<I2C::Error>> {
+        let pll = offset.value() / 0x10;
+
+        if pll > D::PLLS {
+            Err(Error::NoSuchPll(pll))
+        } else {
+            Ok(())
+        }
+    }
+
+    // Writes the byte at the given offset without checking that the address is
+    // valid. This is safe as far as the code is concerned but may cause
+    // unexpected or undefined behavior in the PLL if the target offset is not
+    // in the valid range. According to the datasheet, writing beyond 0x20 "may
+    // affect device function", so proceed at your own risk, or use
+    // `write_byte` with a `RegisterOffset`.
+    pub async fn write_byte_unchecked(
+        &mut self,
+        offset: u8,
+        value: u8,
+    ) -> Result<(), Error<I2C::Error>> {
+        self.ensure_serial_interface()?;
+
+        self.record_transaction(2);
+        self.i2c
+            .write(
+                self.address,
+                &[CommandCode::new(OpCode::Byte, offset).into(), value],
+            )
+            .await?;
+
+        trace!("write {:#x} = {:#x}", offset, value);
+
+        self.track_write(offset, value);
+
+        Ok(())
+    }
+
+    // Notices SPICON being set or BCOUNT changing with a write of `value` to
+    // `offset`
+    fn track_write(&mut self, offset: u8, value: u8) {
+        if offset == Register::GenericConfiguration as u8 + 2
+            && GenericConfigurationRegister2::from(value).serial_interface_pin_mode()
+                == SerialInterfacePinMode::ControlS1S2
+        {
+            warn!("SPICON set, the serial interface pins are now S1/S2");
+            self.serial_interface_released = true;
+        }
+
+        match offset.checked_sub(Register::GenericConfiguration as u8) {
+            Some(1) if !GenericConfigurationRegister1::from(value).pwdn() => self.bcount = None,
+            Some(6) => self.bcount = Some(GenericConfigurationRegister6::from(value).bcount()),
+            _ => {}
+        }
+    }
+
+    // Reads the byte at the given offset without checking that the address is
+    // valid. This is generally safe but the result may not be deterministic if
+    // the offset isn't in the allowed range.
+    pub async fn read_byte_unchecked(&mut self, offset: u8) -> Result<u8, Error<I2C::Error>> {
+        self.ensure_serial_interface()?;
+
+        let mut buf = [0u8; 1];
+
+        self.record_transaction(2);
+        self.i2c
+            .write_read(
+                self.address,
+                &[CommandCode::new(OpCode::Byte, offset).into()],
+                &mut buf,
+            )
+            .await?;
+
+        trace!("read {:#x} = {:#x}", offset, buf[0]);
+
+        Ok(buf[0])
+    }
+
+    // Reads `N` consecutive registers starting at `offset`. The device answers
+    // a block read with the byte count BCOUNT followed by that many registers
+    // from 0x00, all of which have to be read to finish the transfer, so this
+    // reads them all and picks out the requested ones. Registers beyond
+    // BCOUNT are read one byte at a time instead.
+    pub(crate) async fn read_block<const N: usize>(
+        &mut self,
+        offset: u8,
+    ) -> Result<[u8; N], Error<I2C::Error>> {
+        let bcount = match self.bcount {
+            Some(bcount) => bcount,
+            None => {
+                let offset = Register::GenericConfiguration as u8 + 6;
+                let reg =
+                    GenericConfigurationRegister6::from(self.read_byte_unchecked(offset).await?);
+                *self.bcount.insert(reg.bcount())
+            }
+        };
+
+        let start = offset as usize;
+        let mut bytes = [0u8; N];
+
+        if start + N > bcount as usize {
+            for (index, byte) in bytes.iter_mut().enumerate() {
+                *byte = self.read_byte_unchecked(offset + index as u8).await?;
+            }
+
+            return Ok(bytes);
+        }
+
+        self.ensure_serial_interface()?;
+
+        let mut buf = [0u8; MAX_BCOUNT + 1];
+        let len = bcount as usize + 1;
+
+        self.record_transaction(len + 1);
+        self.i2c
+            .write_read(
+                self.address,
+                &[CommandCode::new(OpCode::Block, 0x00).into()],
+                &mut buf[..len],
+            )
+            .await?;
+
+        trace!("block read, {} bytes", bcount);
+
+        bytes.copy_from_slice(&buf[start + 1..start + N + 1]);
+
+        Ok(bytes)
+    }
+
+    /// Reads the registers at `offsets`, which needn't be consecutive, in one
+    /// I2C transaction: a byte read for each, joined by repeated starts
+    /// instead of stop conditions. This saves the round trips of separate
+    /// reads and suits controllers and muxes that only keep the device
+    /// selected within one transaction. Writes can't be combined like this,
+    /// since adjacent writes in a transaction go out as a single one. At most
+    /// [`MAX_BATCHED_READS`] offsets fit, and like
+    /// [`Self::read_byte_unchecked`] they aren't checked.
+    pub async fn read_bytes_unchecked<const N: usize>(
+        &mut self,
+        offsets: [u8; N],
+    ) -> Result<[u8; N], Error<I2C::Error>> {
+        const { assert!(N <= MAX_BATCHED_READS) };
+
+        self.ensure_serial_interface()?;
+
+        let commands = offsets.map(|offset| u8::from(CommandCode::new(OpCode::Byte, offset)));
+        let mut values = [[0u8; 1]; N];
+
+        let mut operations: [Operation<'_>; 2 * MAX_BATCHED_READS] =
+            core::array::from_fn(|_| Operation::Write(&[]));
+        for (index, (command, value)) in commands.iter().zip(&mut values).enumerate() {
+            operations[2 * index] = Operation::Write(core::slice::from_ref(command));
+            operations[2 * index + 1] = Operation::Read(value);
+        }
+
+        self.record_transaction(2 * N);
+        self.i2c
+            .transaction(self.address, &mut operations[..2 * N])
+            .await?;
+
+        trace!("batched read of {} registers", N);
+
+        Ok(values.map(|[value]| value))
+    }
+
+    // Writes `data` to consecutive registers starting at `offset` with one
+    // block write. A single transaction either reaches the device or doesn't,
+    // which is what makes multi-register updates built on it cancel-safe.
+    pub(crate) async fn write_block(
+        &mut self,
+        offset: u8,
+        data: &[u8],
+    ) -> Result<(), Error<I2C::Error>> {
+        self.ensure_serial_interface()?;
+
+        let mut buf = [0u8; REGISTER_WINDOW_LEN + 2];
+        let len = data.len().min(REGISTER_WINDOW_LEN);
+
+        buf[0] = CommandCode::new(OpCode::Block, offset).into();
+        buf[1] = len as u8;
+        buf[2..len + 2].copy_from_slice(&data[..len]);
+
+        self.record_transaction(len + 2);
+        self.i2c.write(self.address, &buf[..len + 2]).await?;
+
+        trace!("block write {:#x}, {} bytes", offset, len);
+
+        for (index, &value) in data[..len].iter().enumerate() {
+            self.track_write(offset + index as u8, value);
+        }
+
+        Ok(())
+    }
+
+    // Stages an update of `N` consecutive registers: reads them with one block
+    // read, lets `f` modify the copy and commits it with one block write. If
+    // the future is dropped before the write, the device is left untouched.
+    pub(crate) async fn update_block<const N: usize, R>(
+        &mut self,
+        offset: u8,
+        f: impl FnOnce(&mut [u8; N]) -> R,
+    ) -> Result<R, Error<I2C::Error>> {
+        let mut bytes = self.read_block::<N>(offset).await?;
+        let result = f(&mut bytes);
+
+        self.write_block(offset, &bytes).await?;
+
+        Ok(result)
+    }
+
+    /// Tells the driver that the serial interface is usable again after SPICON
+    /// was switched to control pin mode, e.g. because the device was power
+    /// cycled with SPICON cleared in its EEPROM. Until this is called, every
+    /// operation fails with [`Error::SerialInterfaceDisabled`].
+    pub fn assume_serial_interface_restored(&mut self) {
+        self.serial_interface_released = false;
+    }
+
+    /// Tells the driver the frequency of the input clock (crystal, VCXO or
+    /// LVCMOS), which it needs to compute output frequencies
+    pub fn set_reference_frequency_hz(&mut self, reference_hz: Option<u32>) {
+        self.reference_hz = reference_hz;
+    }
+
+    pub fn reference_frequency_hz(&self) -> Option<u32> {
+        self.reference_hz
+    }
+
+    /// Tells the driver which supply the device runs from, so planning
+    /// applies the limits for it without reading E_EL first. Without a hint
+    /// the variant is read from the device each time.
+    pub fn set_supply_voltage(&mut self, supply: Option<SupplyVoltage>) {
+        self.supply_voltage = supply;
+    }
+
+    pub fn supply_voltage(&self) -> Option<SupplyVoltage> {
+        self.supply_voltage
+    }
+
+    pub fn set_sequencing(&mut self, sequencing: Sequencing) {
+        self.sequencing = sequencing;
+    }
+
+    pub fn sequencing(&self) -> Sequencing {
+        self.sequencing
+    }
+
+    /// Picks what setters taking a plain number, such as
+    /// [`Self::set_crystal_load_capacitance_pf`], [`Output::set_divider`] and
+    /// `set_spread_spectrum_amount`, do with a value out of range
+    pub fn set_validation_policy(&mut self, validation: ValidationPolicy) {
+        self.validation = validation;
+    }
+
+    pub fn validation_policy(&self) -> ValidationPolicy {
+        self.validation
+    }
+
+    /// Whether a setter clamped a value under [`ValidationPolicy::Lenient`]
+    /// since the last call
+    pub fn take_clamped(&mut self) -> bool {
+        core::mem::take(&mut self.clamped)
+    }
+
+    // Goes with `clamped`, the value the field can hold in place of the one
+    // given, unless the given one was `exact` or the policy is strict
+    pub(crate) fn check_value<T>(
+        &mut self,
+        exact: bool,
+        clamped: T,
+        error: Error<I2C::Error>,
+    ) -> Result<T, Error<I2C::Error>> {
+        if exact {
+            return Ok(clamped);
+        }
+
+        match self.validation {
+            ValidationPolicy::Strict => Err(error),
+            ValidationPolicy::Lenient => {
+                warn!("value out of range, clamped");
+                self.clamped = true;
+                Ok(clamped)
+            }
+        }
+    }
+
+    // Refuses a known reference the device can't take on `input`
+    pub(crate) fn check_reference(
+        &self,
+        input: InputClockSelection,
+    ) -> Result<(), Error<I2C::Error>> {
+        match self.reference_hz {
+            Some(reference_hz) if !InputRange::for_input(input).contains(reference_hz) => {
+                Err(Error::ReferenceOutOfRange {
+                    input,
+                    reference_hz,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Gives back the bus (or bus device), e.g. to hand a shared bus over to
+    /// another driver
+    pub fn release(self) -> I2C {
+        self.i2c
+    }
+
+    /// Turns the driver into a [`ReadOnlyCdcx913`], e.g. to hand it to a
+    /// telemetry task once the clocks are set up
+    pub fn into_read_only(self) -> ReadOnlyCdcx913<I2C, D, DELAY> {
+        ReadOnlyCdcx913::from(self)
+    }
+
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> BusStats {
+        self.stats
+    }
+
+    #[cfg(feature = "stats")]
+    pub fn reset_stats(&mut self) {
+        self.stats = BusStats::default();
+    }
+
+    // Accounts for one transaction moving `bytes` bytes
+    fn record_transaction(&mut self, bytes: usize) {
+        #[cfg(feature = "stats")]
+        {
+            self.stats.transactions = self.stats.transactions.wrapping_add(1);
+            self.stats.bytes = self.stats.bytes.wrapping_add(bytes as u32);
+        }
+        #[cfg(not(feature = "stats"))]
+        let _ = bytes;
+    }
+
+    #[cfg(feature = "eeprom")]
+    pub(crate) fn record_retry(&mut self) {
+        #[cfg(feature = "stats")]
+        {
+            self.stats.retries = self.stats.retries.wrapping_add(1);
+        }
+    }
+
+    /// Counterpart to [`Self::new`] for handing the clock domain over, e.g. to
+    /// a bootloader: turns the outputs off starting with those of the
+    /// highest PLL block and ending with Y1, optionally powers the device
+    /// down and gives back the bus. Like [`Self::disable_outputs`], this
+    /// overwrites both state definitions of every output. On failure the
+    /// driver is returned along with the error, so the caller can retry or
+    /// [`Self::release`] it.
+    pub async fn shutdown(
+        mut self,
+        options: ShutdownOptions,
+    ) -> Result<I2C, (Error<I2C::Error>, Self)> {
+        match self.disable_all_outputs(options).await {
+            Ok(()) => Ok(self.i2c),
+            Err(e) => Err((e, self)),
+        }
+    }
+
+    async fn disable_all_outputs(
+        &mut self,
+        options: ShutdownOptions,
+    ) -> Result<(), Error<I2C::Error>> {
+        let definition = options.mode.into();
+
+        for n in (1..=D::PLLS).rev() {
+            let offset = Register::Pll1Configuration as u8 + 0x10 * (n - 1) + 4;
+
+            self.modify_byte_unchecked(offset, |reg: &mut Pll1ConfigurationRegister4| {
+                reg.set_y2y3_state0_definition(definition);
+                reg.set_y2y3_state1_definition(definition);
+            })
+            .await?;
+        }
+
+        self.set_output_states(OutputMask::Y1, definition).await?;
+
+        if options.power_down {
+            self.set_power_down(true).await?;
+        }
+
+        debug!("outputs shut down");
+
+        Ok(())
+    }
+
+    /// Whether any of the outputs is currently fed by PLL1 (rather than the
+    /// input clock through the bypass paths) and enabled for at least one
+    /// control input combination
+    pub async fn pll1_outputs_enabled(&mut self) -> Result<bool, Error<I2C::Error>> {
+        let pll4 = read!(self, Pll1Configuration, 4, |reg| *reg)?;
+
+        if pll4.pll1_multiplexer() == Pll1Multiplexer::Pll1Bypass {
+            return Ok(false);
+        }
+
+        self.mux1_outputs_enabled().await
+    }
+
+    /// Whether any output routed through MUX1 is enabled for at least one
+    /// control input combination, whichever way MUX1 is switched. These are
+    /// the outputs whose frequency changes when the PLL is bypassed or
+    /// engaged.
+    async fn mux1_outputs_enabled(&mut self) -> Result<bool, Error<I2C::Error>> {
+        let [generic2, y1, pll4, y2y3] = self
+            .read_bytes_unchecked([
+                Register::GenericConfiguration as u8 + 2,
+                Register::GenericConfiguration as u8 + 4,
+                Register::Pll1Configuration as u8 + 4,
+                Register::Pll1Configuration as u8 + 5,
+            ])
+            .await?;
+        let generic2 = GenericConfigurationRegister2::from(generic2);
+        let y1 = GenericConfigurationRegister4::from(y1);
+        let pll4 = Pll1ConfigurationRegister4::from(pll4);
+        let y2y3 = Pll1ConfigurationRegister5::from(y2y3);
+
+        let pdiv1_from_mux1 = generic2.y1_clock_source() == Y1ClockSource::Pll1Clock;
+
+        let y2_from_mux1 = match pll4.output_y2_multiplexer() {
+            OutputY2Multiplexer::Pdiv1 => pdiv1_from_mux1,
+            OutputY2Multiplexer::Pdiv2 => true,
+        };
+        let y3_from_mux1 = match pll4.output_y3_multiplexer() {
+            OutputY3Multiplexer::Pdiv1 => pdiv1_from_mux1,
+            _ => true,
+        };
+
+        let y1_enabled = ControlInputState::ALL.into_iter().any(|state| {
+            let definition = match y1.y1_state_selection(state) {
+                OutputStateSelection::State0 => generic2.y1_state0_definition(),
+                OutputStateSelection::State1 => generic2.y1_state1_definition(),
+            };
+
+            definition == OutputStateDefinition::Enabled
+        });
+        let y2y3_enabled = ControlInputState::ALL.into_iter().any(|state| {
+            let definition = match y2y3.y2y3_state_selection(state) {
+                OutputStateSelection::State0 => pll4.y2y3_state0_definition(),
+                OutputStateSelection::State1 => pll4.y2y3_state1_definition(),
+            };
+
+            definition == OutputStateDefinition::Enabled
+        });
+
+        Ok((pdiv1_from_mux1 && y1_enabled) || ((y2_from_mux1 || y3_from_mux1) && y2y3_enabled))
+    }
+
+    pub(crate) async fn ensure_pll1_retunable(&mut self) -> Result<(), Error<I2C::Error>> {
+        if self.sequencing == Sequencing::Strict && self.pll1_outputs_enabled().await? {
+            warn!("refusing to retune PLL1 while its outputs are enabled");
+            Err(Error::OutputsEnabled)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn ensure_serial_interface(&self) -> Result<(), Error<I2C::Error>> {
+        if self.serial_interface_released {
+            Err(Error::SerialInterfaceDisabled)
+        } else {
+            Ok(())
+        }
+    }
+
+    pub(crate) async fn with<T: From<u8>, R>(
+        &mut self,
+        offset: u8,
+        f: impl FnOnce(&T) -> R,
+    ) -> Result<R, Error<I2C::Error>> {
+        let reg: T = self.read_byte_unchecked(offset).await?.into();
+
+        let r = f(&reg);
+
+        Ok(r)
+    }
+
+    pub async fn modify_byte_unchecked<T: From<u8> + Into<u8>, R>(
+        &mut self,
+        offset: u8,
+        f: impl FnOnce(&mut T) -> R,
+    ) -> Result<R, Error<I2C::Error>> {
+        let mut reg: T = self.read_byte_unchecked(offset).await?.into();
+
+        let r = f(&mut reg);
+
+        self.write_byte_unchecked(offset, reg.into()).await?;
+
+        Ok(r)
+    }
+
+    /// Reads register `T` from its own offset, for fields the driver has no
+    /// method for:
+    ///
+    /// ```ignore
+    /// let reg = driver.read_typed::<GenericConfigurationRegister4>().await?;
+    /// ```
+    ///
+    /// PLL register types refer to the PLL1 block, see
+    /// [`RegisterInterface::read_at`] for the other PLLs.
+    pub async fn read_typed<T: TypedRegister>(&mut self) -> Result<T, Error<I2C::Error>> {
+        Ok(self.read_byte_unchecked(T::OFFSET.value()).await?.into())
+    }
+
+    /// Read-modify-write of register `T` at its own offset, returning what
+    /// `f` returns. Like the dedicated setters, this isn't subject to any
+    /// [`Sequencing`] checks.
+    pub async fn modify_typed<T: TypedRegister, R>(
+        &mut self,
+        f: impl FnOnce(&mut T) -> R,
+    ) -> Result<R, Error<I2C::Error>> {
+        self.modify_byte_unchecked(T::OFFSET.value(), f).await
+    }
+
+    /// Reads register `index` of the PLL1 block, decoded into its type
+    pub async fn pll1_register(
+        &mut self,
+        index: Pll1RegisterIndex,
+    ) -> Result<Pll1Register, Error<I2C::Error>> {
+        let raw = self.read_byte_unchecked(index.offset().value()).await?;
+
+        Ok(Pll1Register::decode(index, raw))
+    }
+
+    /// Writes a PLL1 register read with [`Self::pll1_register`] back to its
+    /// offset. Like [`Self::modify_typed`], this isn't subject to any
+    /// [`Sequencing`] checks.
+    pub async fn set_pll1_register(
+        &mut self,
+        register: Pll1Register,
+    ) -> Result<(), Error<I2C::Error>> {
+        self.write_byte_unchecked(register.index().offset().value(), register.raw())
+            .await
+    }
+
+    #[doc(alias = "e_el")]
+    pub async fn device_identification(
+        &mut self,
+    ) -> Result<DeviceIdentification, Error<I2C::Error>> {
+        read!(self, GenericConfiguration, 0, |reg| reg
+            .device_identification())
+    }
+
+    #[doc(alias = "rid")]
+    pub async fn revision_number(&mut self) -> Result<u3, Error<I2C::Error>> {
+        read!(self, GenericConfiguration, 0, |reg| u3::new(reg.rid()))
+    }
+
+    #[doc(alias = "vid")]
+    pub async fn vendor_identification(&mut self) -> Result<u4, Error<I2C::Error>> {
+        read!(self, GenericConfiguration, 0, |reg| u4::new(reg.vid()))
+    }
+
+    #[doc(alias = "eepip")]
+    pub async fn eeprom_programming_status(
+        &mut self,
+    ) -> Result<EepromProgrammingStatus, Error<I2C::Error>> {
+        read!(self, GenericConfiguration, 1, |reg| reg
+            .eeprom_programming_status())
+    }
+
+    #[doc(alias = "eelock")]
+    pub async fn eeprom_permanently_locked(&mut self) -> Result<bool, Error<I2C::Error>> {
+        read!(self, GenericConfiguration, 1, |reg| reg.eelock())
+    }
+
+    #[doc(alias = "pwdn")]
+    pub async fn power_down(&mut self) -> Result<bool, Error<I2C::Error>> {
+        read!(self, GenericConfiguration, 1, |reg| reg.pwdn())
+    }
+
+    /// Releasing power-down re-loads the registers from the EEPROM, see
+    /// [`Self::wait_for_startup`] for the time that takes
+    #[doc(alias = "set_pwdn")]
+    pub async fn set_power_down(&mut self, value: bool) -> Result<(), Error<I2C::Error>> {
+        modify!(self, GenericConfiguration, 1, |reg| reg.set_pwdn(value))
+    }
+
+    /// Fails with [`Error::ReservedEncoding`] if INCLK holds the reserved
+    /// encoding
+    #[doc(alias = "inclk")]
+    pub async fn input_clock(&mut self) -> Result<InputClockSelection, Error<I2C::Error>> {
+        read!(self, GenericConfiguration, 1, |reg| reg
+            .input_clock_selection())?
+        .map_err(|ReservedEncoding(raw)| Error::ReservedEncoding(raw))
+    }
+
+    /// Fails with [`Error::ReferenceOutOfRange`] before writing anything if
+    /// the reference set with [`Self::set_reference_frequency_hz`] is outside
+    /// of the [`InputRange`] of `value`
+    #[doc(alias = "set_inclk")]
+    pub async fn set_input_clock(
+        &mut self,
+        value: InputClockSelection,
+    ) -> Result<(), Error<I2C::Error>> {
+        self.check_reference(value)?;
+
+        modify!(self, GenericConfiguration, 1, |reg| reg
+            .set_input_clock_selection(value))
+    }
+
+    #[doc(alias = "target_adr")]
+    pub async fn target_address(&mut self) -> Result<u2, Error<I2C::Error>> {
+        read!(self, GenericConfiguration, 1, |reg| u2::new(
+            reg.target_adr()
+        ))
+    }
+
+    #[doc(alias = "set_target_adr")]
+    pub async fn set_target_address(&mut self, value: u2) -> Result<(), Error<I2C::Error>> {
+        modify!(self, GenericConfiguration, 1, |reg| reg
+            .set_target_adr(value.value()))
+    }
+
+    #[doc(alias = "m1")]
+    pub async fn y1_clock_source(&mut self) -> Result<Y1ClockSource, Error<I2C::Error>> {
+        read!(self, GenericConfiguration, 2, |reg| reg.y1_clock_source())
+    }
+
+    #[doc(alias = "set_m1")]
+    pub async fn set_y1_clock_source(
+        &mut self,
+        value: Y1ClockSource,
+    ) -> Result<(), Error<I2C::Error>> {
+        modify!(self, GenericConfiguration, 2, |reg| reg
+            .set_y1_clock_source(value))
+    }
+
+    #[doc(alias = "spicon")]
+    pub async fn serial_pins_operating_mode(
+        &mut self,
+    ) -> Result<SerialInterfacePinMode, Error<I2C::Error>> {
+        read!(self, GenericConfiguration, 2, |reg| reg
+            .serial_interface_pin_mode())
+    }
+
+    /// Switching to [`SerialInterfacePinMode::ControlS1S2`] turns the serial
+    /// interface pins into the S1/S2 control inputs, so the driver refuses any
+    /// further bus traffic afterwards (see
+    /// [`Self::assume_serial_interface_restored`]).
+    #[doc(alias = "set_spicon")]
+    pub async fn set_serial_pins_operating_mode(
+        &mut self,
+        value: SerialInterfacePinMode,
+    ) -> Result<(), Error<I2C::Error>> {
+        modify!(self, GenericConfiguration, 2, |reg| reg
+            .set_serial_interface_pin_mode(value))
+    }
+
+    #[doc(alias = "y1_st1")]
+    pub async fn y1_state_1(&mut self) -> Result<OutputStateDefinition, Error<I2C::Error>> {
+        read!(self, GenericConfiguration, 2, |reg| reg
+            .y1_state1_definition())
+    }
+
+    #[doc(alias = "set_y1_st1")]
+    pub async fn set_y1_state_1(
+        &mut self,
+        value: OutputStateDefinition,
+    ) -> Result<(), Error<I2C::Error>> {
+        modify!(self, GenericConfiguration, 2, |reg| reg
+            .set_y1_state1_definition(value))
+    }
+
+    #[doc(alias = "y1_st0")]
+    pub async fn y1_state_0(&mut self) -> Result<OutputStateDefinition, Error<I2C::Error>> {
+        read!(self, GenericConfiguration, 2, |reg| reg
+            .y1_state0_definition())
+    }
+
+    #[doc(alias = "set_y1_st0")]
+    pub async fn set_y1_state_0(
+        &mut self,
+        value: OutputStateDefinition,
+    ) -> Result<(), Error<I2C::Error>> {
+        modify!(self, GenericConfiguration, 2, |reg| reg
+            .set_y1_state0_definition(value))
+    }
+
+    #[doc(alias = "pdiv1")]
+    pub async fn y1_output_divider(&mut self) -> Result<u10, Error<I2C::Error>> {
+        let reg2 = read!(self, GenericConfiguration, 2, |reg| *reg)?;
+        let reg3 = read!(self, GenericConfiguration, 3, |reg| *reg)?;
+
+        Ok(u10::new(reg3.pdiv1_full_value(&reg2)))
+    }
+
+    #[doc(alias = "set_pdiv1")]
+    pub async fn set_y1_output_divider(&mut self, value: u10) -> Result<(), Error<I2C::Error>> {
+        self.update_block(
+            Register::GenericConfiguration as u8 + 2,
+            |bytes: &mut [u8; 2]| {
+                let mut reg2 = GenericConfigurationRegister2::from(bytes[0]);
+                let mut reg3 = GenericConfigurationRegister3::from(bytes[1]);
+
+                reg2.set_pdiv1_9_8((value.value() >> 8) as _);
+                reg3.set_pdiv1_7_0((value.value() & 0xFF) as _);
+
+                *bytes = [reg2.into(), reg3.into()];
+            },
+        )
+        .await
+    }
+
+    /// Frequency of Y1 for the reference set with
+    /// [`Self::set_reference_frequency_hz`] while the control inputs are in
+    /// `state`, following M1: the input clock divided by PDIV1, or PLL1 with
+    /// the settings bank FS1 selects for `state` divided by PDIV1. `None`
+    /// while PDIV1 is held in reset or the PLL settings make no sense.
+    pub async fn y1_frequency(
+        &mut self,
+        state: ControlInputState,
+    ) -> Result<Option<u32>, Error<I2C::Error>> {
+        let reference_hz = self.reference_hz.ok_or(Error::UnknownReferenceFrequency)?;
+
+        let config = DeviceConfig::from_bytes(self.read_block(0x00).await?);
+        let bank = config.effective_config(state, None).pll_bank;
+
+        Ok(config.frequencies(bank, reference_hz).y1_hz)
+    }
+
+    /// Sources Y1 from the input clock and divides it by `divider`, with M1
+    /// and PDIV1 going out in one block write. This uses the device as a
+    /// clock buffer or divider and leaves PLL1 alone, so Y2 and Y3 carry on
+    /// undisturbed. A divider of 0 holds PDIV1 in reset.
+    #[doc(alias = "set_m1")]
+    #[doc(alias = "set_pdiv1")]
+    pub async fn set_y1_division_of_input(
+        &mut self,
+        divider: u10,
+    ) -> Result<(), Error<I2C::Error>> {
+        self.update_block(
+            Register::GenericConfiguration as u8 + 2,
+            |bytes: &mut [u8; 2]| {
+                let mut reg2 = GenericConfigurationRegister2::from(bytes[0]);
+                let mut reg3 = GenericConfigurationRegister3::from(bytes[1]);
+
+                reg2.set_y1_clock_source(Y1ClockSource::InputClock);
+                reg2.set_pdiv1_9_8((divider.value() >> 8) as _);
+                reg3.set_pdiv1_7_0((divider.value() & 0xFF) as _);
+
+                *bytes = [reg2.into(), reg3.into()];
+            },
+        )
+        .await
+    }
+
+    #[doc(alias = "y1_x")]
+    pub async fn y1_state_selection(
+        &mut self,
+        control_input: impl Into<ControlInputState>,
+    ) -> Result<OutputStateSelection, Error<I2C::Error>> {
+        let control_input: ControlInputState = control_input.into();
+
+        read!(self, GenericConfiguration, 4, |reg| reg
+            .y1_state_selection(control_input))
+    }
+
+    #[doc(alias = "set_y1_x")]
+    pub async fn set_y1_state_selection(
+        &mut self,
+        control_input: impl Into<ControlInputState>,
+        value: OutputStateSelection,
+    ) -> Result<(), Error<I2C::Error>> {
+        let control_input: ControlInputState = control_input.into();
+
+        modify!(self, GenericConfiguration, 4, |reg| reg
+            .set_y1_state_selection(control_input, value))
+    }
+
+    #[doc(alias = "xcsel")]
+    pub async fn crystal_load_capacitance(
+        &mut self,
+    ) -> Result<CrystalLoadCapacitance, Error<I2C::Error>> {
+        read!(self, GenericConfiguration, 5, |reg| reg
+            .crystal_load_capacitance())
+    }
+
+    #[doc(alias = "set_xcsel")]
+    pub async fn set_crystal_load_capacitance(
+        &mut self,
+        value: CrystalLoadCapacitance,
+    ) -> Result<(), Error<I2C::Error>> {
+        modify!(self, GenericConfiguration, 5, |reg| reg
+            .set_crystal_load_capacitance(value))
+    }
+
+    /// Sets the crystal load capacitance in pF. Values above
+    /// [`CrystalLoadCapacitance::MAX_PF`] are handled according to the
+    /// [`ValidationPolicy`].
+    pub async fn set_crystal_load_capacitance_pf(
+        &mut self,
+        pf: u8,
+    ) -> Result<(), Error<I2C::Error>> {
+        let pf = self.check_value(
+            pf <= CrystalLoadCapacitance::MAX_PF,
+            pf.min(CrystalLoadCapacitance::MAX_PF),
+            Error::ValueOutOfRange,
+        )?;
+
+        self.set_crystal_load_capacitance(CrystalLoadCapacitance::from_xcsel(pf))
+            .await
+    }
+
+    /// Programs the XCSEL value [`CrystalSpec::recommended_load_capacitance`]
+    /// picks for `crystal` and takes its frequency as the reference, see
+    /// [`Self::set_reference_frequency_hz`]. A CL that XCSEL can't get within
+    /// half a picofarad of is handled according to the [`ValidationPolicy`].
+    pub async fn apply_crystal_spec(
+        &mut self,
+        crystal: &CrystalSpec,
+    ) -> Result<LoadCapacitanceMatch, Error<I2C::Error>> {
+        let recommended = crystal.recommended_load_capacitance();
+        let recommended = self.check_value(
+            recommended.is_within_step(),
+            recommended,
+            Error::ValueOutOfRange,
+        )?;
+
+        self.set_crystal_load_capacitance(recommended.capacitance)
+            .await?;
+        self.reference_hz = Some(crystal.frequency_hz);
+
+        Ok(recommended)
+    }
+
+    #[doc(alias = "bcount")]
+    pub async fn block_byte_count(&mut self) -> Result<u7, Error<I2C::Error>> {
+        read!(self, GenericConfiguration, 6, |reg| u7::new(reg.bcount()))
+    }
+
+    #[doc(alias = "set_bcount")]
+    pub async fn set_block_byte_count(&mut self, value: u7) -> Result<(), Error<I2C::Error>> {
+        modify!(self, GenericConfiguration, 6, |reg| reg
+            .set_bcount(value.value()))
+    }
+
+    /// Sets EEWRITE and returns immediately with a handle that can be polled
+    /// for completion. Useful when the caller wants to report progress or apply
+    /// its own timeout/cancellation policy.
+    #[doc(alias = "eewrite")]
+    #[cfg(feature = "eeprom")]
+    pub async fn start_eeprom_write(
+        &mut self,
+    ) -> Result<EepromWriteHandle<'_, I2C, D, DELAY>, Error<I2C::Error>> {
+        modify!(self, GenericConfiguration, 6, |reg| reg.set_eewrite(true))?;
+
+        debug!("EEPROM write started");
+
+        Ok(EepromWriteHandle {
+            driver: self,
+            polls: 0,
+        })
+    }
+
+    // EEWRITE has to be cleared again before another write can be started
+    #[cfg(feature = "eeprom")]
+    pub(crate) async fn finish_eeprom_write(&mut self) -> Result<(), Error<I2C::Error>> {
+        debug!("EEPROM write completed");
+
+        modify!(self, GenericConfiguration, 6, |reg| reg.set_eewrite(false))
+    }
+
+    /* ==== PLL Config ==== */
+
+    #[doc(alias = "ssc1_x")]
+    #[cfg(feature = "ssc")]
+    pub async fn spread_spectrum_clocking_selection_raw(
+        &mut self,
+        control_input: impl Into<ControlInputState>,
+    ) -> Result<u3, Error<I2C::Error>> {
+        let control_input: ControlInputState = control_input.into();
+
+        Ok(u3::new(match control_input {
+            ControlInputState::S111 => read!(self, Pll1Configuration, 0, |reg| reg.ssc1_7())?,
+            ControlInputState::S110 => read!(self, Pll1Configuration, 0, |reg| reg.ssc1_6())?,
+            ControlInputState::S101 => {
+                let hi = read!(self, Pll1Configuration, 0, |reg| reg.ssc1_5())?;
+                let lo = read!(self, Pll1Configuration, 1, |reg| reg.ssc1_5() as u8)?;
+
+                (hi << 1) | lo
+            }
+            ControlInputState::S100 => read!(self, Pll1Configuration, 1, |reg| reg.ssc1_4())?,
+            ControlInputState::S011 => read!(self, Pll1Configuration, 1, |reg| reg.ssc1_3())?,
+            ControlInputState::S010 => {
+                let hi = read!(self, Pll1Configuration, 1, |reg| reg.ssc1_2() as u8)?;
+                let lo = read!(self, Pll1Configuration, 2, |reg| reg.ssc1_2())?;
+
+                (hi << 2) | lo
+            }
+            ControlInputState::S001 => read!(self, Pll1Configuration, 2, |reg| reg.ssc1_1())?,
+            ControlInputState::S000 => read!(self, Pll1Configuration, 2, |reg| reg.ssc1_0())?,
+        }))
+    }
+
+    #[doc(alias = "ssc1_x_down")]
+    #[cfg(feature = "ssc")]
+    pub async fn spread_spectrum_clocking_selection_as_down(
+        &mut self,
+        control_input: impl Into<ControlInputState>,
+    ) -> Result<SscModulationAmountDown, Error<I2C::Error>> {
+        let raw_value = self
+            .spread_spectrum_clocking_selection_raw(control_input)
+            .await?;
+
+        Ok(SscModulationAmountDown::from(raw_value))
+    }
+
+    #[doc(alias = "ssc1_x_center")]
+    #[cfg(feature = "ssc")]
+    pub async fn spread_spectrum_clocking_selection_as_center(
+        &mut self,
+        control_input: impl Into<ControlInputState>,
+    ) -> Result<SscModulationAmountCenter, Error<I2C::Error>> {
+        let raw_value = self
+            .spread_spectrum_clocking_selection_raw(control_input)
+            .await?;
+
+        Ok(SscModulationAmountCenter::from(raw_value))
+    }
+
+    #[doc(alias = "set_ssc1_x")]
+    #[cfg(feature = "ssc")]
+    pub async fn set_spread_spectrum_clocking_selection_raw(
+        &mut self,
+        control_input: impl Into<ControlInputState>,
+        value: u3,
+    ) -> Result<(), Error<I2C::Error>> {
+        let control_input: ControlInputState = control_input.into();
+
+        let value = value.value();
+
+        match control_input {
+            ControlInputState::S111 => {
+                modify!(self, Pll1Configuration, 0, |reg| reg.set_ssc1_7(value))?
+            }
+            ControlInputState::S110 => {
+                modify!(self, Pll1Configuration, 0, |reg| reg.set_ssc1_6(value))?
+            }
+            // The split selections go out in one block write, so a dropped
+            // future can't leave half of one behind
+            ControlInputState::S101 => {
+                self.update_block(Register::Pll1Configuration as u8, |bytes: &mut [u8; 2]| {
+                    let mut reg0 = Pll1ConfigurationRegister0::from(bytes[0]);
+                    let mut reg1 = Pll1ConfigurationRegister1::from(bytes[1]);
+
+                    reg0.set_ssc1_5(value >> 1);
+                    reg1.set_ssc1_5(value & 0b001 != 0);
+
+                    *bytes = [reg0.into(), reg1.into()];
+                })
+                .await?
+            }
+            ControlInputState::S100 => {
+                modify!(self, Pll1Configuration, 1, |reg| reg.set_ssc1_4(value))?
+            }
+            ControlInputState::S011 => {
+                modify!(self, Pll1Configuration, 1, |reg| reg.set_ssc1_3(value))?
+            }
+            ControlInputState::S010 => {
+                let offset = Register::Pll1Configuration as u8 + 1;
+
+                self.update_block(offset, |bytes: &mut [u8; 2]| {
+                    let mut reg1 = Pll1ConfigurationRegister1::from(bytes[0]);
+                    let mut reg2 = Pll1ConfigurationRegister2::from(bytes[1]);
+
+                    reg1.set_ssc1_2(value >> 2 != 0);
+                    reg2.set_ssc1_2(value & 0b011);
+
+                    *bytes = [reg1.into(), reg2.into()];
+                })
+                .await?
+            }
+            ControlInputState::S001 => {
+                modify!(self, Pll1Configuration, 2, |reg| reg.set_ssc1_1(value))?
+            }
+            ControlInputState::S000 => {
+                modify!(self, Pll1Configuration, 2, |reg| reg.set_ssc1_0(value))?
+            }
+        }
+
+        // Only once the device holds it, so a failed write can't make
+        // set_spread_spectrum_enabled restore an amount that never got there
+        if value != 0 {
+            self.ssc_stash[control_input as usize] = value;
+        }
+
+        Ok(())
+    }
+
+    #[doc(alias = "set_ssc1_x_down")]
+    #[cfg(feature = "ssc")]
+    pub async fn set_spread_spectrum_clocking_selection_as_down(
+        &mut self,
+        control_input: impl Into<ControlInputState>,
+        value: SscModulationAmountDown,
+    ) -> Result<(), Error<I2C::Error>> {
+        self.set_spread_spectrum_clocking_selection_raw(control_input, u3::new(value as u8))
+            .await
+    }
+
+    #[doc(alias = "set_ssc1_x_center")]
+    #[cfg(feature = "ssc")]
+    pub async fn set_spread_spectrum_clocking_selection_as_center(
+        &mut self,
+        control_input: impl Into<ControlInputState>,
+        value: SscModulationAmountCenter,
+    ) -> Result<(), Error<I2C::Error>> {
+        self.set_spread_spectrum_clocking_selection_raw(control_input, u3::new(value as u8))
+            .await
+    }
+
+    /// Sets the modulation amount in hundredths of a percent, e.g. 50 for 0.5%,
+    /// whether SSC1DC selects down or center spread. Amounts the device
+    /// doesn't support are handled according to the [`ValidationPolicy`],
+    /// clamping to the next supported amount below.
+    #[cfg(feature = "ssc")]
+    pub async fn set_spread_spectrum_amount(
+        &mut self,
+        control_input: impl Into<ControlInputState>,
+        hundredths: u32,
+    ) -> Result<(), Error<I2C::Error>> {
+        // Down and center spread encode the same amounts
+        const AMOUNTS: [u32; 8] = [0, 25, 50, 75, 100, 125, 150, 200];
+
+        let code = AMOUNTS.iter().rposition(|&amount| amount <= hundredths);
+        let code = self.check_value(
+            AMOUNTS[code.unwrap_or(0)] == hundredths,
+            code.unwrap_or(0) as u8,
+            Error::ValueOutOfRange,
+        )?;
+
+        self.set_spread_spectrum_clocking_selection_raw(control_input, u3::new(code))
+            .await
+    }
+
+    /// Whether spread spectrum modulation is on while the control inputs are
+    /// in `control_input`, i.e. its modulation code is non-zero
+    #[cfg(feature = "ssc")]
+    pub async fn spread_spectrum_enabled(
+        &mut self,
+        control_input: impl Into<ControlInputState>,
+    ) -> Result<bool, Error<I2C::Error>> {
+        let raw_value = self
+            .spread_spectrum_clocking_selection_raw(control_input)
+            .await?;
+
+        Ok(raw_value.value() != 0)
+    }
+
+    /// Turns spread spectrum modulation off or back on for `control_input`
+    /// without the caller having to keep track of the amount. Disabling
+    /// writes the "off" code and remembers the amount that was set, enabling
+    /// restores the last non-zero amount the driver saw for that state, either
+    /// here or through the setters. Enabling with modulation already on leaves
+    /// it as it is, and fails with [`Error::UnknownSscAmount`] if the driver
+    /// never saw a non-zero amount for the state.
+    #[cfg(feature = "ssc")]
+    pub async fn set_spread_spectrum_enabled(
+        &mut self,
+        control_input: impl Into<ControlInputState>,
+        enabled: bool,
+    ) -> Result<(), Error<I2C::Error>> {
+        let control_input: ControlInputState = control_input.into();
+        let current = self
+            .spread_spectrum_clocking_selection_raw(control_input)
+            .await?
+            .value();
+
+        if current != 0 {
+            self.ssc_stash[control_input as usize] = current;
+        }
+
+        match (enabled, current) {
+            (false, 0) | (true, 1..) => Ok(()),
+            (false, _) => {
+                self.set_spread_spectrum_clocking_selection_raw(control_input, u3::new(0))
+                    .await
+            }
+            (true, 0) => match self.ssc_stash[control_input as usize] {
+                0 => Err(Error::UnknownSscAmount),
+                stashed => {
+                    self.set_spread_spectrum_clocking_selection_raw(control_input, u3::new(stashed))
+                        .await
+                }
+            },
+        }
+    }
+
+    #[doc(alias = "fs1_x")]
+    pub async fn pll1_frequency_selection(
+        &mut self,
+        control_input: impl Into<ControlInputState>,
+    ) -> Result<Fs1Selection, Error<I2C::Error>> {
+        let control_input: ControlInputState = control_input.into();
+
+        read!(self, Pll1Configuration, 3, |reg| reg
+            .fs1_selection(control_input))
+    }
+
+    #[doc(alias = "set_fs1_x")]
+    pub async fn set_pll1_frequency_selection(
+        &mut self,
+        control_input: impl Into<ControlInputState>,
+        value: Fs1Selection,
+    ) -> Result<(), Error<I2C::Error>> {
+        let control_input: ControlInputState = control_input.into();
+
+        modify!(self, Pll1Configuration, 3, |reg| reg
+            .set_fs1_selection(control_input, value))
+    }
+
+    #[doc(alias = "mux1")]
+    pub async fn pll1_multiplexer(&mut self) -> Result<Pll1Multiplexer, Error<I2C::Error>> {
+        read!(self, Pll1Configuration, 4, |reg| reg.pll1_multiplexer())
+    }
+
+    #[doc(alias = "set_mux1")]
+    pub async fn set_pll1_multiplexer(
+        &mut self,
+        value: Pll1Multiplexer,
+    ) -> Result<(), Error<I2C::Error>> {
+        modify!(self, Pll1Configuration, 4, |reg| reg
+            .set_pll1_multiplexer(value))
+    }
+
+    /// Whether MUX1 routes the input clock around PLL1
+    pub async fn is_pll_bypassed(&mut self) -> Result<bool, Error<I2C::Error>> {
+        Ok(self.pll1_multiplexer().await? == Pll1Multiplexer::Pll1Bypass)
+    }
+
+    /// Switches MUX1 so the input clock bypasses PLL1. The dividers stay as
+    /// they are, so every output behind MUX1 drops from the VCO frequency to
+    /// the reference frequency divided by the same amount. Under
+    /// [`Sequencing::Strict`] this is refused with [`Error::OutputsEnabled`]
+    /// while any of those outputs is enabled.
+    pub async fn bypass_pll(&mut self) -> Result<(), Error<I2C::Error>> {
+        self.switch_mux1(Pll1Multiplexer::Pll1Bypass).await
+    }
+
+    /// Switches MUX1 back to the PLL1 output, undoing [`Self::bypass_pll`],
+    /// with the same check under [`Sequencing::Strict`]
+    pub async fn engage_pll(&mut self) -> Result<(), Error<I2C::Error>> {
+        self.switch_mux1(Pll1Multiplexer::Pll1).await
+    }
+
+    async fn switch_mux1(&mut self, value: Pll1Multiplexer) -> Result<(), Error<I2C::Error>> {
+        if self.pll1_multiplexer().await? == value {
+            return Ok(());
+        }
+
+        if self.sequencing == Sequencing::Strict && self.mux1_outputs_enabled().await? {
+            warn!("refusing to switch MUX1 while outputs behind it are enabled");
+            return Err(Error::OutputsEnabled);
+        }
+
+        self.set_pll1_multiplexer(value).await
+    }
+
+    /// Programs M1, MUX1, M2 and M3 so every output takes the path given in
+    /// `routing`. Fails with [`Error::InvalidRouting`] before anything is
+    /// written if no setting does that. MUX1 is left alone if no output goes
+    /// through it, and changing it gets the same [`Sequencing::Strict`] check
+    /// as [`Self::bypass_pll`]. All four go out with one block write, so the
+    /// outputs never run with a mix of the old and new routing.
+    pub async fn set_routing(&mut self, routing: Routing) -> Result<(), Error<I2C::Error>> {
+        let settings = routing.mux_settings().map_err(Error::InvalidRouting)?;
+
+        if let Some(mux1) = settings.mux1
+            && self.sequencing == Sequencing::Strict
+            && self.pll1_multiplexer().await? != mux1
+            && self.mux1_outputs_enabled().await?
+        {
+            warn!("refusing to switch MUX1 while outputs behind it are enabled");
+            return Err(Error::OutputsEnabled);
+        }
+
+        // M1 and the PLL1 multiplexers are apart, so the write reaches across
+        // the reserved registers, which get back what they read as
+        let offset = Register::GenericConfiguration as u8 + 2;
+        self.update_block(offset, |bytes: &mut [u8; 19]| {
+            let mut reg2 = GenericConfigurationRegister2::from(bytes[0]);
+            let mut reg6 = GenericConfigurationRegister6::from(bytes[4]);
+            let mut pll4 = Pll1ConfigurationRegister4::from(bytes[0x12]);
+
+            reg2.set_y1_clock_source(settings.m1);
+            // EEWRITE is a command, writing it back could start a commit
+            reg6.set_eewrite(false);
+            if let Some(mux1) = settings.mux1 {
+                pll4.set_pll1_multiplexer(mux1);
+            }
+            pll4.set_output_y2_multiplexer(settings.m2);
+            pll4.set_output_y3_source(settings.m3);
+
+            bytes[0] = reg2.into();
+            bytes[4] = reg6.into();
+            bytes[0x12] = pll4.into();
+        })
+        .await
+    }
+
+    #[doc(alias = "m2")]
+    pub async fn y2_multiplexer(&mut self) -> Result<OutputY2Multiplexer, Error<I2C::Error>> {
+        read!(self, Pll1Configuration, 4, |reg| reg
+            .output_y2_multiplexer())
+    }
+
+    #[doc(alias = "set_m2")]
+    pub async fn set_y2_multiplexer(
+        &mut self,
+        value: OutputY2Multiplexer,
+    ) -> Result<(), Error<I2C::Error>> {
+        modify!(self, Pll1Configuration, 4, |reg| reg
+            .set_output_y2_multiplexer(value))
+    }
+
+    #[doc(alias = "m3")]
+    pub async fn y3_multiplexer(&mut self) -> Result<OutputY3Multiplexer, Error<I2C::Error>> {
+        read!(self, Pll1Configuration, 4, |reg| reg
+            .output_y3_multiplexer())
+    }
+
+    #[doc(alias = "set_m3")]
+    pub async fn set_y3_multiplexer(
+        &mut self,
+        value: OutputY3Multiplexer,
+    ) -> Result<(), Error<I2C::Error>> {
+        modify!(self, Pll1Configuration, 4, |reg| reg
+            .set_output_y3_multiplexer(value))
+    }
+
+    /// Like [`Self::y3_multiplexer`], but fails with
+    /// [`Error::ReservedEncoding`] if M3 holds the reserved encoding
+    #[doc(alias = "m3")]
+    pub async fn y3_source(&mut self) -> Result<OutputY3Source, Error<I2C::Error>> {
+        read!(self, Pll1Configuration, 4, |reg| reg.output_y3_source())?
+            .map_err(|ReservedEncoding(raw)| Error::ReservedEncoding(raw))
+    }
+
+    /// Like [`Self::set_y3_multiplexer`], but only accepts the encodings that
+    /// route a divider to Y3
+    #[doc(alias = "set_m3")]
+    pub async fn set_y3_source(&mut self, value: OutputY3Source) -> Result<(), Error<I2C::Error>> {
+        modify!(self, Pll1Configuration, 4, |reg| reg
+            .set_output_y3_source(value))
+    }
+
+    #[doc(alias = "y2y3_st1")]
+    pub async fn y2y3_state1_definition(
+        &mut self,
+    ) -> Result<OutputStateDefinition, Error<I2C::Error>> {
+        read!(self, Pll1Configuration, 4, |reg| reg
+            .y2y3_state1_definition())
+    }
+
+    #[doc(alias = "set_y2y3_st1")]
+    pub async fn set_y2y3_state1_definition(
+        &mut self,
+        value: OutputStateDefinition,
+    ) -> Result<(), Error<I2C::Error>> {
+        modify!(self, Pll1Configuration, 4, |reg| reg
+            .set_y2y3_state1_definition(value))
+    }
+
+    #[doc(alias = "y2y3_st0")]
+    pub async fn y2y3_state0_definition(
+        &mut self,
+    ) -> Result<OutputStateDefinition, Error<I2C::Error>> {
+        read!(self, Pll1Configuration, 4, |reg| reg
+            .y2y3_state0_definition())
+    }
+
+    #[doc(alias = "set_y2y3_st0")]
+    pub async fn set_y2y3_state0_definition(
+        &mut self,
+        value: OutputStateDefinition,
+    ) -> Result<(), Error<I2C::Error>> {
+        modify!(self, Pll1Configuration, 4, |reg| reg
+            .set_y2y3_state0_definition(value))
+    }
+
+    #[doc(alias = "y2y3_x")]
+    pub async fn y2y3_state_selection(
+        &mut self,
+        control_input: impl Into<ControlInputState>,
+    ) -> Result<OutputStateSelection, Error<I2C::Error>> {
+        let control_input: ControlInputState = control_input.into();
+
+        read!(self, Pll1Configuration, 5, |reg| reg
+            .y2y3_state_selection(control_input))
+    }
+
+    #[doc(alias = "set_y2y3_x")]
+    pub async fn set_y2y3_state_selection(
+        &mut self,
+        control_input: impl Into<ControlInputState>,
+        value: OutputStateSelection,
+    ) -> Result<(), Error<I2C::Error>> {
+        let control_input: ControlInputState = control_input.into();
+
+        modify!(self, Pll1Configuration, 5, |reg| reg
+            .set_y2y3_state_selection(control_input, value))
+    }
+
+    #[doc(alias = "ssc1dc")]
+    #[cfg(feature = "ssc")]
+    pub async fn pll1_ssc_down_center_selection(
+        &mut self,
+    ) -> Result<SscDownCenterSelection, Error<I2C::Error>> {
+        read!(self, Pll1Configuration, 6, |reg| reg
+            .pll1_ssc_down_center_selection())
+    }
+
+    #[doc(alias = "set_ssc1dc")]
+    #[cfg(feature = "ssc")]
+    pub async fn set_pll1_ssc_down_center_selection(
+        &mut self,
+        value: SscDownCenterSelection,
+    ) -> Result<(), Error<I2C::Error>> {
+        modify!(self, Pll1Configuration, 6, |reg| reg
+            .set_pll1_ssc_down_center_selection(value))
+    }
+
+    #[doc(alias = "pdiv2")]
+    pub async fn y2_output_divider(&mut self) -> Result<u7, Error<I2C::Error>> {
+        read!(self, Pll1Configuration, 6, |reg| u7::new(reg.pdiv2()))
+    }
+
+    #[doc(alias = "set_pdiv2")]
+    pub async fn set_y2_output_divider(&mut self, value: u7) -> Result<(), Error<I2C::Error>> {
+        modify!(self, Pll1Configuration, 6, |reg| reg
+            .set_pdiv2(value.value()))
+    }
+
+    #[doc(alias = "pdiv3")]
+    pub async fn y3_output_divider(&mut self) -> Result<u7, Error<I2C::Error>> {
+        read!(self, Pll1Configuration, 7, |reg| u7::new(reg.pdiv3()))
+    }
+
+    #[doc(alias = "set_pdiv3")]
+    pub async fn set_y3_output_divider(&mut self, value: u7) -> Result<(), Error<I2C::Error>> {
+        modify!(self, Pll1Configuration, 7, |reg| reg
+            .set_pdiv3(value.value()))
+    }
+
+    /// N/R/Q/P and VCO range of one of the two PLL1 settings banks
+    pub async fn pll_settings(
+        &mut self,
+        bank: Fs1Selection,
+    ) -> Result<PllSettings, Error<I2C::Error>> {
+        PllBlock::new(self, Register::Pll1Configuration as u8)
+            .settings(bank)
+            .await
+    }
+
+    /// Writes one of the two PLL1 settings banks. Under
+    /// [`Sequencing::Strict`] this is refused while PLL1 outputs are enabled.
+    pub async fn set_pll_settings(
+        &mut self,
+        bank: Fs1Selection,
+        value: PllSettings,
+    ) -> Result<(), Error<I2C::Error>> {
+        PllBlock::new(self, Register::Pll1Configuration as u8)
+            .set_settings(bank, value)
+            .await
+    }
+
+    /// Copies the N/R/Q/P word and VCO range of the `source` PLL1 bank into
+    /// the other bank, e.g. to prepare a new frequency in the inactive bank
+    /// before flipping FS1. Takes one block read and one block write. Under
+    /// [`Sequencing::Strict`] this is refused while PLL1 outputs are enabled.
+    pub async fn copy_pll_config(&mut self, source: Fs1Selection) -> Result<(), Error<I2C::Error>> {
+        self.ensure_pll1_retunable().await?;
+
+        let (from, to) = match source {
+            Fs1Selection::Fvcxo0 => (PLL1_0_OFFSET, PLL1_1_OFFSET),
+            Fs1Selection::Fvcxo1 => (PLL1_1_OFFSET, PLL1_0_OFFSET),
+        };
+
+        let word: [u8; 4] = self.read_block(from).await?;
+
+        self.write_block(to, &word).await
+    }
+
+    /// Exchanges the settings of the two PLL1 banks, with one block read and
+    /// one block write, and the same [`Sequencing::Strict`] check as
+    /// [`Self::copy_pll_config`]
+    pub async fn swap_pll_config(&mut self) -> Result<(), Error<I2C::Error>> {
+        self.ensure_pll1_retunable().await?;
+
+        let mut words: [u8; 8] = self.read_block(PLL1_0_OFFSET).await?;
+        words.rotate_left(4);
+
+        self.write_block(PLL1_0_OFFSET, &words).await
+    }
+
+    #[doc(alias = "pll1_0")]
+    pub async fn pll1_0_settings(&mut self) -> Result<PllSettings, Error<I2C::Error>> {
+        self.pll_settings(Fs1Selection::Fvcxo0).await
+    }
+
+    #[doc(alias = "set_pll1_0")]
+    pub async fn set_pll1_0_settings(
+        &mut self,
+        value: PllSettings,
+    ) -> Result<(), Error<I2C::Error>> {
+        self.set_pll_settings(Fs1Selection::Fvcxo0, value).await
+    }
+
+    #[doc(alias = "pll1_1")]
+    pub async fn pll1_1_settings(&mut self) -> Result<PllSettings, Error<I2C::Error>> {
+        self.pll_settings(Fs1Selection::Fvcxo1).await
+    }
+
+    #[doc(alias = "set_pll1_1")]
+    pub async fn set_pll1_1_settings(
+        &mut self,
+        value: PllSettings,
+    ) -> Result<(), Error<I2C::Error>> {
+        self.set_pll_settings(Fs1Selection::Fvcxo1, value).await
+    }
+
+    /// VCO frequency range of one of the PLL1 settings banks, without reading
+    /// the rest of its [`PllSettings`]
+    #[doc(alias = "vco1_0_range")]
+    #[doc(alias = "vco1_1_range")]
+    pub async fn vco_range(
+        &mut self,
+        bank: Fs1Selection,
+    ) -> Result<VcoRangeSelection, Error<I2C::Error>> {
+        match bank {
+            Fs1Selection::Fvcxo0 => {
+                self.with::<Pll1ConfigurationRegisterB, _>(
+                    Register::Pll1Configuration as u8 + 0xB,
+                    |reg| reg.vco1_0_range_selection(),
+                )
+                .await
+            }
+            Fs1Selection::Fvcxo1 => {
+                self.with::<Pll1ConfigurationRegisterF, _>(
+                    Register::Pll1Configuration as u8 + 0xF,
+                    |reg| reg.vco1_1_range_selection(),
+                )
+                .await
+            }
+        }
+    }
+
+    /// Changes only the VCO range bits of one of the PLL1 settings banks.
+    /// Like the full settings setters, this is refused under
+    /// [`Sequencing::Strict`] while PLL1 outputs are enabled.
+    #[doc(alias = "set_vco1_0_range")]
+    #[doc(alias = "set_vco1_1_range")]
+    pub async fn set_vco_range(
+        &mut self,
+        bank: Fs1Selection,
+        range: VcoRangeSelection,
+    ) -> Result<(), Error<I2C::Error>> {
+        self.ensure_pll1_retunable().await?;
+
+        match bank {
+            Fs1Selection::Fvcxo0 => {
+                self.modify_byte_unchecked::<Pll1ConfigurationRegisterB, _>(
+                    Register::Pll1Configuration as u8 + 0xB,
+                    |reg| reg.set_vco1_0_range_selection(range),
+                )
+                .await
+            }
+            Fs1Selection::Fvcxo1 => {
+                self.modify_byte_unchecked::<Pll1ConfigurationRegisterF, _>(
+                    Register::Pll1Configuration as u8 + 0xF,
+                    |reg| reg.set_vco1_1_range_selection(range),
+                )
+                .await
+            }
+        }
+    }
+
+    /// Reads the N/R/Q/P settings of one of the PLL1 banks and computes the
+    /// VCO frequency they give for the reference set with
+    /// [`Self::set_reference_frequency_hz`]. Check
+    /// [`VcoFrequency::range_matches`] to see whether the programmed VCO
+    /// range agrees with it.
+    pub async fn vco_frequency(
+        &mut self,
+        bank: Fs1Selection,
+    ) -> Result<VcoFrequency, Error<I2C::Error>> {
+        let reference_hz = self.reference_hz.ok_or(Error::UnknownReferenceFrequency)?;
+
+        let settings = self.pll_settings(bank).await?;
+
+        let vco = VcoFrequency::new(settings, reference_hz).ok_or(Error::InvalidPllSettings)?;
+
+        if !vco.range_matches() {
+            warn!(
+                "VCO range {} programmed for {} Hz, expected {}",
+                vco.programmed_range as u8, vco.vco_hz, vco.expected_range as u8
+            );
+        }
+
+        Ok(vco)
+    }
+
+    /* ==== Output frequency ==== */
+
+    /// Retunes the PLL feeding output Y`n` and sets the output divider so the
+    /// output runs at exactly `hz`, using [`crate::planner::plan`] with the
+    /// reference set with [`Self::set_reference_frequency_hz`]. Both settings
+    /// banks of the PLL are written, so the frequency doesn't depend on the
+    /// control inputs, and the other outputs of the PLL follow the new VCO
+    /// frequency. Y1 is only affected if it is sourced from PLL1.
+    ///
+    /// Fails with [`Error::FrequencyUnachievable`] before writing anything if
+    /// no setting reaches `hz` exactly, with [`Error::ReferenceOutOfRange`]
+    /// if the reference doesn't suit the input INCLK selects, and with
+    /// [`Error::OutputNotOnPll`] unless Y`n` runs from its PLL, not bypassed,
+    /// through its own divider: PDIV1 with M1 on PLL1 for Y1, PDIV`n`
+    /// otherwise. Under
+    /// [`Sequencing::Strict`] this is refused for PLL1 while its outputs are
+    /// enabled.
+    #[cfg(feature = "planner")]
+    pub async fn set_output_frequency(&mut self, n: u8, hz: u32) -> Result<(), Error<I2C::Error>> {
+        let (reference_hz, limits) = self.planning_inputs(n).await?;
+        let plan = plan(reference_hz, hz, &limits).map_err(|_| Error::FrequencyUnachievable(hz))?;
+
+        self.write_output_plan(n, hz, &plan).await
+    }
+
+    // Checks the output number and gathers what the planner needs
+    #[cfg(feature = "planner")]
+    async fn planning_inputs(&mut self, n: u8) -> Result<(u32, Limits), Error<I2C::Error>> {
+        if n == 0 || n > D::OUTPUTS {
+            return Err(Error::NoSuchOutput(n));
+        }
+
+        let reference_hz = self.reference_hz.ok_or(Error::UnknownReferenceFrequency)?;
+        let input = self.input_clock().await?;
+        self.check_reference(input)?;
+
+        let limits = match self.supply_voltage {
+            Some(supply) => Limits::for_supply(supply),
+            None => Limits::for_variant(self.device_identification().await?),
+        };
+
+        Ok((reference_hz, limits))
+    }
+
+    #[cfg(feature = "planner")]
+    async fn write_output_plan(
+        &mut self,
+        n: u8,
+        hz: u32,
+        plan: &PllPlan,
+    ) -> Result<(), Error<I2C::Error>> {
+        debug!(
+            "Y{} to {} Hz: VCO {} Hz, divider {}",
+            n, hz, plan.vco_hz, plan.pdiv
+        );
+
+        let base = Self::output_pll_offset(n);
+        if base == Register::Pll1Configuration as u8 {
+            self.ensure_pll1_retunable().await?;
+        }
+
+        let settings = plan.settings.0.to_be_bytes();
+
+        // The divider and both banks go out with one block write, so the
+        // output never runs with a mix of old and new settings. The routing
+        // is checked on the same block read.
+        if n == 1 {
+            // PDIV1 is in the generic registers, so the write reaches across
+            // the reserved registers, which get back what they read as
+            let offset = Register::GenericConfiguration as u8 + 2;
+            let mut bytes = self.read_block::<30>(offset).await?;
+
+            let mut reg2 = GenericConfigurationRegister2::from(bytes[0]);
+            let mux1 = Pll1ConfigurationRegister4::from(bytes[0x12]).pll1_multiplexer();
+            if reg2.y1_clock_source() != Y1ClockSource::Pll1Clock || mux1 != Pll1Multiplexer::Pll1 {
+                return Err(Error::OutputNotOnPll(n));
+            }
+
+            let pdiv = u16::from(plan.pdiv);
+            let mut reg3 = GenericConfigurationRegister3::from(bytes[1]);
+            let mut reg6 = GenericConfigurationRegister6::from(bytes[4]);
+            reg2.set_pdiv1_9_8((pdiv >> 8) as _);
+            reg3.set_pdiv1_7_0((pdiv & 0xFF) as _);
+            // EEWRITE is a command, writing it back could start a commit
+            reg6.set_eewrite(false);
+            bytes[0] = reg2.into();
+            bytes[1] = reg3.into();
+            bytes[4] = reg6.into();
+
+            bytes[0x16..0x1A].copy_from_slice(&settings);
+            bytes[0x1A..].copy_from_slice(&settings);
+
+            return self.write_block(offset, &bytes).await;
+        }
+
+        let mut bytes = self.read_block::<12>(base + 4).await?;
+
+        let reg4 = Pll1ConfigurationRegister4::from(bytes[0]);
+        let own_divider = if n.is_multiple_of(2) {
+            reg4.output_y2_multiplexer() == OutputY2Multiplexer::Pdiv2
+        } else {
+            reg4.output_y3_source() == Ok(OutputY3Source::Pdiv3)
+        };
+        if !own_divider || reg4.pll1_multiplexer() != Pll1Multiplexer::Pll1 {
+            return Err(Error::OutputNotOnPll(n));
+        }
+
+        if n.is_multiple_of(2) {
+            let mut reg6 = Pll1ConfigurationRegister6::from(bytes[2]);
+            reg6.set_pdiv2(plan.pdiv);
+            bytes[2] = reg6.into();
+        } else {
+            let mut reg7 = Pll1ConfigurationRegister7::from(bytes[3]);
+            reg7.set_pdiv3(plan.pdiv);
+            bytes[3] = reg7.into();
+        }
+
+        bytes[4..8].copy_from_slice(&settings);
+        bytes[8..].copy_from_slice(&settings);
+
+        self.write_block(base + 6, &bytes[2..]).await
+    }
+
+    // Base offset of the PLL block feeding output Y`n`, PLL1 for Y1
+    #[cfg(feature = "planner")]
+    fn output_pll_offset(n: u8) -> u8 {
+        Register::Pll1Configuration as u8 + 0x10 * (n.max(2) / 2 - 1)
+    }
+
+    // Frequency of output Y`n` the way [`Self::set_output_frequency`] sets it
+    // up: the VCO of the first settings bank of its PLL divided by the output
+    // divider, rounded like [`PllPlan::output_hz`]. `None` while the divider
+    // is held in reset.
+    #[cfg(feature = "planner")]
+    async fn planned_output_frequency(
+        &mut self,
+        n: u8,
+        reference_hz: u32,
+    ) -> Result<Option<u32>, Error<I2C::Error>> {
+        let base = Self::output_pll_offset(n);
+        let settings = PllSettings(u32::from_be_bytes(self.read_block(base + 8).await?));
+        let m = settings.m().ok_or(Error::InvalidPllSettings)?;
+        let divider = self.output(n)?.divider().await?;
+
+        // fIN * N / (M * PDIV), without truncating the VCO frequency first
+        let num = reference_hz as u64 * settings.pllx_yn() as u64;
+        let den = m as u64 * divider.value() as u64;
+
+        Ok((num + den / 2).checked_div(den).map(|hz| hz as u32))
+    }
+
+    /* ==== Control input truth table ==== */
+
+    /// Reads the FS1_x, SSC1_x, Y1_x and Y2Y3_x registers and assembles them
+    /// into one profile per control input combination
+    pub async fn control_profiles(&mut self) -> Result<ControlProfiles, Error<I2C::Error>> {
+        let y1 = read!(self, GenericConfiguration, 4, |reg| *reg)?;
+        let ssc = unpack_ssc1_selections([
+            read!(self, Pll1Configuration, 0, |reg| reg.0)?,
+            read!(self, Pll1Configuration, 1, |reg| reg.0)?,
+            read!(self, Pll1Configuration, 2, |reg| reg.0)?,
+        ]);
+        let fs1 = read!(self, Pll1Configuration, 3, |reg| *reg)?;
+        let y2y3 = read!(self, Pll1Configuration, 5, |reg| *reg)?;
+
+        let mut profiles = ControlProfiles::default();
+
+        for state in ControlInputState::ALL {
+            profiles.set(
+                state,
+                ControlProfile {
+                    pll_bank: fs1.fs1_selection(state),
+                    ssc: ssc[state as usize],
+                    y1: y1.y1_state_selection(state),
+                    y2y3: y2y3.y2y3_state_selection(state),
+                },
+            );
+        }
+
+        Ok(profiles)
+    }
+
+    /// Writes all four per-control-input registers so that each control input
+    /// combination behaves as described by `profiles`. The PLL1 registers go
+    /// out in one block write before the Y1 selections, so a dropped future
+    /// leaves at most the Y1 selections behind.
+    pub async fn set_control_profiles(
+        &mut self,
+        profiles: &ControlProfiles,
+    ) -> Result<(), Error<I2C::Error>> {
+        let mut y1 = GenericConfigurationRegister4(0);
+        let mut fs1 = Pll1ConfigurationRegister3(0);
+        let mut y2y3 = Pll1ConfigurationRegister5(0);
+        let mut ssc = [u3::new(0); 8];
+
+        for (state, profile) in profiles.iter() {
+            y1.set_y1_state_selection(state, profile.y1);
+            fs1.set_fs1_selection(state, profile.pll_bank);
+            y2y3.set_y2y3_state_selection(state, profile.y2y3);
+            ssc[state as usize] = profile.ssc;
+        }
+
+        // Everything but the Y1 selections is in PLL1 registers 0-5, which
+        // are staged and committed together. Register 4 is carried over.
+        self.update_block(Register::Pll1Configuration as u8, |bytes: &mut [u8; 6]| {
+            bytes[..3].copy_from_slice(&pack_ssc1_selections(ssc));
+            bytes[3] = fs1.into();
+            bytes[5] = y2y3.into();
+        })
+        .await?;
+
+        self.write_byte_unchecked(Register::GenericConfiguration as u8 + 4, y1.into())
+            .await
+    }
+
+    /// Reads the device and works out which PLL bank, SSC setting and output
+    /// states are active while the control inputs are in `state`, including
+    /// output frequencies if [`Self::set_reference_frequency_hz`] was called
+    pub async fn effective_config_for(
+        &mut self,
+        state: ControlInputState,
+    ) -> Result<EffectiveConfig, Error<I2C::Error>> {
+        let config = self.read_config().await?;
+
+        Ok(config.effective_config(state, self.reference_hz))
+    }
+
+    /// Reads the device and describes how the clocks are routed from the
+    /// input to the outputs while the control inputs are in `state`, with
+    /// frequencies if [`Self::set_reference_frequency_hz`] was called
+    pub async fn clock_tree(
+        &mut self,
+        state: ControlInputState,
+    ) -> Result<ClockTree, Error<I2C::Error>> {
+        let config = self.read_config().await?;
+
+        Ok(config.clock_tree(state, self.reference_hz))
+    }
+
+    /* ==== Whole-device configuration ==== */
+
+    /// Reads the complete register window into a [`DeviceConfig`]. On the
+    /// larger family members that leaves out the PLL blocks past PLL1.
+    pub async fn read_config(&mut self) -> Result<DeviceConfig, Error<I2C::Error>> {
+        let mut registers = [0u8; REGISTER_WINDOW_LEN];
+
+        for (offset, byte) in registers.iter_mut().enumerate() {
+            *byte = self.read_byte_unchecked(offset as u8).await?;
+        }
+
+        Ok(DeviceConfig::from_bytes(registers))
+    }
+
+    /// Writes every configuration register from `config` to the volatile
+    /// registers. EELOCK is left as it currently is on the device (use
+    /// [`Self::lock_eeprom_permanently`] for that) and EEWRITE is never set, so
+    /// this doesn't touch the EEPROM. TARGET_ADR is kept as well, since the
+    /// device would stop answering on its address part way through; change
+    /// it with [`Self::set_target_address`]. All three show up in
+    /// [`ChangeReport::skipped_fields`] if `config` asks for something else.
+    /// A SPICON handing the serial interface pins to S1/S2 is written last,
+    /// after everything else went out.
+    ///
+    /// The register window is read with one block read first, so the returned
+    /// [`ChangeReport`] records exactly which registers and fields changed.
+    /// The generic and the PLL1 registers are then each committed with one
+    /// block write, in that order. If the future is dropped in between, only
+    /// the generic registers have changed and calling this again completes the
+    /// configuration.
+    ///
+    /// If the bus fails during either write, e.g. because the device stopped
+    /// acknowledging part way through a block, the registers as read
+    /// beforehand are written back. [`Error::ApplyFailed`] then tells whether
+    /// that succeeded, i.e. whether the device holds its old configuration or
+    /// an undefined mix.
+    pub async fn apply_config(
+        &mut self,
+        config: &DeviceConfig,
+    ) -> Result<ChangeReport, Error<I2C::Error>> {
+        let before = self.read_block::<REGISTER_WINDOW_LEN>(0x00).await?;
+
+        self.write_config(DeviceConfig::from_bytes(before), config)
+            .await
+    }
+
+    /// Same as [`Self::apply_config`], but only if the device still holds
+    /// `expected_baseline`, for buses where another master may also
+    /// reconfigure the device. Returns [`Error::Conflict`] without writing
+    /// anything if the configuration bits differ.
+    ///
+    /// The check uses the block read [`Self::apply_config`] does anyway, so it
+    /// costs no extra traffic. It narrows the window for lost updates to the
+    /// time between that read and the writes but can't close it, the device
+    /// has no way to lock out other masters.
+    pub async fn compare_and_apply(
+        &mut self,
+        expected_baseline: &DeviceConfig,
+        new_config: &DeviceConfig,
+    ) -> Result<ChangeReport, Error<I2C::Error>> {
+        let before = DeviceConfig::from_bytes(self.read_block::<REGISTER_WINDOW_LEN>(0x00).await?);
+
+        if let Some(mismatch) = expected_baseline.diff(&before).mismatches().next() {
+            warn!(
+                "register {:#x} changed behind our back, not applying",
+                mismatch.offset
+            );
+            return Err(Error::Conflict(mismatch));
+        }
+
+        self.write_config(before, new_config).await
+    }
+
+    // Writes `config` on top of `before`, the register window as just read.
+    // If the bus fails part way, writes `before` back.
+    async fn write_config(
+        &mut self,
+        before: DeviceConfig,
+        config: &DeviceConfig,
+    ) -> Result<ChangeReport, Error<I2C::Error>> {
+        let report = ChangeReport::new(before, *config, true);
+
+        match self.write_writable_registers(report.after()).await {
+            Ok(()) => Ok(report),
+            Err(
+                Error::I2c(error)
+                | Error::AddressNack(error)
+                | Error::DataNack(error)
+                | Error::ArbitrationLoss(error),
+            ) => {
+                warn!("applying the configuration failed, restoring the previous one");
+
+                // Goes through the same fix-ups, so EEWRITE stays clear
+                let restore = ChangeReport::new(before, before, true);
+                let rolled_back = self.write_writable_registers(restore.after()).await.is_ok();
+
+                Err(Error::ApplyFailed { error, rolled_back })
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn write_writable_registers(
+        &mut self,
+        config: &DeviceConfig,
+    ) -> Result<(), Error<I2C::Error>> {
+        let mut registers = WRITABLE_OFFSETS.map(|offset| config.register(offset));
+
+        // With SPICON set the bus is gone, so it waits for the PLL1 registers
+        let reg2 = GenericConfigurationRegister2::from(registers[1]);
+        let mut staged = reg2;
+        staged.set_serial_interface_pin_mode(SerialInterfacePinMode::SerialProgrammingInterface);
+        registers[1] = staged.into();
+
+        let (generic, pll1) = registers.split_at(6);
+        self.write_block(WRITABLE_OFFSETS[0], generic).await?;
+        self.write_block(Register::Pll1Configuration as u8, pll1)
+            .await?;
+
+        if staged != reg2 {
+            self.write_byte_unchecked(Register::GenericConfiguration as u8 + 2, reg2.into())
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Compares the device against `config` without writing anything. The
+    /// returned [`ChangeReport`] lists what [`Self::apply_config`] would change,
+    /// so [`ChangeReport::is_unchanged`] means the device already holds
+    /// `config`.
+    pub async fn verify_config(
+        &mut self,
+        config: &DeviceConfig,
+    ) -> Result<ChangeReport, Error<I2C::Error>> {
+        let current = self.read_block::<REGISTER_WINDOW_LEN>(0x00).await?;
+
+        Ok(ChangeReport::new(
+            DeviceConfig::from_bytes(current),
+            *config,
+            false,
+        ))
+    }
+}
+
+/// Waiting for conditions on the device, using the delay the driver was
+/// created with, see [`CDCx913::new_with_delay`]
+impl<I2C: I2c, D: Device, DELAY: DelayNs> CDCx913<I2C, D, DELAY> {
+    /// Waits out [`STABILIZATION_TIME_US`], the time the device needs after
+    /// power is applied or power-down is released to re-load its EEPROM,
+    /// start the oscillator and lock the PLL. The outputs aren't stable
+    /// before then.
+    pub async fn wait_for_startup(&mut self) {
+        self.delay.delay_us(STABILIZATION_TIME_US).await;
+    }
+
+    /// Reads register `T` every [`POLL_INTERVAL_MS`] until `predicate` accepts
+    /// it and returns the accepted value, e.g. to wait for EEPIP:
+    ///
+    /// ```ignore
+    /// driver
+    ///     .poll_register_until(
+    ///         |reg: &GenericConfigurationRegister1| {
+    ///             reg.eeprom_programming_status() == EepromProgrammingStatus::Completed
+    ///         },
+    ///         EEPROM_WRITE_TIMEOUT_MS,
+    ///     )
+    ///     .await?;
+    /// ```
+    ///
+    /// Fails with [`Error::PollTimeout`] if `predicate` still refuses the
+    /// register after `timeout_ms`. PLL register types refer to the PLL1
+    /// block, like everywhere else.
+    pub async fn poll_register_until<T: TypedRegister>(
+        &mut self,
+        mut predicate: impl FnMut(&T) -> bool,
+        timeout_ms: u32,
+    ) -> Result<T, Error<I2C::Error>> {
+        let mut elapsed_ms = 0;
+
+        loop {
+            let reg = self.read_typed::<T>().await?;
+
+            if predicate(&reg) {
+                return Ok(reg);
+            }
+
+            if elapsed_ms >= timeout_ms {
+                warn!(
+                    "register {:#x} still not as expected after {} ms",
+                    T::OFFSET.value(),
+                    timeout_ms
+                );
+                return Err(Error::PollTimeout);
+            }
+
+            self.delay.delay_ms(POLL_INTERVAL_MS).await;
+            elapsed_ms += POLL_INTERVAL_MS;
+        }
+    }
+}
+
+/// Everything that has to wait for the device, using the delay the driver was
+/// created with, see [`CDCx913::new_with_delay`]
+#[cfg(feature = "eeprom")]
+impl<I2C: I2c, D: Device, DELAY: DelayNs> CDCx913<I2C, D, DELAY> {
+    /// Sets EEWRITE to commit the current register contents to the EEPROM and
+    /// waits for EEPIP to clear, giving up after [`EEPROM_WRITE_TIMEOUT_MS`].
+    #[doc(alias = "eewrite")]
+    pub async fn initiate_eeprom_write(&mut self) -> Result<(), Error<I2C::Error>> {
+        self.initiate_eeprom_write_with_timeout(EEPROM_WRITE_TIMEOUT_MS)
+            .await
+    }
+
+    /// Same as [`Self::initiate_eeprom_write`] but with a caller-provided
+    /// timeout. Returns [`Error::EepromTimeout`] if EEPIP is still set once the
+    /// timeout elapses, in which case EEWRITE is left set.
+    #[doc(alias = "eewrite")]
+    pub async fn initiate_eeprom_write_with_timeout(
+        &mut self,
+        timeout_ms: u32,
+    ) -> Result<(), Error<I2C::Error>> {
+        let mut handle = self.start_eeprom_write().await?;
+        let mut elapsed_ms = 0;
+
+        while handle.poll().await? == EepromProgrammingStatus::InProgress {
+            if elapsed_ms >= timeout_ms {
+                warn!("EEPROM write still in progress after {} ms", timeout_ms);
+                return Err(Error::EepromTimeout);
+            }
+
+            handle.driver.delay.delay_ms(EEPROM_POLL_INTERVAL_MS).await;
+            elapsed_ms += EEPROM_POLL_INTERVAL_MS;
+        }
+
+        Ok(())
+    }
+
+    /// Sets EELOCK and commits it to the EEPROM. From then on the EEPROM is
+    /// read-only forever: on the fly configuration of the registers is still
+    /// allowed but the power-up defaults can never be changed again.
+    #[doc(alias = "set_eelock")]
+    pub async fn lock_eeprom_permanently(
+        &mut self,
+        _confirmation: EepromLockConfirmation,
+    ) -> Result<(), Error<I2C::Error>> {
+        warn!("locking the EEPROM permanently");
+
+        modify!(self, GenericConfiguration, 1, |reg| reg.set_eelock(true))?;
+
+        self.initiate_eeprom_write().await
+    }
+
+    /// Applies `config` to the volatile registers and then commits it to the
+    /// EEPROM, so it becomes the power-up default of the device. Returns the
+    /// report of [`Self::apply_config`].
+    pub async fn write_config_to_eeprom(
+        &mut self,
+        config: &DeviceConfig,
+    ) -> Result<ChangeReport, Error<I2C::Error>> {
+        let changes = self.apply_config(config).await?;
+
+        self.initiate_eeprom_write().await?;
+
+        Ok(changes)
+    }
+
+    /// Discards any volatile changes by cycling the device through power-down,
+    /// which makes it re-load its registers from the EEPROM, and waits for it
+    /// to settle afterwards
+    pub async fn reload_from_eeprom(&mut self) -> Result<(), Error<I2C::Error>> {
+        debug!("reloading the registers from the EEPROM");
+
+        self.set_power_down(true).await?;
+        self.set_power_down(false).await?;
+
+        self.wait_for_startup().await;
+
+        Ok(())
+    }
+
+    /// Checks that `expected` actually made it into the EEPROM by re-loading it
+    /// (see [`Self::reload_from_eeprom`]) and comparing the resulting
+    /// configuration against `expected`. Any volatile changes that weren't
+    /// committed are lost in the process.
+    pub async fn verify_eeprom_commit(
+        &mut self,
+        expected: &DeviceConfig,
+    ) -> Result<ConfigDiff, Error<I2C::Error>> {
+        self.reload_from_eeprom().await?;
+
+        let actual = self.read_config().await?;
+        let diff = expected.diff(&actual);
+
+        #[cfg(feature = "stats")]
+        if !diff.is_empty() {
+            self.stats.verify_failures = self.stats.verify_failures.wrapping_add(1);
+        }
+
+        Ok(diff)
+    }
+
+    /// Production provisioning in one call: applies `config`, commits it to the
+    /// EEPROM and then, depending on `options`, verifies the commit and
+    /// permanently locks the EEPROM. A failed verification skips the lock.
+    pub async fn provision(
+        &mut self,
+        config: &DeviceConfig,
+        options: ProvisionOptions,
+    ) -> Result<ProvisionReport, Error<I2C::Error>> {
+        let changes = self.write_config_to_eeprom(config).await?;
+
+        let mut report = ProvisionReport {
+            changes,
+            verification: None,
+            locked: false,
+        };
+
+        if options.verify {
+            report.verification = Some(self.verify_eeprom_commit(config).await?);
+        }
+
+        if let Some(confirmation) = options.lock
+            && report.is_verified()
+        {
+            self.lock_eeprom_permanently(confirmation).await?;
+            report.locked = true;
+        }
+
+        Ok(report)
+    }
+
+    /// Programs `golden` into the EEPROM only if the device doesn't already
+    /// match it, so firmware can call this on every boot without wearing out
+    /// the EEPROM. The comparison is done against the current registers, which
+    /// reflect the EEPROM as long as nothing was changed since power-up.
+    /// Returns [`Error::EepromLocked`] if the device differs but can't be
+    /// reprogrammed.
+    pub async fn ensure_provisioned(
+        &mut self,
+        golden: &DeviceConfig,
+    ) -> Result<ProvisionOutcome, Error<I2C::Error>> {
+        let current = self.read_config().await?;
+
+        if golden.diff(&current).is_empty() {
+            debug!("already provisioned");
+            return Ok(ProvisionOutcome::AlreadyProvisioned);
+        }
+
+        if self.eeprom_permanently_locked().await? {
+            warn!("configuration differs but the EEPROM is locked");
+            return Err(Error::EepromLocked);
+        }
+
+        debug!("configuration differs, reprogramming the EEPROM");
+
+        self.write_config_to_eeprom(golden).await?;
+
+        Ok(ProvisionOutcome::Programmed)
+    }
+}
+
+/// Frequency sweeps and ramps, waiting with the delay the driver was created
+/// with
+#[cfg(feature = "planner")]
+impl<I2C: I2c, D: Device, DELAY: DelayNs> CDCx913<I2C, D, DELAY> {
+    /// Steps output Y`n` from `start_hz` to `stop_hz` (in either direction) in
+    /// steps of `step_hz`, staying on each frequency for `dwell_ms`, e.g. for
+    /// EMC pre-scans. The last step is shortened to end exactly on `stop_hz`.
+    /// Each step goes through [`Self::set_output_frequency`]; frequencies no
+    /// setting reaches exactly are skipped and counted in the return value.
+    /// A `step_hz` of 0 is treated as 1.
+    pub async fn sweep_output_frequency(
+        &mut self,
+        n: u8,
+        start_hz: u32,
+        stop_hz: u32,
+        step_hz: u32,
+        dwell_ms: u32,
+    ) -> Result<u32, Error<I2C::Error>> {
+        let step_hz = step_hz.max(1);
+        let mut hz = start_hz;
+        let mut skipped = 0;
+
+        loop {
+            match self.set_output_frequency(n, hz).await {
+                Ok(()) => self.delay.delay_ms(dwell_ms).await,
+                Err(Error::FrequencyUnachievable(_)) => {
+                    warn!("skipping {} Hz, no setting reaches it", hz);
+                    skipped += 1;
+                }
+                Err(e) => return Err(e),
+            }
+
+            if hz == stop_hz {
+                return Ok(skipped);
+            }
+
+            hz = if stop_hz > hz {
+                hz.saturating_add(step_hz).min(stop_hz)
+            } else {
+                hz.saturating_sub(step_hz).max(stop_hz)
+            };
+        }
+    }
+
+    /// Moves output Y`n` to `target_hz` in steps of at most `max_step_ppm` of
+    /// the current frequency, staying on each intermediate frequency for
+    /// `dwell_ms`, for downstream parts that only tolerate limited changes of
+    /// their input clock at a time. The ramp starts from the frequency the
+    /// first settings bank of the PLL and the output divider give. Each step
+    /// is written like [`Self::set_output_frequency`] does, so the PLL relocks
+    /// at every step. Intermediate frequencies only need to be as close to a
+    /// full step as the PLL gets, see [`crate::planner::plan_nearest`]; the
+    /// last step lands on `target_hz` exactly. Returns the number of steps.
+    ///
+    /// Fails with [`Error::FrequencyUnachievable`] before writing anything if
+    /// `target_hz` can't be reached exactly, and with the frequency of the
+    /// next step if no setting lies between the current frequency and it.
+    /// An output whose divider is held in reset has no frequency to ramp
+    /// from and fails with [`Error::OutputStopped`]; start it with
+    /// [`Self::set_output_frequency`] instead.
+    ///
+    /// Fractional frequencies, of the intermediate steps or the one the ramp
+    /// starts from, are rounded to the nearest hertz, so a step may exceed
+    /// `max_step_ppm` by that half hertz.
+    pub async fn ramp_output_frequency(
+        &mut self,
+        n: u8,
+        target_hz: u32,
+        max_step_ppm: u32,
+        dwell_ms: u32,
+    ) -> Result<u32, Error<I2C::Error>> {
+        let (reference_hz, limits) = self.planning_inputs(n).await?;
+        let target = plan(reference_hz, target_hz, &limits)
+            .map_err(|_| Error::FrequencyUnachievable(target_hz))?;
+
+        let mut hz = self
+            .planned_output_frequency(n, reference_hz)
+            .await?
+            .ok_or(Error::OutputStopped(n))?;
+
+        let mut steps = 0;
+
+        while hz != target_hz {
+            // Capped by the distance left before narrowing, so the cast can't
+            // truncate
+            let max_step_hz = (hz as u64 * max_step_ppm as u64 / 1_000_000)
+                .clamp(1, hz.abs_diff(target_hz) as u64) as u32;
+
+            let (next_hz, next) = if hz.abs_diff(target_hz) <= max_step_hz {
+                (target_hz, target)
+            } else {
+                // Anything strictly between here and a full step makes
+                // progress without overshooting
+                let (full_step_hz, min_hz, max_hz) = if target_hz > hz {
+                    let full_step_hz = hz.saturating_add(max_step_hz);
+                    (full_step_hz, hz + 1, full_step_hz)
+                } else {
+                    let full_step_hz = hz.saturating_sub(max_step_hz);
+                    (full_step_hz, full_step_hz, hz - 1)
+                };
+
+                let next = plan_nearest(reference_hz, full_step_hz, min_hz, max_hz, &limits)
+                    .map_err(|_| Error::FrequencyUnachievable(full_step_hz))?;
+
+                (next.output_hz, next)
+            };
+
+            self.write_output_plan(n, next_hz, &next).await?;
+            steps += 1;
+            hz = next_hz;
+
+            if hz != target_hz {
+                self.delay.delay_ms(dwell_ms).await;
+            }
+        }
+
+        Ok(steps)
+    }
+}
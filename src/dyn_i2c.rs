@@ -0,0 +1,116 @@
+//! Type-erased I2C transport, for firmware that drives devices on several
+//! kinds of bus. [`crate::CDCx913`] is generic over the bus, so every bus type
+//! gets its own copy of the whole driver. Going through [`DynI2c`] instead
+//! leaves one copy of the driver plus a few small shims per bus type:
+//!
+//! ```ignore
+//! let mut internal = CDCE913::new_dyn(&mut i2c1);
+//! let mut behind_mux = CDCE913::new_dyn(&mut mux_channel);
+//! ```
+//!
+//! Bus errors are reduced to their [`ErrorKind`] and every transfer boxes its
+//! future, so this trades a heap allocation per transfer for flash.
+
+use alloc::boxed::Box;
+use core::{future::Future, pin::Pin};
+
+use embedded_hal_async::i2c::{Error as _, ErrorKind, ErrorType, I2c, Operation};
+
+type BoxFuture<'a> = Pin<Box<dyn Future<Output = Result<(), ErrorKind>> + 'a>>;
+
+/// Dyn-compatible counterpart of [`I2c`], implemented for every bus
+pub trait ErasedI2c {
+    fn read<'a>(&'a mut self, address: u8, read: &'a mut [u8]) -> BoxFuture<'a>;
+
+    fn write<'a>(&'a mut self, address: u8, write: &'a [u8]) -> BoxFuture<'a>;
+
+    fn write_read<'a>(
+        &'a mut self,
+        address: u8,
+        write: &'a [u8],
+        read: &'a mut [u8],
+    ) -> BoxFuture<'a>;
+
+    fn transaction<'a, 'b: 'a>(
+        &'a mut self,
+        address: u8,
+        operations: &'a mut [Operation<'b>],
+    ) -> BoxFuture<'a>;
+}
+
+impl<T: I2c> ErasedI2c for T {
+    fn read<'a>(&'a mut self, address: u8, read: &'a mut [u8]) -> BoxFuture<'a> {
+        Box::pin(async move { I2c::read(self, address, read).await.map_err(|e| e.kind()) })
+    }
+
+    fn write<'a>(&'a mut self, address: u8, write: &'a [u8]) -> BoxFuture<'a> {
+        Box::pin(async move { I2c::write(self, address, write).await.map_err(|e| e.kind()) })
+    }
+
+    fn write_read<'a>(
+        &'a mut self,
+        address: u8,
+        write: &'a [u8],
+        read: &'a mut [u8],
+    ) -> BoxFuture<'a> {
+        Box::pin(async move {
+            I2c::write_read(self, address, write, read)
+                .await
+                .map_err(|e| e.kind())
+        })
+    }
+
+    fn transaction<'a, 'b: 'a>(
+        &'a mut self,
+        address: u8,
+        operations: &'a mut [Operation<'b>],
+    ) -> BoxFuture<'a> {
+        Box::pin(async move {
+            I2c::transaction(self, address, operations)
+                .await
+                .map_err(|e| e.kind())
+        })
+    }
+}
+
+/// [`I2c`] bus forwarding to any [`ErasedI2c`], see the module documentation
+pub struct DynI2c<'a> {
+    bus: &'a mut dyn ErasedI2c,
+}
+
+impl<'a> DynI2c<'a> {
+    pub fn new(bus: &'a mut dyn ErasedI2c) -> Self {
+        Self { bus }
+    }
+}
+
+impl ErrorType for DynI2c<'_> {
+    type Error = ErrorKind;
+}
+
+impl I2c for DynI2c<'_> {
+    async fn read(&mut self, address: u8, read: &mut [u8]) -> Result<(), Self::Error> {
+        self.bus.read(address, read).await
+    }
+
+    async fn write(&mut self, address: u8, write: &[u8]) -> Result<(), Self::Error> {
+        self.bus.write(address, write).await
+    }
+
+    async fn write_read(
+        &mut self,
+        address: u8,
+        write: &[u8],
+        read: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.bus.write_read(address, write, read).await
+    }
+
+    async fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.bus.transaction(address, operations).await
+    }
+}
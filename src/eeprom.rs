@@ -0,0 +1,252 @@
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+#[cfg(feature = "eeprom")]
+use embedded_hal_async::i2c::I2c;
+
+#[cfg(feature = "eeprom")]
+use crate::{
+    CDCx913, Error, family::Device, registers::generic_configuration::EepromProgrammingStatus,
+    timing::NoDelay,
+};
+use crate::{
+    config::{ChangeReport, ConfigDiff, DeviceConfig, REGISTER_WINDOW_LEN},
+    registers::generic_configuration::{
+        GenericConfigurationRegister1, GenericConfigurationRegister6,
+    },
+};
+
+/// Token required by [`crate::CDCx913::lock_eeprom_permanently`]. Locking the
+/// EEPROM can't be undone, so it's only reachable by spelling out the
+/// confirmation constant rather than passing a `bool` that's easy to get wrong.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct EepromLockConfirmation(());
+
+impl EepromLockConfirmation {
+    pub const I_UNDERSTAND_THIS_IS_IRREVERSIBLE: Self = Self(());
+}
+
+/// The byte image committed to the EEPROM for a [`DeviceConfig`], laid out like
+/// the register window. This is what [`crate::CDCx913::write_config_to_eeprom`]
+/// ends up programming, so it can be handed to external programmers to produce
+/// parts that are identical to ones provisioned by firmware.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct EepromImage {
+    bytes: [u8; REGISTER_WINDOW_LEN],
+}
+
+impl EepromImage {
+    /// Builds the image for `config`. EEPIP and EEWRITE are cleared since they
+    /// are status/command bits, and EELOCK is cleared as well: use
+    /// [`Self::with_permanent_lock`] to produce an image for locked parts.
+    pub fn from_config(config: &DeviceConfig) -> Self {
+        let mut bytes = config.to_bytes();
+
+        let mut reg = GenericConfigurationRegister1::from(bytes[0x01]);
+        reg.set_eepip(false);
+        reg.set_eelock(false);
+        bytes[0x01] = reg.into();
+
+        let mut reg = GenericConfigurationRegister6::from(bytes[0x06]);
+        reg.set_eewrite(false);
+        bytes[0x06] = reg.into();
+
+        Self { bytes }
+    }
+
+    /// Sets EELOCK in the image, so parts programmed with it can never have
+    /// their EEPROM rewritten
+    pub fn with_permanent_lock(mut self, _confirmation: EepromLockConfirmation) -> Self {
+        let mut reg = GenericConfigurationRegister1::from(self.bytes[0x01]);
+
+        reg.set_eelock(true);
+        self.bytes[0x01] = reg.into();
+
+        self
+    }
+
+    pub fn to_bytes(&self) -> [u8; REGISTER_WINDOW_LEN] {
+        self.bytes
+    }
+
+    pub fn as_bytes(&self) -> &[u8; REGISTER_WINDOW_LEN] {
+        &self.bytes
+    }
+
+    pub fn to_config(&self) -> DeviceConfig {
+        DeviceConfig::from_bytes(self.bytes)
+    }
+}
+
+impl From<&DeviceConfig> for EepromImage {
+    fn from(config: &DeviceConfig) -> Self {
+        Self::from_config(config)
+    }
+}
+
+#[cfg(feature = "alloc")]
+const HEX_RECORD_LEN: usize = 16;
+
+#[cfg(feature = "alloc")]
+impl EepromImage {
+    /// Renders the image as Intel HEX, with the register offset as the load
+    /// address
+    pub fn to_intel_hex(&self) -> String {
+        let mut out = String::new();
+
+        for (index, chunk) in self.bytes.chunks(HEX_RECORD_LEN).enumerate() {
+            let address = (index * HEX_RECORD_LEN) as u16;
+
+            write_intel_hex_record(&mut out, address, 0x00, chunk);
+        }
+
+        write_intel_hex_record(&mut out, 0x0000, 0x01, &[]);
+
+        out
+    }
+
+    /// Renders the image as Motorola S-record (S19), with the register offset
+    /// as the load address
+    pub fn to_srec(&self) -> String {
+        let mut out = String::new();
+
+        write_srec_record(&mut out, '0', 0x0000, b"cdcx913");
+
+        for (index, chunk) in self.bytes.chunks(HEX_RECORD_LEN).enumerate() {
+            let address = (index * HEX_RECORD_LEN) as u16;
+
+            write_srec_record(&mut out, '1', address, chunk);
+        }
+
+        let record_count = self.bytes.len().div_ceil(HEX_RECORD_LEN) as u16;
+
+        write_srec_record(&mut out, '5', record_count, &[]);
+        write_srec_record(&mut out, '9', 0x0000, &[]);
+
+        out
+    }
+}
+
+#[cfg(feature = "alloc")]
+fn write_intel_hex_record(out: &mut String, address: u16, record_type: u8, data: &[u8]) {
+    let [address_hi, address_lo] = address.to_be_bytes();
+    let header = [data.len() as u8, address_hi, address_lo, record_type];
+
+    let sum = header
+        .iter()
+        .chain(data)
+        .fold(0u8, |sum, byte| sum.wrapping_add(*byte));
+
+    out.push(':');
+    push_hex(out, &header);
+    push_hex(out, data);
+    push_hex(out, &[sum.wrapping_neg()]);
+    out.push('\n');
+}
+
+#[cfg(feature = "alloc")]
+fn write_srec_record(out: &mut String, record_type: char, address: u16, data: &[u8]) {
+    let [address_hi, address_lo] = address.to_be_bytes();
+    // The count covers the address, the data and the checksum
+    let header = [data.len() as u8 + 3, address_hi, address_lo];
+
+    let sum = header
+        .iter()
+        .chain(data)
+        .fold(0u8, |sum, byte| sum.wrapping_add(*byte));
+
+    out.push('S');
+    out.push(record_type);
+    push_hex(out, &header);
+    push_hex(out, data);
+    push_hex(out, &[!sum]);
+    out.push('\n');
+}
+
+#[cfg(feature = "alloc")]
+fn push_hex(out: &mut String, bytes: &[u8]) {
+    use core::fmt::Write;
+
+    for byte in bytes {
+        // Writing into a String can't fail
+        let _ = write!(out, "{byte:02X}");
+    }
+}
+
+/// Options for [`crate::CDCx913::provision`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ProvisionOptions {
+    /// Re-load the EEPROM after writing it and compare the result against the
+    /// configuration, see [`crate::CDCx913::verify_eeprom_commit`]
+    pub verify: bool,
+    /// Permanently lock the EEPROM once it's been written. If `verify` is set,
+    /// the lock is only applied when verification passed.
+    pub lock: Option<EepromLockConfirmation>,
+}
+
+/// Outcome of each stage of [`crate::CDCx913::provision`]. Applying the
+/// configuration and writing the EEPROM either succeed or abort provisioning
+/// with an error, so only what was applied is part of the report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ProvisionReport {
+    /// What was programmed into the registers before committing them
+    pub changes: ChangeReport,
+    /// Result of the verification stage, `None` if it wasn't requested
+    pub verification: Option<ConfigDiff>,
+    /// Whether the EEPROM was permanently locked
+    pub locked: bool,
+}
+
+impl ProvisionReport {
+    /// `true` if verification (when requested) found no mismatches
+    pub fn is_verified(&self) -> bool {
+        self.verification.is_none_or(|diff| diff.is_empty())
+    }
+}
+
+/// What [`crate::CDCx913::ensure_provisioned`] had to do
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ProvisionOutcome {
+    /// The device already matched the golden configuration, nothing was written
+    AlreadyProvisioned,
+    /// The golden configuration was written to the EEPROM
+    Programmed,
+}
+
+/// An EEPROM write that has been started but not necessarily finished, see
+/// [`crate::CDCx913::start_eeprom_write`]. Call [`Self::poll`] at whatever pace
+/// suits the application until it reports completion; dropping the handle early
+/// leaves the write running on the device with EEWRITE still set.
+#[cfg(feature = "eeprom")]
+pub struct EepromWriteHandle<'a, I2C: I2c, D, DELAY = NoDelay> {
+    pub(crate) driver: &'a mut CDCx913<I2C, D, DELAY>,
+    pub(crate) polls: u32,
+}
+
+#[cfg(feature = "eeprom")]
+impl<I2C: I2c, D: Device, DELAY> EepromWriteHandle<'_, I2C, D, DELAY> {
+    /// Reads EEPIP once. When the write has completed, EEWRITE is cleared
+    /// before [`EepromProgrammingStatus::Completed`] is returned.
+    pub async fn poll(&mut self) -> Result<EepromProgrammingStatus, Error<I2C::Error>> {
+        self.polls += 1;
+
+        let status = self.driver.eeprom_programming_status().await?;
+
+        match status {
+            EepromProgrammingStatus::Completed => self.driver.finish_eeprom_write().await?,
+            EepromProgrammingStatus::InProgress => self.driver.record_retry(),
+        }
+
+        Ok(status)
+    }
+
+    /// How many times [`Self::poll`] has been called so far
+    pub fn polls(&self) -> u32 {
+        self.polls
+    }
+}
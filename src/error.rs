@@ -0,0 +1,190 @@
+#[cfg(feature = "driver")]
+use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
+
+use crate::{
+    config::RegisterMismatch, registers::generic_configuration::InputClockSelection,
+    routing::RoutingError,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Error<E> {
+    /// The underlying I2C bus reported an error not covered by the variants
+    /// below, including a missing acknowledge the bus can't attribute
+    I2c(E),
+    /// Nothing acknowledged the address: the device is absent, unpowered, on
+    /// another address or has handed its bus pins to S1/S2
+    AddressNack(E),
+    /// The device acknowledged its address but not a data byte, i.e. it's
+    /// there but busy, e.g. while it programs its EEPROM
+    DataNack(E),
+    /// Another bus master won arbitration, so the transfer can be retried
+    ArbitrationLoss(E),
+    /// EEPIP did not clear within the allotted time after starting an EEPROM
+    /// write. This usually points at a supply problem during programming.
+    EepromTimeout,
+    /// The EEPROM would have to be written but EELOCK is set
+    EepromLocked,
+    /// SPICON was switched to control pin mode, so the serial interface pins
+    /// now act as S1/S2 and must not see any more traffic
+    SerialInterfaceDisabled,
+    /// Refused to retune or bypass PLL1 while outputs fed by it are enabled,
+    /// see [`crate::Sequencing::Strict`]
+    OutputsEnabled,
+    /// The device has no PLL with the given number
+    NoSuchPll(u8),
+    /// The device has no output with the given number
+    NoSuchOutput(u8),
+    /// The divider does not fit the output, whose divider is only 7 bits wide
+    /// unless it is Y1
+    DividerOutOfRange,
+    /// No family member answered on any of the default addresses
+    NoDeviceFound,
+    /// A register field read back from the device holds an encoding the
+    /// datasheet marks as reserved, given as the raw field value
+    ReservedEncoding(u8),
+    /// The operation needs the input clock frequency, see
+    /// [`crate::CDCx913::set_reference_frequency_hz`]
+    UnknownReferenceFrequency,
+    /// The PLL N/R/Q/P settings don't describe a valid divider
+    InvalidPllSettings,
+    /// Only one of Y2 and Y3 was given, but they share their state
+    /// definitions and can only be enabled or disabled together
+    SharedOutputState,
+    /// The [`crate::output::OutputSource`] variant doesn't apply to output Yn
+    WrongOutputSource(u8),
+    /// The device no longer matches the configuration it was expected to
+    /// hold, e.g. because another bus master changed it, see
+    /// [`crate::CDCx913::compare_and_apply`]. Gives the first register that
+    /// differs.
+    Conflict(RegisterMismatch),
+    /// The bus failed while [`crate::CDCx913::apply_config`] was writing. The
+    /// previous configuration was written back if `rolled_back` is set,
+    /// otherwise the device holds a mix of the old and the new one.
+    ApplyFailed { error: E, rolled_back: bool },
+    /// No PLL setting and output divider produce the given frequency in Hz
+    /// exactly, or it's above what the device can output
+    FrequencyUnachievable(u32),
+    /// Spread spectrum was to be re-enabled for a control input state the
+    /// driver never saw a non-zero modulation amount for, see
+    /// [`crate::CDCx913::set_spread_spectrum_enabled`]
+    UnknownSscAmount,
+    /// No multiplexer setting gives every output the path asked for, see
+    /// [`crate::CDCx913::set_routing`]
+    InvalidRouting(RoutingError),
+    /// A value given to a setter is out of range for its field and the
+    /// driver's [`crate::ValidationPolicy`] is strict
+    ValueOutOfRange,
+    /// A register didn't reach the state waited for in time, see
+    /// [`crate::CDCx913::poll_register_until`]
+    PollTimeout,
+    /// The reference frequency is outside of the [`crate::planner::InputRange`]
+    /// of the input mode, so the device can't run from it
+    ReferenceOutOfRange {
+        input: InputClockSelection,
+        reference_hz: u32,
+    },
+    /// Output Y`n` isn't fed by its PLL through its own divider, because the
+    /// PLL is bypassed or M1, M2 or M3 (M4, M5, ... for the later PLLs)
+    /// selects another path. Retuning that PLL and divider wouldn't reach the
+    /// output, see [`crate::CDCx913::set_routing`].
+    OutputNotOnPll(u8),
+    /// Output Y`n` has its divider held in reset, so it has no frequency to
+    /// start from, see [`crate::CDCx913::ramp_output_frequency`]
+    OutputStopped(u8),
+}
+
+impl<E: core::fmt::Debug> core::fmt::Display for Error<E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::I2c(e) => write!(f, "I2C error: {e:?}"),
+            Self::AddressNack(e) => write!(f, "no device acknowledged the address: {e:?}"),
+            Self::DataNack(e) => write!(f, "device did not acknowledge data: {e:?}"),
+            Self::ArbitrationLoss(e) => write!(f, "lost arbitration to another master: {e:?}"),
+            Self::EepromTimeout => f.write_str("EEPROM write did not complete in time"),
+            Self::EepromLocked => f.write_str("EEPROM is permanently locked"),
+            Self::SerialInterfaceDisabled => {
+                f.write_str("serial interface pins were switched to control inputs")
+            }
+            Self::OutputsEnabled => f.write_str("refused to retune PLL1 with its outputs enabled"),
+            Self::NoSuchPll(n) => write!(f, "device has no PLL{n}"),
+            Self::NoSuchOutput(n) => write!(f, "device has no output Y{n}"),
+            Self::DividerOutOfRange => f.write_str("divider does not fit the output"),
+            Self::NoDeviceFound => f.write_str("no device answered on any default address"),
+            Self::ReservedEncoding(raw) => {
+                write!(f, "register field holds reserved value {raw:#x}")
+            }
+            Self::UnknownReferenceFrequency => f.write_str("reference frequency is not known"),
+            Self::InvalidPllSettings => f.write_str("PLL settings don't describe a valid divider"),
+            Self::SharedOutputState => f.write_str("Y2 and Y3 can only be gated together"),
+            Self::WrongOutputSource(n) => write!(f, "source does not apply to output Y{n}"),
+            Self::Conflict(mismatch) => write!(
+                f,
+                "register {:#04x} holds {:#04x} instead of the expected {:#04x}",
+                mismatch.offset, mismatch.actual, mismatch.expected
+            ),
+            Self::ApplyFailed {
+                error,
+                rolled_back: true,
+            } => write!(f, "I2C error while applying, rolled back: {error:?}"),
+            Self::ApplyFailed {
+                error,
+                rolled_back: false,
+            } => write!(f, "I2C error while applying, rollback failed: {error:?}"),
+            Self::FrequencyUnachievable(hz) => write!(f, "no setting produces exactly {hz} Hz"),
+            Self::UnknownSscAmount => f.write_str("no spread spectrum amount to restore"),
+            Self::InvalidRouting(error) => write!(f, "impossible routing: {error}"),
+            Self::ValueOutOfRange => f.write_str("value out of range for the field"),
+            Self::PollTimeout => f.write_str("register did not reach the expected state in time"),
+            Self::ReferenceOutOfRange {
+                input,
+                reference_hz,
+            } => write!(f, "{reference_hz} Hz is out of range for {input:?} input"),
+            Self::OutputNotOnPll(n) => {
+                write!(
+                    f,
+                    "output Y{n} is not fed by its PLL through its own divider"
+                )
+            }
+            Self::OutputStopped(n) => write!(f, "output Y{n} has its divider held in reset"),
+        }
+    }
+}
+
+/// Chains to the bus error through [`core::error::Error::source`], so it's
+/// available whenever the bus error type implements the trait itself. The same
+/// trait is `std::error::Error` on hosted targets.
+impl<E: core::error::Error + 'static> core::error::Error for Error<E> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        self.bus_error()
+            .map(|e| e as &(dyn core::error::Error + 'static))
+    }
+}
+
+impl<E> Error<E> {
+    /// The error the bus reported, for the variants that carry one
+    pub fn bus_error(&self) -> Option<&E> {
+        match self {
+            Self::I2c(e)
+            | Self::AddressNack(e)
+            | Self::DataNack(e)
+            | Self::ArbitrationLoss(e)
+            | Self::ApplyFailed { error: e, .. } => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Sorts the bus error by its [`ErrorKind`] into the variants telling an
+/// absent device from a busy one or a contended bus
+#[cfg(feature = "driver")]
+impl<E: embedded_hal::i2c::Error> From<E> for Error<E> {
+    fn from(value: E) -> Self {
+        match value.kind() {
+            ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address) => Self::AddressNack(value),
+            ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data) => Self::DataNack(value),
+            ErrorKind::ArbitrationLoss => Self::ArbitrationLoss(value),
+            _ => Self::I2c(value),
+        }
+    }
+}
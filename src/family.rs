@@ -0,0 +1,239 @@
+//! The CDCx913 shares its register architecture with the rest of the
+//! CDCE(L)9xx family. Each family member gets a marker type implementing
+//! [`Device`], which the driver is parameterized over.
+
+use arbitrary_int::{u3, u4};
+#[cfg(feature = "driver")]
+use embedded_hal_async::i2c::I2c;
+
+#[cfg(feature = "driver")]
+use crate::{
+    Error,
+    i2c::{CommandCode, OpCode},
+    registers::generic_configuration::GenericConfigurationRegister0,
+};
+use crate::{planner::Limits, registers::generic_configuration::DeviceIdentification};
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+pub trait Device: sealed::Sealed {
+    const NAME: &'static str;
+    /// Number of PLLs, each with its own configuration block
+    const PLLS: u8;
+    /// Number of clock outputs (Y1..Yn)
+    const OUTPUTS: u8;
+    /// I2C address of the device with TARGET_ADR at its default
+    const DEFAULT_ADDRESS: u8;
+    /// Size of the register map: the generic block plus one block per PLL.
+    /// [`crate::config::DeviceConfig`] only spans the first 0x20 bytes of it.
+    const REGISTER_MAP_LEN: usize = 0x10 * (Self::PLLS as usize + 1);
+}
+
+/// CDCE913 / CDCEL913: 1 PLL, 3 outputs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Cdce913;
+
+/// CDCE925 / CDCEL925: 2 PLLs, 5 outputs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Cdce925;
+
+/// CDCE937 / CDCEL937: 3 PLLs, 7 outputs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Cdce937;
+
+/// CDCE949 / CDCEL949: 4 PLLs, 9 outputs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Cdce949;
+
+impl sealed::Sealed for Cdce913 {}
+impl sealed::Sealed for Cdce925 {}
+impl sealed::Sealed for Cdce937 {}
+impl sealed::Sealed for Cdce949 {}
+
+impl Device for Cdce913 {
+    const NAME: &'static str = "CDCE(L)913";
+    const PLLS: u8 = 1;
+    const OUTPUTS: u8 = 3;
+    const DEFAULT_ADDRESS: u8 = 0b110_0101;
+}
+
+impl Device for Cdce925 {
+    const NAME: &'static str = "CDCE(L)925";
+    const PLLS: u8 = 2;
+    const OUTPUTS: u8 = 5;
+    const DEFAULT_ADDRESS: u8 = 0b110_0100;
+}
+
+impl Device for Cdce937 {
+    const NAME: &'static str = "CDCE(L)937";
+    const PLLS: u8 = 3;
+    const OUTPUTS: u8 = 7;
+    const DEFAULT_ADDRESS: u8 = 0b110_1101;
+}
+
+impl Device for Cdce949 {
+    const NAME: &'static str = "CDCE(L)949";
+    const PLLS: u8 = 4;
+    const OUTPUTS: u8 = 9;
+    const DEFAULT_ADDRESS: u8 = 0b110_1100;
+}
+
+/// Family member and output voltage variant of a detected device
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum DeviceKind {
+    CDCE913,
+    CDCEL913,
+    CDCE925,
+    CDCEL925,
+    CDCE937,
+    CDCEL937,
+    CDCE949,
+    CDCEL949,
+}
+
+impl DeviceKind {
+    /// Whether this is one of the 1.8 V CDCEL variants
+    pub fn is_low_voltage(&self) -> bool {
+        matches!(
+            self,
+            Self::CDCEL913 | Self::CDCEL925 | Self::CDCEL937 | Self::CDCEL949
+        )
+    }
+
+    /// Output voltage variant, which decides the frequency limits
+    pub fn variant(&self) -> DeviceIdentification {
+        if self.is_low_voltage() {
+            DeviceIdentification::CDCEL913
+        } else {
+            DeviceIdentification::CDCE913
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::CDCE913 => "CDCE913",
+            Self::CDCEL913 => "CDCEL913",
+            Self::CDCE925 => "CDCE925",
+            Self::CDCEL925 => "CDCEL925",
+            Self::CDCE937 => "CDCE937",
+            Self::CDCEL937 => "CDCEL937",
+            Self::CDCE949 => "CDCE949",
+            Self::CDCEL949 => "CDCEL949",
+        }
+    }
+
+    pub fn capabilities(&self) -> Capabilities {
+        let (plls, outputs) = match self {
+            Self::CDCE913 | Self::CDCEL913 => (Cdce913::PLLS, Cdce913::OUTPUTS),
+            Self::CDCE925 | Self::CDCEL925 => (Cdce925::PLLS, Cdce925::OUTPUTS),
+            Self::CDCE937 | Self::CDCEL937 => (Cdce937::PLLS, Cdce937::OUTPUTS),
+            Self::CDCE949 | Self::CDCEL949 => (Cdce949::PLLS, Cdce949::OUTPUTS),
+        };
+
+        Capabilities {
+            plls,
+            outputs,
+            max_output_hz: Limits::for_variant(self.variant()).max_output_hz,
+        }
+    }
+
+    #[cfg(feature = "driver")]
+    fn new(address: u8, identification: DeviceIdentification) -> Option<Self> {
+        let low_voltage = identification == DeviceIdentification::CDCEL913;
+
+        Some(match (address, low_voltage) {
+            (Cdce913::DEFAULT_ADDRESS, false) => Self::CDCE913,
+            (Cdce913::DEFAULT_ADDRESS, true) => Self::CDCEL913,
+            (Cdce925::DEFAULT_ADDRESS, false) => Self::CDCE925,
+            (Cdce925::DEFAULT_ADDRESS, true) => Self::CDCEL925,
+            (Cdce937::DEFAULT_ADDRESS, false) => Self::CDCE937,
+            (Cdce937::DEFAULT_ADDRESS, true) => Self::CDCEL937,
+            (Cdce949::DEFAULT_ADDRESS, false) => Self::CDCE949,
+            (Cdce949::DEFAULT_ADDRESS, true) => Self::CDCEL949,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Capabilities {
+    pub plls: u8,
+    pub outputs: u8,
+    /// Highest output frequency the datasheet specifies for the device
+    pub max_output_hz: u32,
+}
+
+/// Result of [`detect`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Detected {
+    pub kind: DeviceKind,
+    /// Address the device answered on
+    pub address: u8,
+    pub revision: u3,
+    pub vendor: u4,
+}
+
+/// Vendor ID of Texas Instruments in VID
+pub const TI_VENDOR_ID: u4 = u4::new(0b0001);
+
+/// Probes the default address of every family member and identifies the
+/// first TI device that answers. An address NACK means nothing is there and
+/// moves on to the next address, any other bus error is returned.
+///
+/// The family member is inferred from the address (register 0 only tells the
+/// output voltage variant apart), so this only works for devices whose
+/// TARGET_ADR is still at its default. Family members differing only in
+/// TARGET_ADR share addresses: a CDCE925 strapped to TARGET_ADR = 01 answers
+/// on 0x65 and is reported as a CDCE913, and a CDCE949 strapped the same way
+/// as a CDCE937.
+#[cfg(feature = "driver")]
+pub async fn detect<I2C: I2c>(i2c: &mut I2C) -> Result<Detected, Error<I2C::Error>> {
+    for address in [
+        Cdce913::DEFAULT_ADDRESS,
+        Cdce925::DEFAULT_ADDRESS,
+        Cdce937::DEFAULT_ADDRESS,
+        Cdce949::DEFAULT_ADDRESS,
+    ] {
+        let mut buf = [0u8; 1];
+
+        let result = i2c
+            .write_read(
+                address,
+                &[CommandCode::new(OpCode::Byte, 0).into()],
+                &mut buf,
+            )
+            .await;
+
+        // A NACK on the address just means nothing is there
+        match result.map_err(Error::from) {
+            Err(Error::AddressNack(_)) => continue,
+            result => result?,
+        }
+
+        let reg = GenericConfigurationRegister0::from(buf[0]);
+
+        if u4::new(reg.vid()) != TI_VENDOR_ID {
+            continue;
+        }
+
+        if let Some(kind) = DeviceKind::new(address, reg.device_identification()) {
+            return Ok(Detected {
+                kind,
+                address,
+                revision: u3::new(reg.rid()),
+                vendor: u4::new(reg.vid()),
+            });
+        }
+    }
+
+    Err(Error::NoDeviceFound)
+}
@@ -0,0 +1,152 @@
+//! Self-checking routines for hardware-in-the-loop bring-up. Each routine
+//! talks to a real device and returns a [`HilFailure`] describing the first
+//! thing that didn't behave as expected, so they can be called straight from
+//! `defmt-test` or `embedded-test` cases:
+//!
+//! ```ignore
+//! #[test]
+//! async fn cdcx913_smoke(state: &mut State) {
+//!     hiltest::run_all(&mut state.cdcx913).await.unwrap();
+//! }
+//! ```
+//!
+//! The routines restore every register they change, but the outputs will
+//! glitch while they run.
+
+use arbitrary_int::{u3, u4};
+use embedded_hal_async::i2c::I2c;
+
+use crate::{
+    CDCx913, Error,
+    config::WRITABLE_OFFSETS,
+    family::Device,
+    registers::generic_configuration::{
+        DeviceIdentification, EepromProgrammingStatus, GenericConfigurationRegister6,
+    },
+};
+
+pub use crate::family::TI_VENDOR_ID;
+
+/// Bit patterns written by [`readback_rw_fields`]
+const PATTERNS: [u8; 4] = [0x55, 0xAA, 0xFF, 0x00];
+
+/// Bits the readback test may toggle. TARGET_ADR, PWDN, EELOCK, SPICON and
+/// EEWRITE are left alone since changing them would lose the device, power it
+/// down, lock or write the EEPROM.
+const fn readback_mask(offset: u8) -> u8 {
+    match offset {
+        0x01 => 0b0000_1100,
+        0x02 => 0b1011_1111,
+        0x06 => 0b1111_1110,
+        _ => 0xFF,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum HilFailure<E> {
+    /// A driver operation failed
+    Driver(Error<E>),
+    /// Register 0x00 reports a vendor other than TI
+    UnexpectedVendor(u4),
+    /// A register didn't read back what was written to it
+    ReadbackMismatch { offset: u8, written: u8, read: u8 },
+    /// EEPIP reports an EEPROM write although none was started
+    EepromBusy,
+    /// EEWRITE is set although no EEPROM write is in progress
+    EepromWriteStuck,
+}
+
+impl<E> From<Error<E>> for HilFailure<E> {
+    fn from(value: Error<E>) -> Self {
+        Self::Driver(value)
+    }
+}
+
+/// What [`probe`] found in register 0x00
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ProbeReport {
+    pub identification: DeviceIdentification,
+    pub revision: u3,
+    pub vendor: u4,
+}
+
+/// Checks that the device answers and identifies as a TI part
+pub async fn probe<I2C: I2c, D: Device, DELAY>(
+    driver: &mut CDCx913<I2C, D, DELAY>,
+) -> Result<ProbeReport, HilFailure<I2C::Error>> {
+    let report = ProbeReport {
+        identification: driver.device_identification().await?,
+        revision: driver.revision_number().await?,
+        vendor: driver.vendor_identification().await?,
+    };
+
+    if report.vendor != TI_VENDOR_ID {
+        return Err(HilFailure::UnexpectedVendor(report.vendor));
+    }
+
+    Ok(report)
+}
+
+/// Writes a set of bit patterns to every read/write field and checks they read
+/// back unchanged, restoring each register afterwards
+pub async fn readback_rw_fields<I2C: I2c, D: Device, DELAY>(
+    driver: &mut CDCx913<I2C, D, DELAY>,
+) -> Result<(), HilFailure<I2C::Error>> {
+    for offset in WRITABLE_OFFSETS {
+        let mask = readback_mask(offset);
+        let original = driver.read_byte_unchecked(offset).await?;
+
+        for pattern in PATTERNS {
+            let written = (original & !mask) | (pattern & mask);
+
+            driver.write_byte_unchecked(offset, written).await?;
+            let read = driver.read_byte_unchecked(offset).await?;
+
+            if read & mask != written & mask {
+                // Put the register back before reporting, best effort
+                let _ = driver.write_byte_unchecked(offset, original).await;
+
+                return Err(HilFailure::ReadbackMismatch {
+                    offset,
+                    written,
+                    read,
+                });
+            }
+        }
+
+        driver.write_byte_unchecked(offset, original).await?;
+    }
+
+    Ok(())
+}
+
+/// Checks that the EEPROM is idle: EEPIP and EEWRITE both clear
+pub async fn eeprom_status_sanity<I2C: I2c, D: Device, DELAY>(
+    driver: &mut CDCx913<I2C, D, DELAY>,
+) -> Result<(), HilFailure<I2C::Error>> {
+    if driver.eeprom_programming_status().await? == EepromProgrammingStatus::InProgress {
+        return Err(HilFailure::EepromBusy);
+    }
+
+    let reg = GenericConfigurationRegister6::from(driver.read_byte_unchecked(0x06).await?);
+    if reg.eewrite() {
+        return Err(HilFailure::EepromWriteStuck);
+    }
+
+    Ok(())
+}
+
+/// Runs [`probe`], [`eeprom_status_sanity`] and [`readback_rw_fields`] in that
+/// order
+pub async fn run_all<I2C: I2c, D: Device, DELAY>(
+    driver: &mut CDCx913<I2C, D, DELAY>,
+) -> Result<ProbeReport, HilFailure<I2C::Error>> {
+    let report = probe(driver).await?;
+
+    eeprom_status_sanity(driver).await?;
+    readback_rw_fields(driver).await?;
+
+    Ok(report)
+}
@@ -1,10 +1,19 @@
 use derive_more::{From, Into};
 
-pub const ADDRESS: u8 = 0b110_0101;
+use crate::family::{Cdce913, Device};
+
+/// Default address of the CDCx913, see [`Device::DEFAULT_ADDRESS`] for the
+/// other family members
+pub const ADDRESS: u8 = Cdce913::DEFAULT_ADDRESS;
+
+/// Most registers `CDCx913::read_bytes_unchecked` combines into one
+/// transaction, which keeps the operations it hands the bus on the stack
+pub const MAX_BATCHED_READS: usize = 8;
 
 bitfield::bitfield! {
     /// Defined in Table 7-8 (Command Code Definition)
-    #[derive(Clone, Copy, PartialEq, Eq, From, Into, defmt::Format)]
+    #[derive(Clone, Copy, PartialEq, Eq, From, Into)]
+    #[cfg_attr(feature = "defmt", derive(defmt::Format))]
     pub struct CommandCode(u8);
     impl Debug;
     u8;
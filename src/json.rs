@@ -0,0 +1,137 @@
+//! JSON form of a [`DeviceConfig`], for reviewing configurations in pull
+//! requests and exchanging them with tools not written in Rust. There is one
+//! key per field, named after the datasheet mnemonic as in
+//! [`crate::report`], in register order:
+//!
+//! ```json
+//! {
+//!   "E_EL": 1,
+//!   "RID": 0,
+//!   "VID": 1,
+//!   "RESERVED_01": 0,
+//!   "EEPIP": 0,
+//!   ...
+//!   "PDIV1": 1,
+//!   ...
+//! }
+//! ```
+//!
+//! Values are the raw field contents. Fields split across registers, such as
+//! PDIV1 or PLL1_0N, appear once with their whole value, and the reserved bits
+//! of registers 0x01, 0x05 and 0x17 are keyed by their offset. Registers
+//! 0x07-0x0F hold no fields, so they aren't exported and are 0 after an
+//! import. Imports reject unknown and missing keys as well as values that
+//! don't fit their field.
+
+use alloc::{
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de, ser::SerializeMap};
+
+use crate::{
+    config::{DeviceConfig, REGISTER_WINDOW_LEN},
+    report::{Field, register_fields},
+};
+
+impl DeviceConfig {
+    /// Renders the configuration as pretty-printed JSON, see the module
+    /// documentation
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("a map of numbers always serializes")
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+impl Serialize for DeviceConfig {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // Keeps the order of the register map, unlike a BTreeMap
+        let mut values: Vec<(String, u16)> = Vec::new();
+
+        for (offset, field) in fields() {
+            let (key, lsb) = key(offset, field);
+            let part = (field.value(self.register(offset)) as u16) << lsb;
+
+            match values.iter_mut().find(|(other, _)| *other == key) {
+                Some((_, value)) => *value |= part,
+                None => values.push((key, part)),
+            }
+        }
+
+        let mut map = serializer.serialize_map(Some(values.len()))?;
+        for (key, value) in &values {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for DeviceConfig {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let values = BTreeMap::<String, u16>::deserialize(deserializer)?;
+        let mut registers = [0; REGISTER_WINDOW_LEN];
+        // Bits of each value the fields hold
+        let mut masks = BTreeMap::<String, u16>::new();
+
+        for (offset, field) in fields() {
+            let (key, lsb) = key(offset, field);
+            let value = *values
+                .get(&key)
+                .ok_or_else(|| de::Error::custom(format!("missing field `{key}`")))?;
+
+            registers[offset as usize] |= (((value >> lsb) << field.lsb) as u8) & field.mask();
+            *masks.entry(key).or_default() |= ((1 << field.width()) - 1) << lsb;
+        }
+
+        for (key, value) in &values {
+            let mask = masks
+                .get(key)
+                .ok_or_else(|| de::Error::custom(format!("unknown field `{key}`")))?;
+
+            if value & !mask != 0 {
+                return Err(de::Error::custom(format!(
+                    "{key} = {value} doesn't fit its {} bits",
+                    mask.count_ones()
+                )));
+            }
+        }
+
+        Ok(Self::from_bytes(registers))
+    }
+}
+
+// The fields of every register in the window, in register order
+fn fields() -> impl Iterator<Item = (u8, &'static Field)> {
+    (0..REGISTER_WINDOW_LEN as u8).flat_map(|offset| {
+        register_fields(offset)
+            .iter()
+            .map(move |field| (offset, field))
+    })
+}
+
+// The JSON key of `field` and the lowest bit of the whole value it holds,
+// e.g. `PDIV1` and 8 for `PDIV1[9:8]`
+fn key(offset: u8, field: &Field) -> (String, u8) {
+    if field.name == "RESERVED" {
+        return (format!("RESERVED_{offset:02X}"), 0);
+    }
+
+    match field.name.split_once('[') {
+        Some((name, bits)) => {
+            let lsb = bits
+                .trim_end_matches(']')
+                .rsplit(':')
+                .next()
+                .and_then(|lsb| lsb.parse().ok())
+                .unwrap_or(0);
+
+            (name.to_string(), lsb)
+        }
+        None => (field.name.to_string(), 0),
+    }
+}
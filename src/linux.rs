@@ -0,0 +1,138 @@
+//! Bus and delay for Linux hosts, so gateways can manage the PLL with the same
+//! driver code as firmware. [`LinuxI2c`] talks to `/dev/i2c-N` through the
+//! kernel's i2c-dev interface and implements both the blocking and the async
+//! `embedded-hal` traits; [`StdDelay`] sleeps the calling thread. Futures can
+//! be awaited in any executor or run with [`crate::blocking::block_on`]:
+//!
+//! ```ignore
+//! let mut pll = CDCE913::new_with_delay(LinuxI2c::open("/dev/i2c-1")?, StdDelay);
+//! pll.set_reference_frequency_hz(Some(27_000_000));
+//! block_on(pll.set_output_frequency(2, 48_000_000))?;
+//! ```
+//!
+//! Buses from `linux-embedded-hal` 0.4 and later implement the blocking
+//! traits, so they work through [`crate::blocking::Blocking`] instead.
+
+use std::{fmt, path::Path, thread, time::Duration};
+
+use embedded_hal::{delay::DelayNs as BlockingDelayNs, i2c::I2c as BlockingI2c};
+use embedded_hal_async::{
+    delay::DelayNs,
+    i2c::{self, ErrorKind, ErrorType, I2c, NoAcknowledgeSource, Operation},
+};
+use i2cdev::{
+    core::{I2CMessage, I2CTransfer},
+    linux::{LinuxI2CBus, LinuxI2CError, LinuxI2CMessage},
+};
+
+// errno values the i2c-dev adapters report for a missing acknowledge and lost
+// arbitration
+const ENXIO: i32 = 6;
+const EAGAIN: i32 = 11;
+const EREMOTEIO: i32 = 121;
+
+/// An I2C bus device such as `/dev/i2c-1`. Each transaction is one
+/// `I2C_RDWR` transfer, so reads keep their repeated start.
+pub struct LinuxI2c {
+    bus: LinuxI2CBus,
+}
+
+impl LinuxI2c {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, LinuxI2cError> {
+        Ok(Self {
+            bus: LinuxI2CBus::new(path).map_err(LinuxI2cError)?,
+        })
+    }
+}
+
+impl fmt::Debug for LinuxI2c {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LinuxI2c").finish_non_exhaustive()
+    }
+}
+
+/// Error of the i2c-dev interface. Its `Debug` form is the OS message, as
+/// that's what [`crate::Error`] shows.
+pub struct LinuxI2cError(pub LinuxI2CError);
+
+impl fmt::Debug for LinuxI2cError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl fmt::Display for LinuxI2cError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for LinuxI2cError {}
+
+impl i2c::Error for LinuxI2cError {
+    fn kind(&self) -> ErrorKind {
+        let errno = match &self.0 {
+            LinuxI2CError::Nix(errno) => Some(*errno as i32),
+            LinuxI2CError::Io(error) => error.raw_os_error(),
+        };
+
+        match errno {
+            Some(ENXIO | EREMOTEIO) => ErrorKind::NoAcknowledge(NoAcknowledgeSource::Unknown),
+            Some(EAGAIN) => ErrorKind::ArbitrationLoss,
+            _ => ErrorKind::Other,
+        }
+    }
+}
+
+impl ErrorType for LinuxI2c {
+    type Error = LinuxI2cError;
+}
+
+impl BlockingI2c for LinuxI2c {
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        let mut messages: Vec<_> = operations
+            .iter_mut()
+            .map(|operation| match operation {
+                Operation::Read(buf) => LinuxI2CMessage::read(buf),
+                Operation::Write(bytes) => LinuxI2CMessage::write(bytes),
+            })
+            .map(|message| message.with_address(address.into()))
+            .collect();
+
+        self.bus
+            .transfer(&mut messages)
+            .map(|_| ())
+            .map_err(LinuxI2cError)
+    }
+}
+
+impl I2c for LinuxI2c {
+    async fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        BlockingI2c::transaction(self, address, operations)
+    }
+}
+
+/// Delay that sleeps the calling thread, which is fine for the driver's waits
+/// of a few milliseconds at most
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StdDelay;
+
+impl BlockingDelayNs for StdDelay {
+    fn delay_ns(&mut self, ns: u32) {
+        thread::sleep(Duration::from_nanos(ns.into()));
+    }
+}
+
+impl DelayNs for StdDelay {
+    async fn delay_ns(&mut self, ns: u32) {
+        thread::sleep(Duration::from_nanos(ns.into()));
+    }
+}
@@ -0,0 +1,471 @@
+use core::ops::{BitOr, BitOrAssign};
+
+use arbitrary_int::u10;
+#[cfg(feature = "driver")]
+use arbitrary_int::{u3, u7};
+#[cfg(feature = "driver")]
+use embedded_hal_async::i2c::I2c;
+
+use crate::registers::{
+    OutputStateDefinition, OutputStateSelection,
+    generic_configuration::Y1ClockSource,
+    pll1_configuration::{OutputY2Multiplexer, OutputY3Source},
+};
+#[cfg(feature = "driver")]
+use crate::{
+    CDCx913, Error, Register,
+    control::ControlInputState,
+    family::Device,
+    planner::PDIV_MAX,
+    registers::{
+        ReservedEncoding,
+        generic_configuration::{
+            GenericConfigurationRegister2, GenericConfigurationRegister3,
+            GenericConfigurationRegister4,
+        },
+        pll1_configuration::{
+            Pll1ConfigurationRegister4, Pll1ConfigurationRegister5, Pll1ConfigurationRegister6,
+            Pll1ConfigurationRegister7,
+        },
+    },
+    timing::NoDelay,
+};
+
+/// Set of outputs, for [`crate::CDCx913::disable_outputs`] and
+/// [`crate::CDCx913::enable_outputs`]. Combine with `|`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct OutputMask(u8);
+
+impl OutputMask {
+    pub const Y1: Self = Self(1 << 0);
+    pub const Y2: Self = Self(1 << 1);
+    pub const Y3: Self = Self(1 << 2);
+    /// Y2 and Y3 share their state definitions, so they can only be gated
+    /// together
+    pub const Y2Y3: Self = Self(Self::Y2.0 | Self::Y3.0);
+    pub const ALL: Self = Self(Self::Y1.0 | Self::Y2Y3.0);
+
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
+
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub const fn intersects(self, other: Self) -> bool {
+        self.0 & other.0 != 0
+    }
+
+    pub const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+impl BitOr for OutputMask {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        self.union(rhs)
+    }
+}
+
+impl BitOrAssign for OutputMask {
+    fn bitor_assign(&mut self, rhs: Self) {
+        *self = self.union(rhs);
+    }
+}
+
+/// How [`crate::CDCx913::disable_outputs`] turns the outputs off, i.e. the
+/// disabled variants of [`OutputStateDefinition`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum DisableMode {
+    PowerDown,
+    #[default]
+    HighImpedance,
+    Low,
+}
+
+impl From<DisableMode> for OutputStateDefinition {
+    fn from(mode: DisableMode) -> Self {
+        match mode {
+            DisableMode::PowerDown => Self::DevicePowerDown,
+            DisableMode::HighImpedance => Self::Disabled3State,
+            DisableMode::Low => Self::DisabledLow,
+        }
+    }
+}
+
+/// Options for [`crate::CDCx913::shutdown`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ShutdownOptions {
+    /// How the outputs are turned off
+    pub mode: DisableMode,
+    /// Put the whole device into power-down once the outputs are off
+    pub power_down: bool,
+}
+
+/// What feeds an output. Which variant applies depends on the output: Y1 has
+/// its own source selection in front of PDIV1, the outputs of each PLL block
+/// pick one of the dividers. Outside the PLL1 block, `Pdiv1`, `Pdiv2` and
+/// `Pdiv3` select PDIV(2n-2), PDIV(2n) and PDIV(2n+1) for PLLn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum OutputSource {
+    /// Y1, M1
+    Y1(Y1ClockSource),
+    /// The first output of a PLL block (Y2, Y4, ...), M2
+    First(OutputY2Multiplexer),
+    /// The second output of a PLL block (Y3, Y5, ...), M3
+    Second(OutputY3Source),
+}
+
+/// Estimated duty cycle of a divider output in tenths of a percent, see
+/// [`Output::estimated_duty_cycle`]. Dividing by an even number, or not at
+/// all, keeps the 50% of the clock going in. An odd divider N can only hold
+/// its output high for (N - 1) / 2 of its N input cycles, e.g. 33.3% for 3.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DutyCycle(u16);
+
+impl DutyCycle {
+    /// `None` for 0, which holds the divider in reset
+    pub const fn for_divider(divider: u16) -> Option<Self> {
+        match divider {
+            0 => None,
+            1 => Some(Self(500)),
+            n if n.is_multiple_of(2) => Some(Self(500)),
+            n => Some(Self(((n - 1) as u32 * 500 / n as u32) as u16)),
+        }
+    }
+
+    pub const fn permille(self) -> u16 {
+        self.0
+    }
+
+    /// How far the duty cycle is off 50%, in tenths of a percent
+    pub const fn deviation_permille(self) -> u16 {
+        500 - self.0
+    }
+
+    pub const fn is_balanced(self) -> bool {
+        self.0 == 500
+    }
+}
+
+/// Everything that configures one output, see
+/// [`crate::CDCx913::output_config`]. Except for Y1, both outputs of a PLL
+/// block share their state definitions and selections, so setting them for
+/// one output sets them for the other as well.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct OutputConfig {
+    pub source: OutputSource,
+    /// Output divider, 0 holds it in reset. Only Y1 takes more than 7 bits.
+    pub divider: u10,
+    pub state0: OutputStateDefinition,
+    pub state1: OutputStateDefinition,
+    /// Which of the two state definitions applies, indexed by control input
+    /// combination
+    pub selections: [OutputStateSelection; 8],
+}
+
+/// Handle to a single output. Y1 has its own 10-bit divider PDIV1, every other
+/// output Yn has a 7-bit divider PDIVn in the block of PLL(n / 2).
+#[cfg(feature = "driver")]
+pub struct Output<'a, I2C, D, DELAY = NoDelay> {
+    driver: &'a mut CDCx913<I2C, D, DELAY>,
+    n: u8,
+}
+
+#[cfg(feature = "driver")]
+impl<'a, I2C: I2c, D: Device, DELAY> Output<'a, I2C, D, DELAY> {
+    pub(crate) fn new(driver: &'a mut CDCx913<I2C, D, DELAY>, n: u8) -> Self {
+        Self { driver, n }
+    }
+
+    /// Number of the output, i.e. `n` for Yn
+    pub fn number(&self) -> u8 {
+        self.n
+    }
+
+    // Base offset of the PLL block the output belongs to, for any output but Y1
+    fn block_offset(&self) -> u8 {
+        Register::Pll1Configuration as u8 + 0x10 * (self.n / 2 - 1)
+    }
+
+    // Offset of the register holding PDIVn, for any output but Y1
+    fn divider_offset(&self) -> u8 {
+        self.block_offset() + 6 + self.n % 2
+    }
+
+    // The 7-bit divider of any output but Y1, going by the validation policy
+    fn check_divider(&mut self, value: u10) -> Result<u7, Error<I2C::Error>> {
+        let value = self.driver.check_value(
+            value.value() <= PDIV_MAX as u16,
+            value.value().min(PDIV_MAX as u16) as u8,
+            Error::DividerOutOfRange,
+        )?;
+
+        Ok(u7::new(value))
+    }
+
+    pub async fn divider(&mut self) -> Result<u10, Error<I2C::Error>> {
+        if self.n == 1 {
+            return self.driver.y1_output_divider().await;
+        }
+
+        let offset = self.divider_offset();
+        let value = if self.n.is_multiple_of(2) {
+            self.driver
+                .with(offset, |reg: &Pll1ConfigurationRegister6| reg.pdiv2())
+                .await?
+        } else {
+            self.driver
+                .with(offset, |reg: &Pll1ConfigurationRegister7| reg.pdiv3())
+                .await?
+        };
+
+        Ok(u10::new(value.into()))
+    }
+
+    /// Duty cycle of the output, going by the divider its multiplexer selects
+    /// and assuming the clock going into that divider is 50%. `None` while the
+    /// divider is held in reset. Fails with [`Error::ReservedEncoding`] like
+    /// [`Self::config`].
+    pub async fn estimated_duty_cycle(&mut self) -> Result<Option<DutyCycle>, Error<I2C::Error>> {
+        // Output number whose own divider drives this output. Pdiv1 is PDIV1
+        // in the PLL1 block and PDIV(2n-2) of the block before for PLLn.
+        let pdiv1 = if self.n < 4 { 1 } else { 2 * (self.n / 2) - 2 };
+        let n = if self.n == 1 {
+            1
+        } else {
+            let reg4 = self
+                .driver
+                .with(
+                    self.block_offset() + 4,
+                    |reg: &Pll1ConfigurationRegister4| *reg,
+                )
+                .await?;
+
+            if self.n.is_multiple_of(2) {
+                match reg4.output_y2_multiplexer() {
+                    OutputY2Multiplexer::Pdiv1 => pdiv1,
+                    OutputY2Multiplexer::Pdiv2 => self.n,
+                }
+            } else {
+                match reg4
+                    .output_y3_source()
+                    .map_err(|ReservedEncoding(raw)| Error::ReservedEncoding(raw))?
+                {
+                    OutputY3Source::Pdiv1 => pdiv1,
+                    OutputY3Source::Pdiv2 => self.n - 1,
+                    OutputY3Source::Pdiv3 => self.n,
+                }
+            }
+        };
+
+        let divider = Output::new(&mut *self.driver, n).divider().await?;
+
+        Ok(DutyCycle::for_divider(divider.value()))
+    }
+
+    /// Sets the output divider, where 0 holds the divider in reset. Values
+    /// above 127 on anything but Y1 are handled according to the
+    /// [`crate::ValidationPolicy`].
+    pub async fn set_divider(&mut self, value: u10) -> Result<(), Error<I2C::Error>> {
+        if self.n == 1 {
+            return self.driver.set_y1_output_divider(value).await;
+        }
+
+        let value = self.check_divider(value)?;
+
+        let offset = self.divider_offset();
+        if self.n.is_multiple_of(2) {
+            self.driver
+                .modify_byte_unchecked(offset, |reg: &mut Pll1ConfigurationRegister6| {
+                    reg.set_pdiv2(value.value())
+                })
+                .await
+        } else {
+            self.driver
+                .modify_byte_unchecked(offset, |reg: &mut Pll1ConfigurationRegister7| {
+                    reg.set_pdiv3(value.value())
+                })
+                .await
+        }
+    }
+
+    /// The state definition that applies while the control inputs are in
+    /// `state`, resolved through the state selection bit. Y1 has its own
+    /// definitions, every other output shares them with the other output of
+    /// its PLL block.
+    pub async fn state_definition(
+        &mut self,
+        state: ControlInputState,
+    ) -> Result<OutputStateDefinition, Error<I2C::Error>> {
+        if self.n == 1 {
+            return match self.driver.y1_state_selection(state).await? {
+                OutputStateSelection::State0 => self.driver.y1_state_0().await,
+                OutputStateSelection::State1 => self.driver.y1_state_1().await,
+            };
+        }
+
+        let mut block = self.driver.pll(self.n / 2)?;
+        let selection = block.state_selection(state).await?;
+
+        block.state_definition(selection).await
+    }
+
+    /// Reads source, divider, state definitions and state selections of the
+    /// output. Fails with [`Error::ReservedEncoding`] if the multiplexer of the
+    /// second output of a block holds the reserved encoding.
+    pub async fn config(&mut self) -> Result<OutputConfig, Error<I2C::Error>> {
+        if self.n == 1 {
+            let base = Register::GenericConfiguration as u8;
+            let reg2 = self
+                .driver
+                .with(base + 2, |reg: &GenericConfigurationRegister2| *reg)
+                .await?;
+            let reg3 = self
+                .driver
+                .with(base + 3, |reg: &GenericConfigurationRegister3| *reg)
+                .await?;
+            let selections = self
+                .driver
+                .with(base + 4, |reg: &GenericConfigurationRegister4| {
+                    core::array::from_fn(|index| reg.y1_state_selection(u3::new(index as u8)))
+                })
+                .await?;
+
+            return Ok(OutputConfig {
+                source: OutputSource::Y1(reg2.y1_clock_source()),
+                divider: u10::new(reg3.pdiv1_full_value(&reg2)),
+                state0: reg2.y1_state0_definition(),
+                state1: reg2.y1_state1_definition(),
+                selections,
+            });
+        }
+
+        let base = self.block_offset();
+        let reg4 = self
+            .driver
+            .with(base + 4, |reg: &Pll1ConfigurationRegister4| *reg)
+            .await?;
+        let selections = self
+            .driver
+            .with(base + 5, |reg: &Pll1ConfigurationRegister5| {
+                core::array::from_fn(|index| reg.y2y3_state_selection(u3::new(index as u8)))
+            })
+            .await?;
+
+        let source = if self.n.is_multiple_of(2) {
+            OutputSource::First(reg4.output_y2_multiplexer())
+        } else {
+            OutputSource::Second(
+                reg4.output_y3_source()
+                    .map_err(|ReservedEncoding(raw)| Error::ReservedEncoding(raw))?,
+            )
+        };
+
+        Ok(OutputConfig {
+            source,
+            divider: self.divider().await?,
+            state0: reg4.y2y3_state0_definition(),
+            state1: reg4.y2y3_state1_definition(),
+            selections,
+        })
+    }
+
+    /// Writes a configuration read with [`Self::config`]. Fails with
+    /// [`Error::WrongOutputSource`] if the kind of source doesn't match the
+    /// output, before anything was written. A divider that doesn't fit is
+    /// handled like in [`Self::set_divider`].
+    pub async fn set_config(&mut self, config: &OutputConfig) -> Result<(), Error<I2C::Error>> {
+        let applies = match config.source {
+            OutputSource::Y1(_) => self.n == 1,
+            OutputSource::First(_) => self.n.is_multiple_of(2),
+            OutputSource::Second(_) => self.n != 1 && !self.n.is_multiple_of(2),
+        };
+
+        if !applies {
+            return Err(Error::WrongOutputSource(self.n));
+        }
+
+        // Every register involved is staged and committed with one block
+        // write, so a dropped future leaves the output either untouched or
+        // fully configured
+        if let OutputSource::Y1(source) = config.source {
+            let divider = config.divider.value();
+
+            return self
+                .driver
+                .update_block(
+                    Register::GenericConfiguration as u8 + 2,
+                    |bytes: &mut [u8; 3]| {
+                        let mut reg2 = GenericConfigurationRegister2::from(bytes[0]);
+                        let mut reg3 = GenericConfigurationRegister3::from(bytes[1]);
+                        let mut reg4 = GenericConfigurationRegister4::from(bytes[2]);
+
+                        reg2.set_y1_clock_source(source);
+                        reg2.set_y1_state0_definition(config.state0);
+                        reg2.set_y1_state1_definition(config.state1);
+                        reg2.set_pdiv1_9_8((divider >> 8) as _);
+                        reg3.set_pdiv1_7_0((divider & 0xFF) as _);
+                        for (index, selection) in config.selections.into_iter().enumerate() {
+                            reg4.set_y1_state_selection(u3::new(index as u8), selection);
+                        }
+
+                        *bytes = [reg2.into(), reg3.into(), reg4.into()];
+                    },
+                )
+                .await;
+        }
+
+        // Checked up front since it's the part that can be rejected
+        let divider = self.check_divider(config.divider)?;
+        let even = self.n.is_multiple_of(2);
+
+        self.driver
+            .update_block(self.block_offset() + 4, |bytes: &mut [u8; 4]| {
+                let mut reg4 = Pll1ConfigurationRegister4::from(bytes[0]);
+                let mut reg5 = Pll1ConfigurationRegister5::from(bytes[1]);
+
+                match config.source {
+                    OutputSource::First(mux) => reg4.set_output_y2_multiplexer(mux),
+                    OutputSource::Second(source) => reg4.set_output_y3_source(source),
+                    OutputSource::Y1(_) => {}
+                }
+                reg4.set_y2y3_state0_definition(config.state0);
+                reg4.set_y2y3_state1_definition(config.state1);
+                for (index, selection) in config.selections.into_iter().enumerate() {
+                    reg5.set_y2y3_state_selection(u3::new(index as u8), selection);
+                }
+
+                bytes[0] = reg4.into();
+                bytes[1] = reg5.into();
+
+                if even {
+                    let mut reg6 = Pll1ConfigurationRegister6::from(bytes[2]);
+                    reg6.set_pdiv2(divider.value());
+                    bytes[2] = reg6.into();
+                } else {
+                    let mut reg7 = Pll1ConfigurationRegister7::from(bytes[3]);
+                    reg7.set_pdiv3(divider.value());
+                    bytes[3] = reg7.into();
+                }
+            })
+            .await
+    }
+}
@@ -0,0 +1,801 @@
+//! Finds PLL settings and output dividers for a requested output frequency.
+//! Everything here is `const fn` so configurations can be planned at compile
+//! time as well as at run time. The search itself is behind the `planner`
+//! feature, only the datasheet limits remain without it.
+
+use crate::registers::{
+    generic_configuration::{DeviceIdentification, InputClockSelection},
+    pll1_configuration::VcoRangeSelection,
+};
+#[cfg(feature = "planner")]
+use crate::{
+    config::{DeviceConfig, REGISTER_WINDOW_LEN, SscSetting},
+    family::{Cdce913, Device},
+    registers::{
+        OutputStateDefinition,
+        generic_configuration::CrystalLoadCapacitance,
+        pll1_configuration::{
+            OutputY2Multiplexer, OutputY3Multiplexer, PllSettings, SscModulationAmountCenter,
+            SscModulationAmountDown,
+        },
+    },
+};
+
+/// Datasheet limits of the PLL dividers
+pub const N_MAX: u32 = 4095;
+pub const M_MAX: u32 = 511;
+pub const P_MAX: u32 = 4;
+pub const Q_MIN: u32 = 16;
+pub const Q_MAX: u32 = 63;
+pub const R_MAX: u32 = 51;
+/// Largest value of the 7-bit output dividers PDIV2..PDIVn
+pub const PDIV_MAX: u8 = 127;
+
+/// Frequency limits of one voltage variant of the device
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Limits {
+    pub vco_min_hz: u32,
+    pub vco_max_hz: u32,
+    /// Highest LVCMOS output frequency the datasheet specifies
+    pub max_output_hz: u32,
+}
+
+impl Limits {
+    pub const fn for_variant(variant: DeviceIdentification) -> Self {
+        match variant {
+            DeviceIdentification::CDCE913 => Self {
+                vco_min_hz: 80_000_000,
+                vco_max_hz: 230_000_000,
+                max_output_hz: 230_000_000,
+            },
+            // The 1.8 V outputs of the CDCEL variants can't toggle as fast
+            DeviceIdentification::CDCEL913 => Self {
+                vco_min_hz: 80_000_000,
+                vco_max_hz: 230_000_000,
+                max_output_hz: 150_000_000,
+            },
+        }
+    }
+
+    pub const fn vco_in_range(&self, vco_hz: u32) -> bool {
+        vco_hz >= self.vco_min_hz && vco_hz <= self.vco_max_hz
+    }
+}
+
+/// Reference frequencies the datasheet specifies for an input mode. The
+/// oscillator only starts with crystals of 8 to 32 MHz, a pullable one for
+/// the VCXO has to stay within 10 to 30 MHz, and an LVCMOS clock may run
+/// anywhere up to 160 MHz.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct InputRange {
+    pub min_hz: u32,
+    pub max_hz: u32,
+}
+
+impl InputRange {
+    pub const fn for_input(input: InputClockSelection) -> Self {
+        match input {
+            InputClockSelection::Xtal => Self {
+                min_hz: 8_000_000,
+                max_hz: 32_000_000,
+            },
+            InputClockSelection::Vcxo => Self {
+                min_hz: 10_000_000,
+                max_hz: 30_000_000,
+            },
+            InputClockSelection::LvCmos => Self {
+                min_hz: 0,
+                max_hz: 160_000_000,
+            },
+        }
+    }
+
+    pub const fn contains(&self, hz: u32) -> bool {
+        hz >= self.min_hz && hz <= self.max_hz
+    }
+}
+
+/// Supply voltage of the device, which is what sets the CDCEL variants apart
+/// and decides their limits, see [`Limits::for_supply`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SupplyVoltage {
+    /// CDCEL9xx
+    V1_8,
+    /// CDCE9xx
+    V3_3,
+}
+
+impl SupplyVoltage {
+    /// The supply the variant E_EL reports runs from
+    pub const fn for_variant(variant: DeviceIdentification) -> Self {
+        match variant {
+            DeviceIdentification::CDCE913 => Self::V3_3,
+            DeviceIdentification::CDCEL913 => Self::V1_8,
+        }
+    }
+
+    /// The variant running from this supply
+    pub const fn variant(&self) -> DeviceIdentification {
+        match self {
+            Self::V1_8 => DeviceIdentification::CDCEL913,
+            Self::V3_3 => DeviceIdentification::CDCE913,
+        }
+    }
+}
+
+impl Limits {
+    pub const fn for_supply(supply: SupplyVoltage) -> Self {
+        Self::for_variant(supply.variant())
+    }
+}
+
+#[cfg(feature = "planner")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PlanError {
+    /// The requested frequency is above what the variant can output
+    OutputTooFast,
+    /// No combination of dividers produces exactly the requested frequency
+    Unachievable,
+    /// The reference is outside of the [`InputRange`] of the input mode
+    ReferenceOutOfRange,
+}
+
+/// PLL settings and output divider producing the requested frequency
+#[cfg(feature = "planner")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PllPlan {
+    pub settings: PllSettings,
+    pub vco_hz: u32,
+    /// Divider between the VCO and the output, 1..=[`PDIV_MAX`]
+    pub pdiv: u8,
+    /// Output frequency the settings give, rounded to the nearest hertz
+    /// where it's fractional. Unlike `vco_hz / pdiv`, this doesn't lose the
+    /// fractions of the VCO frequency and the division to truncation.
+    pub output_hz: u32,
+}
+
+/// VCO range bits matching the given VCO frequency
+pub const fn vco_range(vco_hz: u32) -> VcoRangeSelection {
+    if vco_hz < 125_000_000 {
+        VcoRangeSelection::LessThan125MHz
+    } else if vco_hz < 150_000_000 {
+        VcoRangeSelection::From125To150MHz
+    } else if vco_hz < 175_000_000 {
+        VcoRangeSelection::From150To175MHz
+    } else {
+        VcoRangeSelection::GreaterOrEqual175MHz
+    }
+}
+
+/// Encodes fVCO = fIN * N / M as register settings, following the datasheet:
+/// P = 4 - int(log2(N / M)), N' = N * 2^P, Q = int(N' / M), R = N' - M * Q.
+/// Returns `None` if the resulting P, Q or R are out of range.
+#[cfg(feature = "planner")]
+pub const fn pll_settings(n: u32, m: u32, vco_hz: u32) -> Option<PllSettings> {
+    if n == 0 || n > N_MAX || m == 0 || m > M_MAX {
+        return None;
+    }
+
+    let ratio = n / m;
+    let log2 = if ratio == 0 { 0 } else { ratio.ilog2() };
+    let p = P_MAX.saturating_sub(log2);
+
+    let n_prime = n << p;
+    let q = n_prime / m;
+    let r = n_prime - m * q;
+
+    if q < Q_MIN || q > Q_MAX || r > R_MAX {
+        return None;
+    }
+
+    Some(PllSettings(
+        (n << 20) | (r << 11) | (q << 5) | (p << 2) | vco_range(vco_hz) as u32,
+    ))
+}
+
+#[cfg(feature = "planner")]
+const fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        let t = a % b;
+        a = b;
+        b = t;
+    }
+
+    a
+}
+
+/// Finds PLL settings that turn `input_hz` into exactly `vco_hz`
+#[cfg(feature = "planner")]
+pub const fn settings_for_vco(input_hz: u32, vco_hz: u32) -> Option<PllSettings> {
+    if input_hz == 0 || vco_hz == 0 {
+        return None;
+    }
+
+    let divisor = gcd(vco_hz as u64, input_hz as u64);
+    let n = vco_hz as u64 / divisor;
+    let m = input_hz as u64 / divisor;
+
+    // N / M is in lowest terms, multiples of it may still fit Q and R
+    let mut k = 1;
+    while n * k <= N_MAX as u64 && m * k <= M_MAX as u64 {
+        if let Some(settings) = pll_settings((n * k) as u32, (m * k) as u32, vco_hz) {
+            return Some(settings);
+        }
+
+        k += 1;
+    }
+
+    None
+}
+
+/// Finds PLL settings and an output divider that turn `input_hz` into exactly
+/// `output_hz`. Higher VCO frequencies are preferred since they give lower
+/// jitter.
+#[cfg(feature = "planner")]
+pub const fn plan(input_hz: u32, output_hz: u32, limits: &Limits) -> Result<PllPlan, PlanError> {
+    search(input_hz, output_hz, limits, false)
+}
+
+/// Like [`plan`], but goes for an even output divider (or none) first, which
+/// keeps the duty cycle at 50%, see [`crate::output::DutyCycle`]. Falls back
+/// to an odd divider if that's the only way to reach `output_hz`.
+#[cfg(feature = "planner")]
+pub const fn plan_preferring_even(
+    input_hz: u32,
+    output_hz: u32,
+    limits: &Limits,
+) -> Result<PllPlan, PlanError> {
+    match search(input_hz, output_hz, limits, true) {
+        Ok(plan) => Ok(plan),
+        Err(_) => search(input_hz, output_hz, limits, false),
+    }
+}
+
+// Search behind `plan`, only considering dividers that keep the duty cycle at
+// 50% if `balanced_only` is set
+#[cfg(feature = "planner")]
+const fn search(
+    input_hz: u32,
+    output_hz: u32,
+    limits: &Limits,
+    balanced_only: bool,
+) -> Result<PllPlan, PlanError> {
+    if output_hz > limits.max_output_hz {
+        return Err(PlanError::OutputTooFast);
+    }
+    if input_hz == 0 || output_hz == 0 {
+        return Err(PlanError::Unachievable);
+    }
+
+    let mut pdiv = PDIV_MAX;
+    while pdiv > 0 {
+        let vco_hz = output_hz as u64 * pdiv as u64;
+
+        if vco_hz <= limits.vco_max_hz as u64 {
+            if vco_hz < limits.vco_min_hz as u64 {
+                break;
+            }
+
+            if (!balanced_only || balanced(pdiv as u32))
+                && let Some(settings) = settings_for_vco(input_hz, vco_hz as u32)
+            {
+                return Ok(PllPlan {
+                    settings,
+                    vco_hz: vco_hz as u32,
+                    pdiv,
+                    output_hz,
+                });
+            }
+        }
+
+        pdiv -= 1;
+    }
+
+    Err(PlanError::Unachievable)
+}
+
+/// Like [`plan`], but for when an exact match isn't needed: finds the setting
+/// whose output frequency comes closest to `output_hz` while staying within
+/// `min_hz..=max_hz`. The output frequency of the result may be fractional,
+/// see [`PllPlan::output_hz`].
+#[cfg(feature = "planner")]
+pub const fn plan_nearest(
+    input_hz: u32,
+    output_hz: u32,
+    min_hz: u32,
+    max_hz: u32,
+    limits: &Limits,
+) -> Result<PllPlan, PlanError> {
+    if min_hz > limits.max_output_hz {
+        return Err(PlanError::OutputTooFast);
+    }
+    if input_hz == 0 || max_hz == 0 || min_hz > max_hz {
+        return Err(PlanError::Unachievable);
+    }
+
+    let input = input_hz as u128;
+    let mut best: Option<PllPlan> = None;
+    // Distance of the best output frequency from `output_hz` as the fraction
+    // best_num / best_den, compared by cross-multiplying
+    let mut best_num = 0u128;
+    let mut best_den = 1u128;
+
+    let mut pdiv = PDIV_MAX as u32;
+    while pdiv > 0 {
+        let mut m = 1;
+        while m <= M_MAX {
+            // Output frequency input * n / (m * pdiv), so the two values of
+            // n around the one hitting `output_hz` are the candidates
+            let den = (m * pdiv) as u128;
+            let floor = output_hz as u128 * den / input;
+            let mut n = if floor == 0 { 1 } else { floor };
+
+            while n <= floor + 1 && n <= N_MAX as u128 {
+                let num = input * n;
+                let vco_hz = (num / m as u128) as u32;
+
+                if num >= min_hz as u128 * den
+                    && num <= max_hz as u128 * den
+                    && limits.vco_in_range(vco_hz)
+                    && let Some(settings) = pll_settings(n as u32, m, vco_hz)
+                {
+                    let distance = num.abs_diff(output_hz as u128 * den);
+
+                    if best.is_none() || distance * best_den < best_num * den {
+                        best = Some(PllPlan {
+                            settings,
+                            vco_hz,
+                            pdiv: pdiv as u8,
+                            output_hz: ((num + den / 2) / den) as u32,
+                        });
+                        best_num = distance;
+                        best_den = den;
+                    }
+                }
+
+                n += 1;
+            }
+
+            m += 1;
+        }
+
+        pdiv -= 1;
+    }
+
+    match best {
+        Some(plan) => Ok(plan),
+        None => Err(PlanError::Unachievable),
+    }
+}
+
+/// Spread spectrum setting for a center spread of `hundredths` of a percent
+/// (e.g. 50 for ±0.5%), if the device supports that amount
+#[cfg(feature = "planner")]
+pub const fn ssc_center(hundredths: u32) -> Option<SscSetting> {
+    use SscModulationAmountCenter::*;
+
+    Some(SscSetting::Center(match hundredths {
+        0 => Off,
+        25 => PlusMinus025Percent,
+        50 => PlusMinus05Percent,
+        75 => PlusMinus075Percent,
+        100 => PlusMinus1Percent,
+        125 => PlusMinus125Percent,
+        150 => PlusMinus15Percent,
+        200 => PlusMinus2Percent,
+        _ => return None,
+    }))
+}
+
+/// Spread spectrum setting for a down spread of `hundredths` of a percent
+/// (e.g. 50 for -0.5%), if the device supports that amount
+#[cfg(feature = "planner")]
+pub const fn ssc_down(hundredths: u32) -> Option<SscSetting> {
+    use SscModulationAmountDown::*;
+
+    Some(SscSetting::Down(match hundredths {
+        0 => Off,
+        25 => Minus025Percent,
+        50 => Minus05Percent,
+        75 => Minus075Percent,
+        100 => Minus1Percent,
+        125 => Minus125Percent,
+        150 => Minus15Percent,
+        200 => Minus2Percent,
+        _ => return None,
+    }))
+}
+
+/// Largest value of the 10-bit Y1 divider PDIV1
+pub const PDIV1_MAX: u16 = 1023;
+
+/// Output frequencies and options for a complete CDCx913 configuration,
+/// turned into a [`DeviceConfig`] by [`Self::build`]. Every requested output
+/// is enabled for all control input states, outputs that aren't requested are
+/// 3-stated. TARGET_ADR is the default of the family member given with
+/// [`Self::with_device`], the CDCE(L)913 unless changed.
+#[cfg(feature = "planner")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ConfigRequest {
+    reference_hz: u32,
+    y1_hz: Option<u32>,
+    y2_hz: Option<u32>,
+    y3_hz: Option<u32>,
+    ssc: Option<SscSetting>,
+    variant: DeviceIdentification,
+    even_dividers: bool,
+    target_adr: u8,
+}
+
+#[cfg(feature = "planner")]
+impl Default for ConfigRequest {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "planner")]
+impl ConfigRequest {
+    pub const fn new() -> Self {
+        Self {
+            reference_hz: 0,
+            y1_hz: None,
+            y2_hz: None,
+            y3_hz: None,
+            ssc: None,
+            variant: DeviceIdentification::CDCE913,
+            even_dividers: false,
+            target_adr: Cdce913::DEFAULT_ADDRESS & 0b11,
+        }
+    }
+
+    /// Frequency of the crystal on Xin/Xout, within the
+    /// [`InputClockSelection::Xtal`] [`InputRange`]
+    pub const fn with_reference_hz(mut self, hz: u32) -> Self {
+        self.reference_hz = hz;
+        self
+    }
+
+    pub const fn with_y1_hz(mut self, hz: u32) -> Self {
+        self.y1_hz = Some(hz);
+        self
+    }
+
+    pub const fn with_y2_hz(mut self, hz: u32) -> Self {
+        self.y2_hz = Some(hz);
+        self
+    }
+
+    pub const fn with_y3_hz(mut self, hz: u32) -> Self {
+        self.y3_hz = Some(hz);
+        self
+    }
+
+    /// Spread spectrum modulation applied in every control input state
+    pub const fn with_ssc(mut self, ssc: SscSetting) -> Self {
+        self.ssc = Some(ssc);
+        self
+    }
+
+    /// Voltage variant whose limits apply, the CDCE913 unless changed
+    pub const fn with_variant(mut self, variant: DeviceIdentification) -> Self {
+        self.variant = variant;
+        self
+    }
+
+    /// Family member the configuration is for, which decides TARGET_ADR: its
+    /// default, so the device keeps answering on its default address, e.g.
+    /// 00 for the CDCE(L)925 and 01 for the CDCE(L)913. Only the window
+    /// shared by the family is planned, see [`DeviceConfig`].
+    pub const fn with_device<D: Device + Copy>(mut self, _device: D) -> Self {
+        self.target_adr = D::DEFAULT_ADDRESS & 0b11;
+        self
+    }
+
+    /// Like [`Self::with_variant`], for the variant running from `supply`
+    pub const fn with_supply_voltage(self, supply: SupplyVoltage) -> Self {
+        self.with_variant(supply.variant())
+    }
+
+    /// Goes for even output dividers first, like [`plan_preferring_even`],
+    /// for outputs feeding loads that need a 50% duty cycle
+    pub const fn with_even_dividers_preferred(mut self) -> Self {
+        self.even_dividers = true;
+        self
+    }
+
+    /// Plans the dividers for all requested outputs, which share one VCO
+    /// frequency. Y1 is derived from the input clock directly when it divides
+    /// evenly, so PLL1 is bypassed when no other output needs it.
+    pub const fn build(&self) -> Result<DeviceConfig, PlanError> {
+        if self.even_dividers
+            && let Ok(config) = self.build_with(true)
+        {
+            return Ok(config);
+        }
+
+        self.build_with(false)
+    }
+
+    const fn build_with(&self, balanced_only: bool) -> Result<DeviceConfig, PlanError> {
+        let limits = Limits::for_variant(self.variant);
+        let outputs = [self.y1_hz, self.y2_hz, self.y3_hz];
+
+        if self.reference_hz == 0 {
+            return Err(PlanError::Unachievable);
+        }
+        if !InputRange::for_input(InputClockSelection::Xtal).contains(self.reference_hz) {
+            return Err(PlanError::ReferenceOutOfRange);
+        }
+
+        let mut index = 0;
+        while index < outputs.len() {
+            if let Some(hz) = outputs[index] {
+                if hz > limits.max_output_hz {
+                    return Err(PlanError::OutputTooFast);
+                }
+                if hz == 0 {
+                    return Err(PlanError::Unachievable);
+                }
+            }
+            index += 1;
+        }
+
+        let y1_from_input = match self.y1_hz {
+            Some(hz) => {
+                self.reference_hz.is_multiple_of(hz)
+                    && self.reference_hz / hz <= PDIV1_MAX as u32
+                    && (!balanced_only || balanced(self.reference_hz / hz))
+            }
+            None => true,
+        };
+        let needs_pll = self.y2_hz.is_some() || self.y3_hz.is_some() || !y1_from_input;
+
+        let mut settings = PllSettings(0);
+        let mut pdiv = [0u32; 3];
+
+        if let Some(hz) = self.y1_hz
+            && y1_from_input
+        {
+            pdiv[0] = self.reference_hz / hz;
+        }
+
+        if needs_pll {
+            // The VCO is searched as a multiple of the first requested PLL
+            // output and has to divide evenly into all the others
+            let (primary_hz, primary_max) = match (self.y2_hz, self.y3_hz, self.y1_hz) {
+                (Some(hz), _, _) | (None, Some(hz), _) => (hz, PDIV_MAX as u32),
+                (None, None, Some(hz)) => (hz, PDIV1_MAX as u32),
+                (None, None, None) => return Err(PlanError::Unachievable),
+            };
+
+            let mut divider = primary_max;
+            let found = loop {
+                if divider == 0 {
+                    break None;
+                }
+
+                let vco_hz = primary_hz as u64 * divider as u64;
+                divider -= 1;
+
+                if vco_hz > limits.vco_max_hz as u64 {
+                    continue;
+                }
+                if vco_hz < limits.vco_min_hz as u64 {
+                    break None;
+                }
+
+                let vco_hz = vco_hz as u32;
+                let y1 = if y1_from_input {
+                    Some(pdiv[0])
+                } else {
+                    pll_divider(vco_hz, self.y1_hz, PDIV1_MAX as u32)
+                };
+                let y2 = pll_divider(vco_hz, self.y2_hz, PDIV_MAX as u32);
+                let y3 = pll_divider(vco_hz, self.y3_hz, PDIV_MAX as u32);
+
+                if let (Some(y1), Some(y2), Some(y3)) = (y1, y2, y3)
+                    && (!balanced_only || (balanced(y1) && balanced(y2) && balanced(y3)))
+                    && let Some(found) = settings_for_vco(self.reference_hz, vco_hz)
+                {
+                    pdiv = [y1, y2, y3];
+                    break Some(found);
+                }
+            };
+
+            settings = match found {
+                Some(settings) => settings,
+                None => return Err(PlanError::Unachievable),
+            };
+        }
+
+        Ok(self.encode(settings, pdiv, y1_from_input, needs_pll))
+    }
+
+    const fn encode(
+        &self,
+        settings: PllSettings,
+        pdiv: [u32; 3],
+        y1_from_input: bool,
+        needs_pll: bool,
+    ) -> DeviceConfig {
+        const ENABLED: u8 = OutputStateDefinition::Enabled as u8;
+        const DISABLED: u8 = OutputStateDefinition::Disabled3State as u8;
+
+        let mut registers = [0u8; REGISTER_WINDOW_LEN];
+
+        let y1_state = if self.y1_hz.is_some() {
+            ENABLED
+        } else {
+            DISABLED
+        };
+        let y2y3_state = if self.y2_hz.is_some() || self.y3_hz.is_some() {
+            ENABLED
+        } else {
+            DISABLED
+        };
+        let (ssc_center, ssc_code) = match self.ssc {
+            Some(SscSetting::Center(amount)) => (true, amount as u32),
+            Some(SscSetting::Down(amount)) => (false, amount as u32),
+            None => (false, 0),
+        };
+
+        // Crystal input, TARGET_ADR at the default of the family member
+        registers[0x01] = self.target_adr;
+        registers[0x02] =
+            (!y1_from_input as u8) << 7 | y1_state << 4 | y1_state << 2 | (pdiv[0] >> 8) as u8;
+        registers[0x03] = pdiv[0] as u8;
+        // Every control input state selects state 1
+        registers[0x04] = 0xFF;
+        registers[0x05] = CrystalLoadCapacitance::DEFAULT.xcsel() << 3;
+        registers[0x06] = (REGISTER_WINDOW_LEN as u8) << 1;
+
+        let mut ssc_word = 0u32;
+        let mut state = 0;
+        while state < 8 {
+            ssc_word |= ssc_code << (3 * state);
+            state += 1;
+        }
+        let [_, ssc0, ssc1, ssc2] = ssc_word.to_be_bytes();
+        registers[0x10] = ssc0;
+        registers[0x11] = ssc1;
+        registers[0x12] = ssc2;
+
+        registers[0x14] = (!needs_pll as u8) << 7
+            | (OutputY2Multiplexer::Pdiv2 as u8) << 6
+            | (OutputY3Multiplexer::Pdiv3 as u8) << 4
+            | y2y3_state << 2
+            | y2y3_state;
+        registers[0x15] = 0xFF;
+        registers[0x16] = (ssc_center as u8) << 7 | pdiv[1] as u8;
+        registers[0x17] = pdiv[2] as u8;
+
+        // Both banks get the same settings, so FS1 doesn't matter
+        let [s0, s1, s2, s3] = settings.0.to_be_bytes();
+        registers[0x18] = s0;
+        registers[0x19] = s1;
+        registers[0x1A] = s2;
+        registers[0x1B] = s3;
+        registers[0x1C] = s0;
+        registers[0x1D] = s1;
+        registers[0x1E] = s2;
+        registers[0x1F] = s3;
+
+        DeviceConfig::from_bytes(registers)
+    }
+}
+
+/// Divider producing `output_hz` from `vco_hz`: `Some(0)` (divider held in
+/// reset) if the output isn't requested, `None` if it can't be produced
+#[cfg(feature = "planner")]
+const fn pll_divider(vco_hz: u32, output_hz: Option<u32>, max: u32) -> Option<u32> {
+    match output_hz {
+        None => Some(0),
+        Some(hz) if vco_hz.is_multiple_of(hz) && vco_hz / hz <= max => Some(vco_hz / hz),
+        Some(_) => None,
+    }
+}
+
+// Whether a divider keeps the duty cycle at 50%, counting 0 (reset) as well
+#[cfg(feature = "planner")]
+const fn balanced(divider: u32) -> bool {
+    divider <= 1 || divider.is_multiple_of(2)
+}
+
+/// Builds a [`DeviceConfig`](crate::config::DeviceConfig) at compile time,
+/// failing the build if the requested frequencies can't be produced exactly:
+///
+/// ```
+/// const CONFIG: cdcx913::config::DeviceConfig = cdcx913::cdcx913_config! {
+///     reference = 25 MHz,
+///     y1 = 25 MHz,
+///     y2 = 50 MHz,
+///     ssc = center 0.5%,
+/// };
+/// ```
+///
+/// Frequencies take `MHz`, `kHz` or `Hz`, spread spectrum is `center` or
+/// `down` followed by the amount in percent, `variant = CDCEL913` applies
+/// the limits of the 1.8 V variant and `device = Cdce925` keeps TARGET_ADR at
+/// the default of another family member. See [`ConfigRequest`] for the run
+/// time equivalent.
+#[cfg(feature = "planner")]
+#[macro_export]
+macro_rules! cdcx913_config {
+    ($($body:tt)*) => {
+        const {
+            match $crate::__cdcx913_config!(@munch ($crate::planner::ConfigRequest::new()) $($body)*)
+                .build()
+            {
+                ::core::result::Result::Ok(config) => config,
+                ::core::result::Result::Err($crate::planner::PlanError::OutputTooFast) => {
+                    ::core::panic!("cdcx913_config!: an output is faster than the device allows")
+                }
+                ::core::result::Result::Err($crate::planner::PlanError::ReferenceOutOfRange) => {
+                    ::core::panic!("cdcx913_config!: the reference is outside of the crystal range")
+                }
+                ::core::result::Result::Err(_) => {
+                    ::core::panic!("cdcx913_config!: the requested frequencies are unachievable")
+                }
+            }
+        }
+    };
+}
+
+#[cfg(feature = "planner")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __cdcx913_config {
+    (@munch ($request:expr)) => { $request };
+    (@munch ($request:expr) reference = $value:literal $unit:ident $(, $($rest:tt)*)?) => {
+        $crate::__cdcx913_config!(@munch ($request.with_reference_hz(
+            $crate::__cdcx913_config!(@hz $value $unit)
+        )) $($($rest)*)?)
+    };
+    (@munch ($request:expr) y1 = $value:literal $unit:ident $(, $($rest:tt)*)?) => {
+        $crate::__cdcx913_config!(@munch ($request.with_y1_hz(
+            $crate::__cdcx913_config!(@hz $value $unit)
+        )) $($($rest)*)?)
+    };
+    (@munch ($request:expr) y2 = $value:literal $unit:ident $(, $($rest:tt)*)?) => {
+        $crate::__cdcx913_config!(@munch ($request.with_y2_hz(
+            $crate::__cdcx913_config!(@hz $value $unit)
+        )) $($($rest)*)?)
+    };
+    (@munch ($request:expr) y3 = $value:literal $unit:ident $(, $($rest:tt)*)?) => {
+        $crate::__cdcx913_config!(@munch ($request.with_y3_hz(
+            $crate::__cdcx913_config!(@hz $value $unit)
+        )) $($($rest)*)?)
+    };
+    (@munch ($request:expr) ssc = $kind:ident $value:literal % $(, $($rest:tt)*)?) => {
+        $crate::__cdcx913_config!(@munch ($request.with_ssc(
+            match $crate::__cdcx913_config!(@ssc $kind)((($value as f64) * 100.0 + 0.5) as u32) {
+                ::core::option::Option::Some(ssc) => ssc,
+                ::core::option::Option::None => {
+                    ::core::panic!("cdcx913_config!: unsupported spread spectrum amount")
+                }
+            }
+        )) $($($rest)*)?)
+    };
+    (@munch ($request:expr) device = $device:ident $(, $($rest:tt)*)?) => {
+        $crate::__cdcx913_config!(@munch ($request.with_device(
+            $crate::family::$device
+        )) $($($rest)*)?)
+    };
+    (@munch ($request:expr) variant = $variant:ident $(, $($rest:tt)*)?) => {
+        $crate::__cdcx913_config!(@munch ($request.with_variant(
+            $crate::registers::generic_configuration::DeviceIdentification::$variant
+        )) $($($rest)*)?)
+    };
+    (@ssc center) => { $crate::planner::ssc_center };
+    (@ssc down) => { $crate::planner::ssc_down };
+    (@hz $value:literal MHz) => { (($value as f64) * 1_000_000.0 + 0.5) as u32 };
+    (@hz $value:literal kHz) => { (($value as f64) * 1_000.0 + 0.5) as u32 };
+    (@hz $value:literal Hz) => { $value as u32 };
+}
@@ -0,0 +1,312 @@
+#[cfg(feature = "ssc")]
+use arbitrary_int::u3;
+use arbitrary_int::u7;
+use embedded_hal_async::i2c::I2c;
+
+#[cfg(feature = "ssc")]
+use crate::registers::pll1_configuration::{
+    SscDownCenterSelection, pack_ssc1_selections, unpack_ssc1_selections,
+};
+use crate::{
+    CDCx913, Error, Register,
+    control::ControlInputState,
+    family::Device,
+    registers::{
+        OutputStateDefinition, OutputStateSelection, ReservedEncoding,
+        pll1_configuration::{
+            Fs1Selection, OutputY2Multiplexer, OutputY3Multiplexer, OutputY3Source,
+            Pll1ConfigurationRegister3, Pll1ConfigurationRegister4, Pll1ConfigurationRegister5,
+            Pll1ConfigurationRegister6, Pll1ConfigurationRegister7, Pll1Multiplexer, PllSettings,
+        },
+    },
+    timing::NoDelay,
+};
+
+/// Handle to the configuration block of one PLL. Every PLL block has the same
+/// layout as the PLL1 block, so the `Pll1ConfigurationRegisterN` types apply to
+/// all of them. Each block also controls two outputs: for the block of PLLn
+/// (n >= 2) those are Y(2n) and Y(2n+1), fed by PDIV(2n) and PDIV(2n+1).
+pub struct PllBlock<'a, I2C, D, DELAY = NoDelay> {
+    driver: &'a mut CDCx913<I2C, D, DELAY>,
+    base: u8,
+}
+
+impl<'a, I2C: I2c, D: Device, DELAY> PllBlock<'a, I2C, D, DELAY> {
+    pub(crate) fn new(driver: &'a mut CDCx913<I2C, D, DELAY>, base: u8) -> Self {
+        Self { driver, base }
+    }
+
+    async fn with<T: From<u8>, R>(
+        &mut self,
+        index: u8,
+        f: impl FnOnce(&T) -> R,
+    ) -> Result<R, Error<I2C::Error>> {
+        self.driver.with(self.base + index, f).await
+    }
+
+    async fn modify<T: From<u8> + Into<u8>, R>(
+        &mut self,
+        index: u8,
+        f: impl FnOnce(&mut T) -> R,
+    ) -> Result<R, Error<I2C::Error>> {
+        self.driver
+            .modify_byte_unchecked(self.base + index, f)
+            .await
+    }
+
+    fn settings_offset(&self, bank: Fs1Selection) -> u8 {
+        match bank {
+            Fs1Selection::Fvcxo0 => self.base + 0x8,
+            Fs1Selection::Fvcxo1 => self.base + 0xC,
+        }
+    }
+
+    /// N/R/Q/P and VCO range of one of the two settings banks
+    pub async fn settings(&mut self, bank: Fs1Selection) -> Result<PllSettings, Error<I2C::Error>> {
+        let offset = self.settings_offset(bank);
+        let bytes = self.driver.read_block(offset).await?;
+
+        Ok(PllSettings(u32::from_be_bytes(bytes)))
+    }
+
+    /// Writes all four bytes of one settings bank with a single block write,
+    /// so a dropped future never leaves a mix of old and new settings
+    pub async fn set_settings(
+        &mut self,
+        bank: Fs1Selection,
+        value: PllSettings,
+    ) -> Result<(), Error<I2C::Error>> {
+        if self.base == Register::Pll1Configuration as u8 {
+            self.driver.ensure_pll1_retunable().await?;
+        }
+
+        let offset = self.settings_offset(bank);
+
+        self.driver
+            .write_block(offset, &value.0.to_be_bytes())
+            .await
+    }
+
+    pub async fn multiplexer(&mut self) -> Result<Pll1Multiplexer, Error<I2C::Error>> {
+        self.with(4, |reg: &Pll1ConfigurationRegister4| reg.pll1_multiplexer())
+            .await
+    }
+
+    pub async fn set_multiplexer(
+        &mut self,
+        value: Pll1Multiplexer,
+    ) -> Result<(), Error<I2C::Error>> {
+        self.modify(4, |reg: &mut Pll1ConfigurationRegister4| {
+            reg.set_pll1_multiplexer(value)
+        })
+        .await
+    }
+
+    pub async fn frequency_selection(
+        &mut self,
+        state: ControlInputState,
+    ) -> Result<Fs1Selection, Error<I2C::Error>> {
+        self.with(3, |reg: &Pll1ConfigurationRegister3| {
+            reg.fs1_selection(state)
+        })
+        .await
+    }
+
+    pub async fn set_frequency_selection(
+        &mut self,
+        state: ControlInputState,
+        value: Fs1Selection,
+    ) -> Result<(), Error<I2C::Error>> {
+        self.modify(3, |reg: &mut Pll1ConfigurationRegister3| {
+            reg.set_fs1_selection(state, value)
+        })
+        .await
+    }
+
+    #[cfg(feature = "ssc")]
+    async fn ssc_selections(&mut self) -> Result<[u3; 8], Error<I2C::Error>> {
+        Ok(unpack_ssc1_selections(
+            self.driver.read_block(self.base).await?,
+        ))
+    }
+
+    #[cfg(feature = "ssc")]
+    pub async fn spread_spectrum_clocking_selection_raw(
+        &mut self,
+        state: ControlInputState,
+    ) -> Result<u3, Error<I2C::Error>> {
+        Ok(self.ssc_selections().await?[state as usize])
+    }
+
+    #[cfg(feature = "ssc")]
+    pub async fn set_spread_spectrum_clocking_selection_raw(
+        &mut self,
+        state: ControlInputState,
+        value: u3,
+    ) -> Result<(), Error<I2C::Error>> {
+        self.driver
+            .update_block(self.base, |bytes: &mut [u8; 3]| {
+                let mut selections = unpack_ssc1_selections(*bytes);
+
+                selections[state as usize] = value;
+                *bytes = pack_ssc1_selections(selections);
+            })
+            .await
+    }
+
+    #[cfg(feature = "ssc")]
+    pub async fn ssc_down_center_selection(
+        &mut self,
+    ) -> Result<SscDownCenterSelection, Error<I2C::Error>> {
+        self.with(6, |reg: &Pll1ConfigurationRegister6| {
+            reg.pll1_ssc_down_center_selection()
+        })
+        .await
+    }
+
+    #[cfg(feature = "ssc")]
+    pub async fn set_ssc_down_center_selection(
+        &mut self,
+        value: SscDownCenterSelection,
+    ) -> Result<(), Error<I2C::Error>> {
+        self.modify(6, |reg: &mut Pll1ConfigurationRegister6| {
+            reg.set_pll1_ssc_down_center_selection(value)
+        })
+        .await
+    }
+
+    /// Multiplexer of the first output of the block (M2 for PLL1, M4 for
+    /// PLL2, ...). Outside the PLL1 block `Pdiv1` selects PDIV(2n-2) and
+    /// `Pdiv2` selects PDIV(2n)
+    pub async fn first_output_multiplexer(
+        &mut self,
+    ) -> Result<OutputY2Multiplexer, Error<I2C::Error>> {
+        self.with(4, |reg: &Pll1ConfigurationRegister4| {
+            reg.output_y2_multiplexer()
+        })
+        .await
+    }
+
+    pub async fn set_first_output_multiplexer(
+        &mut self,
+        value: OutputY2Multiplexer,
+    ) -> Result<(), Error<I2C::Error>> {
+        self.modify(4, |reg: &mut Pll1ConfigurationRegister4| {
+            reg.set_output_y2_multiplexer(value)
+        })
+        .await
+    }
+
+    /// Multiplexer of the second output of the block (M3 for PLL1, M5 for
+    /// PLL2, ...). Outside the PLL1 block `Pdiv1`, `Pdiv2` and `Pdiv3` select
+    /// PDIV(2n-2), PDIV(2n) and PDIV(2n+1)
+    pub async fn second_output_multiplexer(
+        &mut self,
+    ) -> Result<OutputY3Multiplexer, Error<I2C::Error>> {
+        self.with(4, |reg: &Pll1ConfigurationRegister4| {
+            reg.output_y3_multiplexer()
+        })
+        .await
+    }
+
+    pub async fn set_second_output_multiplexer(
+        &mut self,
+        value: OutputY3Multiplexer,
+    ) -> Result<(), Error<I2C::Error>> {
+        self.modify(4, |reg: &mut Pll1ConfigurationRegister4| {
+            reg.set_output_y3_multiplexer(value)
+        })
+        .await
+    }
+
+    /// Like [`Self::second_output_multiplexer`], but fails with
+    /// [`Error::ReservedEncoding`] on the reserved encoding
+    pub async fn second_output_source(&mut self) -> Result<OutputY3Source, Error<I2C::Error>> {
+        self.with(4, |reg: &Pll1ConfigurationRegister4| reg.output_y3_source())
+            .await?
+            .map_err(|ReservedEncoding(raw)| Error::ReservedEncoding(raw))
+    }
+
+    pub async fn set_second_output_source(
+        &mut self,
+        value: OutputY3Source,
+    ) -> Result<(), Error<I2C::Error>> {
+        self.modify(4, |reg: &mut Pll1ConfigurationRegister4| {
+            reg.set_output_y3_source(value)
+        })
+        .await
+    }
+
+    /// Output state definition shared by both outputs of the block
+    pub async fn state_definition(
+        &mut self,
+        selection: OutputStateSelection,
+    ) -> Result<OutputStateDefinition, Error<I2C::Error>> {
+        self.with(4, |reg: &Pll1ConfigurationRegister4| match selection {
+            OutputStateSelection::State0 => reg.y2y3_state0_definition(),
+            OutputStateSelection::State1 => reg.y2y3_state1_definition(),
+        })
+        .await
+    }
+
+    pub async fn set_state_definition(
+        &mut self,
+        selection: OutputStateSelection,
+        value: OutputStateDefinition,
+    ) -> Result<(), Error<I2C::Error>> {
+        self.modify(4, |reg: &mut Pll1ConfigurationRegister4| match selection {
+            OutputStateSelection::State0 => reg.set_y2y3_state0_definition(value),
+            OutputStateSelection::State1 => reg.set_y2y3_state1_definition(value),
+        })
+        .await
+    }
+
+    pub async fn state_selection(
+        &mut self,
+        state: ControlInputState,
+    ) -> Result<OutputStateSelection, Error<I2C::Error>> {
+        self.with(5, |reg: &Pll1ConfigurationRegister5| {
+            reg.y2y3_state_selection(state)
+        })
+        .await
+    }
+
+    pub async fn set_state_selection(
+        &mut self,
+        state: ControlInputState,
+        value: OutputStateSelection,
+    ) -> Result<(), Error<I2C::Error>> {
+        self.modify(5, |reg: &mut Pll1ConfigurationRegister5| {
+            reg.set_y2y3_state_selection(state, value)
+        })
+        .await
+    }
+
+    /// Divider of the first output of the block (PDIV2 for PLL1, PDIV4 for
+    /// PLL2, ...)
+    pub async fn first_output_divider(&mut self) -> Result<u7, Error<I2C::Error>> {
+        self.with(6, |reg: &Pll1ConfigurationRegister6| u7::new(reg.pdiv2()))
+            .await
+    }
+
+    pub async fn set_first_output_divider(&mut self, value: u7) -> Result<(), Error<I2C::Error>> {
+        self.modify(6, |reg: &mut Pll1ConfigurationRegister6| {
+            reg.set_pdiv2(value.value())
+        })
+        .await
+    }
+
+    /// Divider of the second output of the block (PDIV3 for PLL1, PDIV5 for
+    /// PLL2, ...)
+    pub async fn second_output_divider(&mut self) -> Result<u7, Error<I2C::Error>> {
+        self.with(7, |reg: &Pll1ConfigurationRegister7| u7::new(reg.pdiv3()))
+            .await
+    }
+
+    pub async fn set_second_output_divider(&mut self, value: u7) -> Result<(), Error<I2C::Error>> {
+        self.modify(7, |reg: &mut Pll1ConfigurationRegister7| {
+            reg.set_pdiv3(value.value())
+        })
+        .await
+    }
+}
@@ -0,0 +1,186 @@
+//! Ready-made plans for the most common uses of the part, built on
+//! [`crate::planner`]: audio master clocks and Ethernet PHY reference clocks.
+//! The audio helpers are `const fn` like the planner.
+
+use crate::{
+    config::{DeviceConfig, ValidationError},
+    output::OutputMask,
+    planner::{ConfigRequest, Limits, PlanError, PllPlan, plan},
+    registers::generic_configuration::DeviceIdentification,
+};
+
+/// Standard audio sample rates
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SampleRate {
+    Hz32000,
+    Hz44100,
+    Hz48000,
+    Hz88200,
+    Hz96000,
+    Hz176400,
+    Hz192000,
+}
+
+impl SampleRate {
+    pub const fn hz(self) -> u32 {
+        match self {
+            Self::Hz32000 => 32_000,
+            Self::Hz44100 => 44_100,
+            Self::Hz48000 => 48_000,
+            Self::Hz88200 => 88_200,
+            Self::Hz96000 => 96_000,
+            Self::Hz176400 => 176_400,
+            Self::Hz192000 => 192_000,
+        }
+    }
+}
+
+/// Master clock frequency as a multiple of the sample rate (fs)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum MclkRatio {
+    Fs256,
+    Fs384,
+    Fs512,
+}
+
+impl MclkRatio {
+    pub const fn multiplier(self) -> u32 {
+        match self {
+            Self::Fs256 => 256,
+            Self::Fs384 => 384,
+            Self::Fs512 => 512,
+        }
+    }
+}
+
+/// Audio master clock frequency for `sample_rate` at `ratio`, e.g.
+/// 12.288 MHz for 48 kHz at 256 fs
+pub const fn mclk_hz(sample_rate: SampleRate, ratio: MclkRatio) -> u32 {
+    sample_rate.hz() * ratio.multiplier()
+}
+
+/// PLL settings and output divider for an audio master clock generated from
+/// a `crystal_hz` crystal. Audio converters need the exact frequency, so this
+/// fails with [`PlanError::Unachievable`] rather than settling for a close
+/// one. How many combinations a crystal reaches varies a lot: 24.576 MHz
+/// reaches all of them, 25 MHz hardly any.
+pub const fn plan_audio_mclk(
+    crystal_hz: u32,
+    sample_rate: SampleRate,
+    ratio: MclkRatio,
+    limits: &Limits,
+) -> Result<PllPlan, PlanError> {
+    plan(crystal_hz, mclk_hz(sample_rate, ratio), limits)
+}
+
+/// Reference clocks Ethernet PHYs take: 25 MHz for MII and most RGMII PHYs,
+/// 50 MHz for RMII and 125 MHz for GMII
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PhyClock {
+    Mhz25,
+    Mhz50,
+    Mhz125,
+}
+
+impl PhyClock {
+    pub const fn hz(self) -> u32 {
+        match self {
+            Self::Mhz25 => 25_000_000,
+            Self::Mhz50 => 50_000_000,
+            Self::Mhz125 => 125_000_000,
+        }
+    }
+}
+
+/// Frequency tolerance Ethernet allows for the PHY reference clock
+pub const PHY_CLOCK_TOLERANCE_PPM: u32 = 50;
+
+/// Frequency error budget of a PHY reference clock. The PLL hits the nominal
+/// frequency exactly, so the output is off by as much as the crystal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PpmReport {
+    /// Total tolerance of the crystal: initial, over temperature and aging
+    pub crystal_ppm: u32,
+    /// What Ethernet allows, [`PHY_CLOCK_TOLERANCE_PPM`]
+    pub allowed_ppm: u32,
+}
+
+impl PpmReport {
+    /// Headroom left by the crystal, negative if it's out of tolerance
+    pub const fn margin_ppm(&self) -> i32 {
+        self.allowed_ppm as i32 - self.crystal_ppm as i32
+    }
+
+    pub const fn is_within_tolerance(&self) -> bool {
+        self.crystal_ppm <= self.allowed_ppm
+    }
+}
+
+/// A configuration from [`phy_clock_config`] with its error budget
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PhyClockConfig {
+    pub config: DeviceConfig,
+    pub ppm: PpmReport,
+}
+
+/// Why [`phy_clock_config`] produced no configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PhyPresetError {
+    /// The crystal can't produce the clock exactly, or no outputs were given
+    Plan(PlanError),
+    /// The planned configuration doesn't hold up against the limits of the
+    /// variant
+    Invalid(ValidationError),
+    /// The crystal alone exceeds the tolerance of the PHY
+    OutOfTolerance(PpmReport),
+}
+
+/// Complete configuration driving `outputs` with `clock` from a
+/// `crystal_hz` crystal of `crystal_ppm` total tolerance, checked with
+/// [`DeviceConfig::validate`]. Spread spectrum stays off since PHYs don't
+/// tolerate it, and outputs not in `outputs` are 3-stated.
+pub fn phy_clock_config(
+    crystal_hz: u32,
+    crystal_ppm: u32,
+    clock: PhyClock,
+    outputs: OutputMask,
+    variant: DeviceIdentification,
+) -> Result<PhyClockConfig, PhyPresetError> {
+    let ppm = PpmReport {
+        crystal_ppm,
+        allowed_ppm: PHY_CLOCK_TOLERANCE_PPM,
+    };
+
+    if !ppm.is_within_tolerance() {
+        return Err(PhyPresetError::OutOfTolerance(ppm));
+    }
+    if outputs.is_empty() {
+        return Err(PhyPresetError::Plan(PlanError::Unachievable));
+    }
+
+    let mut request = ConfigRequest::new()
+        .with_reference_hz(crystal_hz)
+        .with_variant(variant);
+    if outputs.contains(OutputMask::Y1) {
+        request = request.with_y1_hz(clock.hz());
+    }
+    if outputs.contains(OutputMask::Y2) {
+        request = request.with_y2_hz(clock.hz());
+    }
+    if outputs.contains(OutputMask::Y3) {
+        request = request.with_y3_hz(clock.hz());
+    }
+
+    let config = request.build().map_err(PhyPresetError::Plan)?;
+    config
+        .validate(variant, crystal_hz)
+        .map_err(PhyPresetError::Invalid)?;
+
+    Ok(PhyClockConfig { config, ppm })
+}
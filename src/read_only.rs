@@ -0,0 +1,203 @@
+//! A view of the driver that can only read, for monitoring and telemetry
+//! tasks. [`ReadOnlyCdcx913`] has no setters and gives back neither the
+//! driver it wraps nor its bus, which could write just as well, so whatever
+//! holds one can't change the clock tree:
+//!
+//! ```ignore
+//! // Next to the task that owns the main driver, on the same shared bus
+//! let mut monitor = ReadOnlyCdcx913::new(I2cDevice::new(bus));
+//! monitor.set_reference_frequency_hz(Some(27_000_000));
+//! let tree = monitor.clock_tree(ControlInputState::S111).await?;
+//! ```
+
+use embedded_hal_async::i2c::I2c;
+
+use crate::{
+    CDCx913, Error,
+    config::{ChangeReport, ClockTree, DeviceConfig, EffectiveConfig, VcoFrequency},
+    control::{ControlInputState, ControlProfiles},
+    family::{Cdce913, Device},
+    output::OutputConfig,
+    registers::{
+        OutputStateDefinition, Pll1Register, Pll1RegisterIndex, RegisterOffset,
+        generic_configuration::{
+            CrystalLoadCapacitance, DeviceIdentification, EepromProgrammingStatus,
+            InputClockSelection,
+        },
+        pll1_configuration::{Fs1Selection, PllSettings},
+    },
+    timing::NoDelay,
+    u3,
+};
+
+/// Read-only [`CDCx913`]. Besides the reads it only keeps the reference
+/// frequency, which decides how frequencies are computed but never reaches
+/// the device.
+pub struct ReadOnlyCdcx913<I2C, D = Cdce913, DELAY = NoDelay> {
+    driver: CDCx913<I2C, D, DELAY>,
+}
+
+impl<I2C: I2c> ReadOnlyCdcx913<I2C> {
+    /// Reads a CDCE(L)913 at its default address, e.g. through its own device
+    /// on a shared bus
+    pub fn new(i2c: I2C) -> Self {
+        Self::from(CDCx913::new(i2c))
+    }
+}
+
+impl<I2C: I2c, D: Device> ReadOnlyCdcx913<I2C, D> {
+    /// Like [`Self::new`], for any member of the family
+    pub fn for_device(i2c: I2C, device: D) -> Self {
+        Self::from(CDCx913::for_device(i2c, device))
+    }
+}
+
+impl<I2C, D, DELAY> From<CDCx913<I2C, D, DELAY>> for ReadOnlyCdcx913<I2C, D, DELAY> {
+    /// Takes over a configured driver, keeping its address and reference
+    /// frequency
+    fn from(driver: CDCx913<I2C, D, DELAY>) -> Self {
+        Self { driver }
+    }
+}
+
+impl<I2C: I2c, D: Device, DELAY> ReadOnlyCdcx913<I2C, D, DELAY> {
+    /// See [`CDCx913::set_reference_frequency_hz`]
+    pub fn set_reference_frequency_hz(&mut self, reference_hz: Option<u32>) {
+        self.driver.set_reference_frequency_hz(reference_hz);
+    }
+
+    pub fn reference_frequency_hz(&self) -> Option<u32> {
+        self.driver.reference_frequency_hz()
+    }
+
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> crate::BusStats {
+        self.driver.stats()
+    }
+
+    /// See [`CDCx913::read_byte`]
+    pub async fn read_byte(&mut self, offset: RegisterOffset) -> Result<u8, Error<I2C::Error>> {
+        self.driver.read_byte(offset).await
+    }
+
+    /// See [`CDCx913::pll1_register`]
+    pub async fn pll1_register(
+        &mut self,
+        index: Pll1RegisterIndex,
+    ) -> Result<Pll1Register, Error<I2C::Error>> {
+        self.driver.pll1_register(index).await
+    }
+
+    /// See [`CDCx913::read_config`]
+    pub async fn read_config(&mut self) -> Result<DeviceConfig, Error<I2C::Error>> {
+        self.driver.read_config().await
+    }
+
+    /// See [`CDCx913::verify_config`]
+    pub async fn verify_config(
+        &mut self,
+        config: &DeviceConfig,
+    ) -> Result<ChangeReport, Error<I2C::Error>> {
+        self.driver.verify_config(config).await
+    }
+
+    /// See [`CDCx913::effective_config_for`]
+    pub async fn effective_config_for(
+        &mut self,
+        state: ControlInputState,
+    ) -> Result<EffectiveConfig, Error<I2C::Error>> {
+        self.driver.effective_config_for(state).await
+    }
+
+    /// See [`CDCx913::clock_tree`]
+    pub async fn clock_tree(
+        &mut self,
+        state: ControlInputState,
+    ) -> Result<ClockTree, Error<I2C::Error>> {
+        self.driver.clock_tree(state).await
+    }
+
+    /// See [`CDCx913::control_profiles`]
+    pub async fn control_profiles(&mut self) -> Result<ControlProfiles, Error<I2C::Error>> {
+        self.driver.control_profiles().await
+    }
+
+    /// See [`CDCx913::output_config`]
+    pub async fn output_config(&mut self, n: u8) -> Result<OutputConfig, Error<I2C::Error>> {
+        self.driver.output_config(n).await
+    }
+
+    /// See [`CDCx913::is_output_enabled`]
+    pub async fn is_output_enabled(
+        &mut self,
+        n: u8,
+        state: ControlInputState,
+    ) -> Result<(bool, OutputStateDefinition), Error<I2C::Error>> {
+        self.driver.is_output_enabled(n, state).await
+    }
+
+    /// See [`CDCx913::pll_settings`]
+    pub async fn pll_settings(
+        &mut self,
+        bank: Fs1Selection,
+    ) -> Result<PllSettings, Error<I2C::Error>> {
+        self.driver.pll_settings(bank).await
+    }
+
+    /// See [`CDCx913::vco_frequency`]
+    pub async fn vco_frequency(
+        &mut self,
+        bank: Fs1Selection,
+    ) -> Result<VcoFrequency, Error<I2C::Error>> {
+        self.driver.vco_frequency(bank).await
+    }
+
+    /// See [`CDCx913::y1_frequency`]
+    pub async fn y1_frequency(
+        &mut self,
+        state: ControlInputState,
+    ) -> Result<Option<u32>, Error<I2C::Error>> {
+        self.driver.y1_frequency(state).await
+    }
+
+    #[doc(alias = "e_el")]
+    pub async fn device_identification(
+        &mut self,
+    ) -> Result<DeviceIdentification, Error<I2C::Error>> {
+        self.driver.device_identification().await
+    }
+
+    #[doc(alias = "rid")]
+    pub async fn revision_number(&mut self) -> Result<u3, Error<I2C::Error>> {
+        self.driver.revision_number().await
+    }
+
+    #[doc(alias = "eepip")]
+    pub async fn eeprom_programming_status(
+        &mut self,
+    ) -> Result<EepromProgrammingStatus, Error<I2C::Error>> {
+        self.driver.eeprom_programming_status().await
+    }
+
+    #[doc(alias = "eelock")]
+    pub async fn eeprom_permanently_locked(&mut self) -> Result<bool, Error<I2C::Error>> {
+        self.driver.eeprom_permanently_locked().await
+    }
+
+    #[doc(alias = "pwdn")]
+    pub async fn power_down(&mut self) -> Result<bool, Error<I2C::Error>> {
+        self.driver.power_down().await
+    }
+
+    #[doc(alias = "inclk")]
+    pub async fn input_clock(&mut self) -> Result<InputClockSelection, Error<I2C::Error>> {
+        self.driver.input_clock().await
+    }
+
+    #[doc(alias = "xcsel")]
+    pub async fn crystal_load_capacitance(
+        &mut self,
+    ) -> Result<CrystalLoadCapacitance, Error<I2C::Error>> {
+        self.driver.crystal_load_capacitance().await
+    }
+}
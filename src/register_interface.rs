@@ -0,0 +1,74 @@
+//! Raw register access, see [`crate::CDCx913::registers`]
+
+use embedded_hal_async::i2c::I2c;
+
+use crate::{
+    CDCx913, Error,
+    family::Device,
+    registers::{RegisterOffset, TypedRegister},
+    timing::NoDelay,
+};
+
+/// Low-level view of a driver: typed access to single registers and byte
+/// access by offset, none of it subject to the checks the high-level methods
+/// do, such as [`crate::Sequencing::Strict`]. The driver still notices when
+/// SPICON hands the serial interface pins over to S1/S2.
+pub struct RegisterInterface<'a, I2C, D, DELAY = NoDelay> {
+    driver: &'a mut CDCx913<I2C, D, DELAY>,
+}
+
+impl<'a, I2C: I2c, D: Device, DELAY> RegisterInterface<'a, I2C, D, DELAY> {
+    pub(crate) fn new(driver: &'a mut CDCx913<I2C, D, DELAY>) -> Self {
+        Self { driver }
+    }
+
+    /// See [`CDCx913::read_typed`]
+    pub async fn read<R: TypedRegister>(&mut self) -> Result<R, Error<I2C::Error>> {
+        self.driver.read_typed().await
+    }
+
+    /// Writes the whole register. Register 0x00 is read-only, the device
+    /// ignores writes to it.
+    pub async fn write<R: TypedRegister>(&mut self, value: R) -> Result<(), Error<I2C::Error>> {
+        self.write_at(R::OFFSET, value).await
+    }
+
+    /// Read-modify-write of a single register, see [`CDCx913::modify_typed`]
+    pub async fn modify<R: TypedRegister, T>(
+        &mut self,
+        f: impl FnOnce(&mut R) -> T,
+    ) -> Result<T, Error<I2C::Error>> {
+        self.driver.modify_typed(f).await
+    }
+
+    /// Reads the register at `offset` as `R`, e.g. a `Pll1ConfigurationRegisterN`
+    /// in the block of another PLL
+    pub async fn read_at<R: From<u8>>(
+        &mut self,
+        offset: RegisterOffset,
+    ) -> Result<R, Error<I2C::Error>> {
+        Ok(self.driver.read_byte(offset).await?.into())
+    }
+
+    pub async fn write_at<R: Into<u8>>(
+        &mut self,
+        offset: RegisterOffset,
+        value: R,
+    ) -> Result<(), Error<I2C::Error>> {
+        self.driver.write_byte(offset, value.into()).await
+    }
+
+    /// See [`CDCx913::read_byte_unchecked`]
+    pub async fn read_byte_unchecked(&mut self, offset: u8) -> Result<u8, Error<I2C::Error>> {
+        self.driver.read_byte_unchecked(offset).await
+    }
+
+    /// See [`CDCx913::write_byte_unchecked`]
+    pub async fn write_byte_unchecked(
+        &mut self,
+        offset: u8,
+        value: u8,
+    ) -> Result<(), Error<I2C::Error>> {
+        self.driver.write_byte_unchecked(offset, value).await
+    }
+}
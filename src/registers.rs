@@ -1,6 +1,7 @@
 use arbitrary_int::u2;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, defmt::Format)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum OutputStateDefinition {
     DevicePowerDown = 0b00,
@@ -9,45 +10,233 @@ pub enum OutputStateDefinition {
     Enabled = 0b11,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, defmt::Format)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum OutputStateSelection {
     State0 = 0,
     State1 = 1,
 }
 
+/// A register field holds an encoding the datasheet marks as reserved. Carries
+/// the raw field value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ReservedEncoding(pub u8);
+
+/// Offset of a documented register: the generic configuration at 0x00-0x06
+/// and the configuration blocks of PLL1 to PLL4 at 0x10-0x4F. Taken by
+/// [`crate::CDCx913::read_byte`] and [`crate::CDCx913::write_byte`], so a
+/// mistyped offset fails to compile or is caught at run time instead of
+/// landing in a reserved register.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RegisterOffset(u8);
+
+impl RegisterOffset {
+    /// Generic configuration register `N`, checked at compile time
+    pub const fn generic<const N: u8>() -> Self {
+        const { assert!(N <= 0x6, "no such generic configuration register") };
+
+        Self(N)
+    }
+
+    /// Register `N` of the configuration block of PLL `PLL`, checked at
+    /// compile time. Whether the device has that PLL is checked when the
+    /// offset is used.
+    pub const fn pll<const PLL: u8, const N: u8>() -> Self {
+        const { assert!(PLL >= 1 && PLL <= 4, "no such PLL") };
+        const { assert!(N <= 0xF, "no such PLL configuration register") };
+
+        Self(0x10 * PLL + N)
+    }
+
+    /// `None` for offsets outside of the documented registers
+    pub const fn new(offset: u8) -> Option<Self> {
+        match offset {
+            0x00..=0x06 | 0x10..=0x4F => Some(Self(offset)),
+            _ => None,
+        }
+    }
+
+    /// Any offset, including reserved ones. The datasheet warns that writing
+    /// to those "may affect device function".
+    pub const fn new_unchecked(offset: u8) -> Self {
+        Self(offset)
+    }
+
+    pub const fn value(self) -> u8 {
+        self.0
+    }
+}
+
+impl From<RegisterOffset> for u8 {
+    fn from(offset: RegisterOffset) -> Self {
+        offset.0
+    }
+}
+
+impl OutputStateDefinition {
+    /// `true` for [`Self::Enabled`], `false` for the three ways of turning the
+    /// output off
+    pub const fn is_enabled(self) -> bool {
+        matches!(self, Self::Enabled)
+    }
+}
+
 impl From<u2> for OutputStateDefinition {
     fn from(value: u2) -> Self {
         match value.value() {
             0b00 => Self::DevicePowerDown,
             0b01 => Self::Disabled3State,
             0b10 => Self::DisabledLow,
-            0b11 => Self::Enabled,
-            _ => unreachable!(),
+            _ => Self::Enabled,
+        }
+    }
+}
+
+/// A register type tied to a fixed offset in the register window, see
+/// [`crate::CDCx913::registers`]. The PLL1 register types also describe the
+/// blocks of the other PLLs, but their offset refers to the PLL1 block.
+pub trait TypedRegister: From<u8> + Into<u8> + Copy {
+    const OFFSET: RegisterOffset;
+}
+
+macro_rules! typed_registers {
+    ($($register:ty => $offset:expr),* $(,)?) => {
+        $(
+            impl TypedRegister for $register {
+                const OFFSET: RegisterOffset = RegisterOffset::new_unchecked($offset);
+            }
+        )*
+    };
+}
+
+typed_registers! {
+    generic_configuration::GenericConfigurationRegister0 => 0x00,
+    generic_configuration::GenericConfigurationRegister1 => 0x01,
+    generic_configuration::GenericConfigurationRegister2 => 0x02,
+    generic_configuration::GenericConfigurationRegister3 => 0x03,
+    generic_configuration::GenericConfigurationRegister4 => 0x04,
+    generic_configuration::GenericConfigurationRegister5 => 0x05,
+    generic_configuration::GenericConfigurationRegister6 => 0x06,
+    pll1_configuration::Pll1ConfigurationRegister0 => 0x10,
+    pll1_configuration::Pll1ConfigurationRegister1 => 0x11,
+    pll1_configuration::Pll1ConfigurationRegister2 => 0x12,
+    pll1_configuration::Pll1ConfigurationRegister3 => 0x13,
+    pll1_configuration::Pll1ConfigurationRegister4 => 0x14,
+    pll1_configuration::Pll1ConfigurationRegister5 => 0x15,
+    pll1_configuration::Pll1ConfigurationRegister6 => 0x16,
+    pll1_configuration::Pll1ConfigurationRegister7 => 0x17,
+    pll1_configuration::Pll1ConfigurationRegister8 => 0x18,
+    pll1_configuration::Pll1ConfigurationRegister9 => 0x19,
+    pll1_configuration::Pll1ConfigurationRegisterA => 0x1A,
+    pll1_configuration::Pll1ConfigurationRegisterB => 0x1B,
+    pll1_configuration::Pll1ConfigurationRegisterC => 0x1C,
+    pll1_configuration::Pll1ConfigurationRegisterD => 0x1D,
+    pll1_configuration::Pll1ConfigurationRegisterE => 0x1E,
+    pll1_configuration::Pll1ConfigurationRegisterF => 0x1F,
+}
+
+macro_rules! pll1_registers {
+    ($($name:ident($register:ident) = $index:literal),* $(,)?) => {
+        /// Register of the PLL1 block by its index within the block, see
+        /// [`crate::CDCx913::pll1_register`]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+        #[repr(u8)]
+        pub enum Pll1RegisterIndex {
+            $($name = $index),*
+        }
+
+        impl Pll1RegisterIndex {
+            /// Every register of the block, in offset order
+            pub const ALL: [Self; 16] = [$(Self::$name),*];
+
+            pub const fn offset(self) -> RegisterOffset {
+                RegisterOffset::new_unchecked(0x10 + self as u8)
+            }
+        }
+
+        /// A PLL1 register decoded into its type, so tooling can go through
+        /// the block with [`Pll1RegisterIndex::ALL`] and still get at the
+        /// fields
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+        pub enum Pll1Register {
+            $($name(pll1_configuration::$register)),*
         }
+
+        impl Pll1Register {
+            pub fn decode(index: Pll1RegisterIndex, raw: u8) -> Self {
+                match index {
+                    $(Pll1RegisterIndex::$name => Self::$name(raw.into())),*
+                }
+            }
+
+            pub fn index(&self) -> Pll1RegisterIndex {
+                match self {
+                    $(Self::$name(_) => Pll1RegisterIndex::$name),*
+                }
+            }
+
+            pub fn raw(&self) -> u8 {
+                match *self {
+                    $(Self::$name(register) => register.into()),*
+                }
+            }
+        }
+    };
+}
+
+pll1_registers! {
+    Register0(Pll1ConfigurationRegister0) = 0x0,
+    Register1(Pll1ConfigurationRegister1) = 0x1,
+    Register2(Pll1ConfigurationRegister2) = 0x2,
+    Register3(Pll1ConfigurationRegister3) = 0x3,
+    Register4(Pll1ConfigurationRegister4) = 0x4,
+    Register5(Pll1ConfigurationRegister5) = 0x5,
+    Register6(Pll1ConfigurationRegister6) = 0x6,
+    Register7(Pll1ConfigurationRegister7) = 0x7,
+    Register8(Pll1ConfigurationRegister8) = 0x8,
+    Register9(Pll1ConfigurationRegister9) = 0x9,
+    RegisterA(Pll1ConfigurationRegisterA) = 0xA,
+    RegisterB(Pll1ConfigurationRegisterB) = 0xB,
+    RegisterC(Pll1ConfigurationRegisterC) = 0xC,
+    RegisterD(Pll1ConfigurationRegisterD) = 0xD,
+    RegisterE(Pll1ConfigurationRegisterE) = 0xE,
+    RegisterF(Pll1ConfigurationRegisterF) = 0xF,
+}
+
+impl From<Pll1Register> for u8 {
+    fn from(register: Pll1Register) -> Self {
+        register.raw()
     }
 }
 
 pub mod generic_configuration {
     use arbitrary_int::{u2, u3};
 
-    use crate::registers::{OutputStateDefinition, OutputStateSelection};
+    use crate::registers::{OutputStateDefinition, OutputStateSelection, ReservedEncoding};
 
-    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, defmt::Format)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    #[cfg_attr(feature = "defmt", derive(defmt::Format))]
     #[repr(u8)]
     pub enum DeviceIdentification {
         CDCEL913 = 0,
         CDCE913 = 1,
     }
 
-    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, defmt::Format)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    #[cfg_attr(feature = "defmt", derive(defmt::Format))]
     #[repr(u8)]
     pub enum EepromProgrammingStatus {
         Completed = 0,
         InProgress = 1,
     }
 
-    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, defmt::Format)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    #[cfg_attr(feature = "defmt", derive(defmt::Format))]
     #[repr(u8)]
     pub enum InputClockSelection {
         Xtal = 0b00,
@@ -55,14 +244,29 @@ pub mod generic_configuration {
         LvCmos = 0b10,
     }
 
-    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, defmt::Format)]
+    impl TryFrom<u2> for InputClockSelection {
+        type Error = ReservedEncoding;
+
+        fn try_from(value: u2) -> Result<Self, Self::Error> {
+            match value.value() {
+                0b00 => Ok(Self::Xtal),
+                0b01 => Ok(Self::Vcxo),
+                0b10 => Ok(Self::LvCmos),
+                raw => Err(ReservedEncoding(raw)),
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    #[cfg_attr(feature = "defmt", derive(defmt::Format))]
     #[repr(u8)]
     pub enum Y1ClockSource {
         InputClock = 0,
         Pll1Clock = 1,
     }
 
-    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, defmt::Format)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    #[cfg_attr(feature = "defmt", derive(defmt::Format))]
     #[repr(u8)]
     pub enum SerialInterfacePinMode {
         SerialProgrammingInterface = 0,
@@ -71,6 +275,7 @@ pub mod generic_configuration {
 
     bitfield::bitfield! {
         #[derive(Clone, Copy, PartialEq, Eq, derive_more::From, derive_more::Into)]
+        #[cfg_attr(feature = "defmt", derive(defmt::Format))]
         pub struct GenericConfigurationRegister0(u8);
         impl Debug;
         pub e_el, _: 7;
@@ -90,9 +295,10 @@ pub mod generic_configuration {
 
     bitfield::bitfield! {
         #[derive(Clone, Copy, PartialEq, Eq, derive_more::From, derive_more::Into)]
+        #[cfg_attr(feature = "defmt", derive(defmt::Format))]
         pub struct GenericConfigurationRegister1(u8);
         impl Debug;
-        pub _eepip, _: 6;
+        pub _eepip, set_eepip: 6;
         pub eelock, set_eelock: 5;
         pub pwdn, set_pwdn: 4;
         pub _inclk, set_inclk: 3, 2;
@@ -108,13 +314,9 @@ pub mod generic_configuration {
             }
         }
 
-        pub fn input_clock_selection(&self) -> InputClockSelection {
-            match self._inclk() {
-                0b00 => InputClockSelection::Xtal,
-                0b01 => InputClockSelection::Vcxo,
-                0b10 => InputClockSelection::LvCmos,
-                _ => InputClockSelection::Xtal, // Reserved maps to Xtal
-            }
+        /// Fails if INCLK holds the reserved encoding 0b11
+        pub fn input_clock_selection(&self) -> Result<InputClockSelection, ReservedEncoding> {
+            InputClockSelection::try_from(u2::new(self._inclk()))
         }
 
         pub fn set_input_clock_selection(&mut self, selection: InputClockSelection) {
@@ -124,6 +326,7 @@ pub mod generic_configuration {
 
     bitfield::bitfield! {
         #[derive(Clone, Copy, PartialEq, Eq, derive_more::From, derive_more::Into)]
+        #[cfg_attr(feature = "defmt", derive(defmt::Format))]
         pub struct GenericConfigurationRegister2(u8);
         impl Debug;
         pub _m1, set_m1: 7;
@@ -177,6 +380,7 @@ pub mod generic_configuration {
 
     bitfield::bitfield! {
         #[derive(Clone, Copy, PartialEq, Eq, derive_more::From, derive_more::Into)]
+        #[cfg_attr(feature = "defmt", derive(defmt::Format))]
         pub struct GenericConfigurationRegister3(u8);
         impl Debug;
         pub pdiv1_7_0, set_pdiv1_7_0: 7, 0;
@@ -192,6 +396,7 @@ pub mod generic_configuration {
 
     bitfield::bitfield! {
         #[derive(Clone, Copy, PartialEq, Eq, derive_more::From, derive_more::Into)]
+        #[cfg_attr(feature = "defmt", derive(defmt::Format))]
         pub struct GenericConfigurationRegister4(u8);
         impl Debug;
         pub y1_7, set_y1_7: 7;
@@ -205,7 +410,9 @@ pub mod generic_configuration {
     }
 
     impl GenericConfigurationRegister4 {
-        pub fn y1_state_selection(&self, index: u3) -> OutputStateSelection {
+        pub fn y1_state_selection(&self, index: impl Into<u3>) -> OutputStateSelection {
+            let index = index.into();
+
             // Based on Y1_x bits and state definitions in register 2
             // This returns which state (0 or 1) is selected
             // The actual interpretation depends on Y1_ST0 and Y1_ST1 from register 2
@@ -217,7 +424,13 @@ pub mod generic_configuration {
             }
         }
 
-        pub fn set_y1_state_selection(&mut self, index: u3, state: OutputStateSelection) {
+        pub fn set_y1_state_selection(
+            &mut self,
+            index: impl Into<u3>,
+            state: OutputStateSelection,
+        ) {
+            let index = index.into();
+
             let bit_value = state as u8;
             self.0 = (self.0 & !(1 << index.value())) | (bit_value << index.value());
         }
@@ -225,31 +438,75 @@ pub mod generic_configuration {
 
     bitfield::bitfield! {
         #[derive(Clone, Copy, PartialEq, Eq, derive_more::From, derive_more::Into)]
+        #[cfg_attr(feature = "defmt", derive(defmt::Format))]
         pub struct GenericConfigurationRegister5(u8);
         impl Debug;
         pub xcsel, set_xcsel: 7, 3;
         pub reserved, _: 2, 0;
     }
 
-    impl GenericConfigurationRegister5 {
-        pub fn crystal_load_capacitance_pf(&self) -> u8 {
-            match self.xcsel() {
-                x @ 0x00..0x14 => x,
-                _ => 20,
+    /// Crystal load capacitance, 0 to 20 pF in 1 pF steps. XCSEL encodes the
+    /// capacitance in pF directly, with every code above 20 also meaning
+    /// 20 pF, so a value of this type is always a valid code.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+    pub struct CrystalLoadCapacitance(u8);
+
+    impl CrystalLoadCapacitance {
+        pub const MAX_PF: u8 = 20;
+        /// Datasheet default
+        pub const DEFAULT: Self = Self(10);
+
+        /// `None` above [`Self::MAX_PF`]
+        pub const fn from_pf(pf: u8) -> Option<Self> {
+            if pf <= Self::MAX_PF {
+                Some(Self(pf))
+            } else {
+                None
             }
         }
 
-        pub fn set_crystal_load_capacitance_pf(&mut self, pf: u8) {
-            let value = match pf {
-                x @ 0x00..0x14 => x,
-                _ => 20,
-            };
-            self.set_xcsel(value);
+        /// Decodes a raw 5-bit XCSEL code, mapping the codes above 20 to
+        /// 20 pF like the device does
+        pub const fn from_xcsel(code: u8) -> Self {
+            let code = code & 0x1F;
+
+            if code <= Self::MAX_PF {
+                Self(code)
+            } else {
+                Self(Self::MAX_PF)
+            }
+        }
+
+        pub const fn pf(self) -> u8 {
+            self.0
+        }
+
+        /// The raw XCSEL code
+        pub const fn xcsel(self) -> u8 {
+            self.0
+        }
+    }
+
+    impl Default for CrystalLoadCapacitance {
+        fn default() -> Self {
+            Self::DEFAULT
+        }
+    }
+
+    impl GenericConfigurationRegister5 {
+        pub fn crystal_load_capacitance(&self) -> CrystalLoadCapacitance {
+            CrystalLoadCapacitance::from_xcsel(self.xcsel())
+        }
+
+        pub fn set_crystal_load_capacitance(&mut self, capacitance: CrystalLoadCapacitance) {
+            self.set_xcsel(capacitance.xcsel());
         }
     }
 
     bitfield::bitfield! {
         #[derive(Clone, Copy, PartialEq, Eq, derive_more::From, derive_more::Into)]
+        #[cfg_attr(feature = "defmt", derive(defmt::Format))]
         pub struct GenericConfigurationRegister6(u8);
         impl Debug;
         pub bcount, set_bcount: 7, 1;
@@ -260,8 +517,10 @@ pub mod generic_configuration {
 pub mod pll1_configuration {
     use arbitrary_int::{u2, u3};
 
-    use crate::registers::{OutputStateDefinition, OutputStateSelection};
+    use crate::registers::{OutputStateDefinition, OutputStateSelection, ReservedEncoding};
 
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    #[cfg_attr(feature = "defmt", derive(defmt::Format))]
     #[repr(u8)]
     pub enum SscModulationAmountDown {
         Off = 0b000,
@@ -284,12 +543,13 @@ pub mod pll1_configuration {
                 0b100 => Self::Minus1Percent,
                 0b101 => Self::Minus125Percent,
                 0b110 => Self::Minus15Percent,
-                0b111 => Self::Minus2Percent,
-                _ => unreachable!(),
+                _ => Self::Minus2Percent,
             }
         }
     }
 
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    #[cfg_attr(feature = "defmt", derive(defmt::Format))]
     #[repr(u8)]
     pub enum SscModulationAmountCenter {
         Off = 0b000,
@@ -312,30 +572,37 @@ pub mod pll1_configuration {
                 0b100 => Self::PlusMinus1Percent,
                 0b101 => Self::PlusMinus125Percent,
                 0b110 => Self::PlusMinus15Percent,
-                0b111 => Self::PlusMinus2Percent,
-                _ => unreachable!(),
+                _ => Self::PlusMinus2Percent,
             }
         }
     }
 
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    #[cfg_attr(feature = "defmt", derive(defmt::Format))]
     #[repr(u8)]
     pub enum Fs1Selection {
         Fvcxo0 = 0,
         Fvcxo1 = 1,
     }
 
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    #[cfg_attr(feature = "defmt", derive(defmt::Format))]
     #[repr(u8)]
     pub enum Pll1Multiplexer {
         Pll1 = 0,
         Pll1Bypass = 1,
     }
 
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    #[cfg_attr(feature = "defmt", derive(defmt::Format))]
     #[repr(u8)]
     pub enum OutputY2Multiplexer {
         Pdiv1 = 0,
         Pdiv2 = 1,
     }
 
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    #[cfg_attr(feature = "defmt", derive(defmt::Format))]
     #[repr(u8)]
     pub enum OutputY3Multiplexer {
         Pdiv1 = 0b00,
@@ -344,18 +611,58 @@ pub mod pll1_configuration {
         Reserved = 0b11,
     }
 
+    /// The encodings of [`OutputY3Multiplexer`] that select a divider, so the
+    /// reserved one can't be programmed by accident
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+    #[repr(u8)]
+    pub enum OutputY3Source {
+        Pdiv1 = 0b00,
+        Pdiv2 = 0b01,
+        Pdiv3 = 0b10,
+    }
+
+    impl TryFrom<u2> for OutputY3Source {
+        type Error = ReservedEncoding;
+
+        fn try_from(value: u2) -> Result<Self, Self::Error> {
+            match value.value() {
+                0b00 => Ok(Self::Pdiv1),
+                0b01 => Ok(Self::Pdiv2),
+                0b10 => Ok(Self::Pdiv3),
+                raw => Err(ReservedEncoding(raw)),
+            }
+        }
+    }
+
+    impl From<OutputY3Source> for OutputY3Multiplexer {
+        fn from(value: OutputY3Source) -> Self {
+            match value {
+                OutputY3Source::Pdiv1 => Self::Pdiv1,
+                OutputY3Source::Pdiv2 => Self::Pdiv2,
+                OutputY3Source::Pdiv3 => Self::Pdiv3,
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    #[cfg_attr(feature = "defmt", derive(defmt::Format))]
     #[repr(u8)]
     pub enum Y2Y3State {
         State0 = 0,
         State1 = 1,
     }
 
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    #[cfg_attr(feature = "defmt", derive(defmt::Format))]
     #[repr(u8)]
     pub enum SscDownCenterSelection {
         Down = 0,
         Center = 1,
     }
 
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+    #[cfg_attr(feature = "defmt", derive(defmt::Format))]
     #[repr(u8)]
     pub enum VcoRangeSelection {
         LessThan125MHz = 0b00,
@@ -370,14 +677,37 @@ pub mod pll1_configuration {
                 0b00 => Self::LessThan125MHz,
                 0b01 => Self::From125To150MHz,
                 0b10 => Self::From150To175MHz,
-                0b11 => Self::GreaterOrEqual175MHz,
-                _ => unreachable!(),
+                _ => Self::GreaterOrEqual175MHz,
             }
         }
     }
 
+    /// Unpacks the eight 3-bit SSC1_x codes from registers 0x10-0x12, indexed by
+    /// control input. Read as one big-endian 24-bit word, SSC1_x occupies bits
+    /// `3x + 2..=3x`, which is why some codes straddle register boundaries.
+    pub fn unpack_ssc1_selections(registers: [u8; 3]) -> [u3; 8] {
+        let word = u32::from_be_bytes([0, registers[0], registers[1], registers[2]]);
+
+        core::array::from_fn(|index| u3::extract_u32(word, 3 * index))
+    }
+
+    /// Inverse of [`unpack_ssc1_selections`]
+    pub fn pack_ssc1_selections(selections: [u3; 8]) -> [u8; 3] {
+        let word = selections
+            .iter()
+            .enumerate()
+            .fold(0u32, |word, (index, code)| {
+                word | ((code.value() as u32) << (3 * index))
+            });
+
+        let [_, r0, r1, r2] = word.to_be_bytes();
+
+        [r0, r1, r2]
+    }
+
     bitfield::bitfield! {
         #[derive(Clone, Copy, PartialEq, Eq, derive_more::From, derive_more::Into)]
+        #[cfg_attr(feature = "defmt", derive(defmt::Format))]
         pub struct Pll1ConfigurationRegister0(u8);
         impl Debug;
         pub ssc1_7, set_ssc1_7: 7, 5;
@@ -387,6 +717,7 @@ pub mod pll1_configuration {
 
     bitfield::bitfield! {
         #[derive(Clone, Copy, PartialEq, Eq, derive_more::From, derive_more::Into)]
+        #[cfg_attr(feature = "defmt", derive(defmt::Format))]
         pub struct Pll1ConfigurationRegister1(u8);
         impl Debug;
         pub ssc1_5, set_ssc1_5: 7;
@@ -397,15 +728,17 @@ pub mod pll1_configuration {
 
     bitfield::bitfield! {
         #[derive(Clone, Copy, PartialEq, Eq, derive_more::From, derive_more::Into)]
+        #[cfg_attr(feature = "defmt", derive(defmt::Format))]
         pub struct Pll1ConfigurationRegister2(u8);
         impl Debug;
         pub ssc1_2, set_ssc1_2: 7, 6;
-        pub ssc1_1, set_ssc1_1: 6, 3;
+        pub ssc1_1, set_ssc1_1: 5, 3;
         pub ssc1_0, set_ssc1_0: 2, 0;
     }
 
     bitfield::bitfield! {
         #[derive(Clone, Copy, PartialEq, Eq, derive_more::From, derive_more::Into)]
+        #[cfg_attr(feature = "defmt", derive(defmt::Format))]
         pub struct Pll1ConfigurationRegister3(u8);
         impl Debug;
         pub fs1_7, set_fs1_7: 7;
@@ -419,7 +752,9 @@ pub mod pll1_configuration {
     }
 
     impl Pll1ConfigurationRegister3 {
-        pub fn fs1_selection(&self, index: u3) -> Fs1Selection {
+        pub fn fs1_selection(&self, index: impl Into<u3>) -> Fs1Selection {
+            let index = index.into();
+
             // Based on Y1_x bits and state definitions in register 2
             // This returns which state (0 or 1) is selected
             // The actual interpretation depends on Y1_ST0 and Y1_ST1 from register 2
@@ -431,7 +766,9 @@ pub mod pll1_configuration {
             }
         }
 
-        pub fn set_fs1_selection(&mut self, index: u3, state: Fs1Selection) {
+        pub fn set_fs1_selection(&mut self, index: impl Into<u3>, state: Fs1Selection) {
+            let index = index.into();
+
             let bit_value = state as u8;
             self.0 = (self.0 & !(1 << index.value())) | (bit_value << index.value());
         }
@@ -439,6 +776,7 @@ pub mod pll1_configuration {
 
     bitfield::bitfield! {
         #[derive(Clone, Copy, PartialEq, Eq, derive_more::From, derive_more::Into)]
+        #[cfg_attr(feature = "defmt", derive(defmt::Format))]
         pub struct Pll1ConfigurationRegister4(u8);
         impl Debug;
         pub _mux1, set_mux1: 7;
@@ -478,8 +816,7 @@ pub mod pll1_configuration {
                 0b00 => OutputY3Multiplexer::Pdiv1,
                 0b01 => OutputY3Multiplexer::Pdiv2,
                 0b10 => OutputY3Multiplexer::Pdiv3,
-                0b11 => OutputY3Multiplexer::Reserved,
-                _ => unreachable!(),
+                _ => OutputY3Multiplexer::Reserved,
             }
         }
 
@@ -487,6 +824,14 @@ pub mod pll1_configuration {
             self.set_m3(mux as u8);
         }
 
+        pub fn output_y3_source(&self) -> Result<OutputY3Source, ReservedEncoding> {
+            OutputY3Source::try_from(u2::new(self._m3()))
+        }
+
+        pub fn set_output_y3_source(&mut self, source: OutputY3Source) {
+            self.set_m3(source as u8);
+        }
+
         pub fn y2y3_state1_definition(&self) -> OutputStateDefinition {
             OutputStateDefinition::from(u2::new(self.y2y3_st1()))
         }
@@ -506,6 +851,7 @@ pub mod pll1_configuration {
 
     bitfield::bitfield! {
         #[derive(Clone, Copy, PartialEq, Eq, derive_more::From, derive_more::Into)]
+        #[cfg_attr(feature = "defmt", derive(defmt::Format))]
         pub struct Pll1ConfigurationRegister5(u8);
         impl Debug;
         pub y2y3_7, set_y2y3_7: 7;
@@ -519,7 +865,9 @@ pub mod pll1_configuration {
     }
 
     impl Pll1ConfigurationRegister5 {
-        pub fn y2y3_state_selection(&self, index: u3) -> OutputStateSelection {
+        pub fn y2y3_state_selection(&self, index: impl Into<u3>) -> OutputStateSelection {
+            let index = index.into();
+
             // Based on Y2Y3_x bits and state definitions in register 4
             // This returns which state (0 or 1) is selected
             // The actual interpretation depends on Y2Y3_ST0 and Y2Y3_ST1 from register 4
@@ -531,7 +879,13 @@ pub mod pll1_configuration {
             }
         }
 
-        pub fn set_y2y3_state_selection(&mut self, index: u3, state: OutputStateSelection) {
+        pub fn set_y2y3_state_selection(
+            &mut self,
+            index: impl Into<u3>,
+            state: OutputStateSelection,
+        ) {
+            let index = index.into();
+
             let bit_value = state as u8;
             self.0 = (self.0 & !(1 << index.value())) | (bit_value << index.value());
         }
@@ -539,6 +893,7 @@ pub mod pll1_configuration {
 
     bitfield::bitfield! {
         #[derive(Clone, Copy, PartialEq, Eq, derive_more::From, derive_more::Into)]
+        #[cfg_attr(feature = "defmt", derive(defmt::Format))]
         pub struct Pll1ConfigurationRegister6(u8);
         impl Debug;
         pub _ssc1dc, set_ssc1dc: 7;
@@ -561,6 +916,7 @@ pub mod pll1_configuration {
 
     bitfield::bitfield! {
         #[derive(Clone, Copy, PartialEq, Eq, derive_more::From, derive_more::Into)]
+        #[cfg_attr(feature = "defmt", derive(defmt::Format))]
         pub struct Pll1ConfigurationRegister7(u8);
         impl Debug;
         pub reserved, _: 7;
@@ -569,6 +925,7 @@ pub mod pll1_configuration {
 
     bitfield::bitfield! {
         #[derive(Clone, Copy, PartialEq, Eq, derive_more::From, derive_more::Into)]
+        #[cfg_attr(feature = "defmt", derive(defmt::Format))]
         pub struct Pll1ConfigurationRegister8(u8);
         impl Debug;
         pub pll1_0n_11_4, set_pll1_0n_11_4: 7, 0;
@@ -576,6 +933,7 @@ pub mod pll1_configuration {
 
     bitfield::bitfield! {
         #[derive(Clone, Copy, PartialEq, Eq, derive_more::From, derive_more::Into)]
+        #[cfg_attr(feature = "defmt", derive(defmt::Format))]
         pub struct Pll1ConfigurationRegister9(u8);
         impl Debug;
         pub pll1_0n_3_0, set_pll1_0n_3_0: 7, 4;
@@ -584,6 +942,7 @@ pub mod pll1_configuration {
 
     bitfield::bitfield! {
         #[derive(Clone, Copy, PartialEq, Eq, derive_more::From, derive_more::Into)]
+        #[cfg_attr(feature = "defmt", derive(defmt::Format))]
         pub struct Pll1ConfigurationRegisterA(u8);
         impl Debug;
         pub pll1_0r_4_0, set_pll1_0r_4_0: 7, 3;
@@ -592,6 +951,7 @@ pub mod pll1_configuration {
 
     bitfield::bitfield! {
         #[derive(Clone, Copy, PartialEq, Eq, derive_more::From, derive_more::Into)]
+        #[cfg_attr(feature = "defmt", derive(defmt::Format))]
         pub struct Pll1ConfigurationRegisterB(u8);
         impl Debug;
         pub pll1_0q_2_0, set_pll1_0q_2_0: 7, 5;
@@ -611,6 +971,7 @@ pub mod pll1_configuration {
 
     bitfield::bitfield! {
         #[derive(Clone, Copy, PartialEq, Eq, derive_more::From, derive_more::Into)]
+        #[cfg_attr(feature = "defmt", derive(defmt::Format))]
         pub struct Pll1ConfigurationRegisterC(u8);
         impl Debug;
         pub pll1_1n_11_4, set_pll1_1n_11_4: 7, 0;
@@ -618,6 +979,7 @@ pub mod pll1_configuration {
 
     bitfield::bitfield! {
         #[derive(Clone, Copy, PartialEq, Eq, derive_more::From, derive_more::Into)]
+        #[cfg_attr(feature = "defmt", derive(defmt::Format))]
         pub struct Pll1ConfigurationRegisterD(u8);
         impl Debug;
         pub pll1_1n_3_0, set_pll1_1n_3_0: 7, 4;
@@ -626,6 +988,7 @@ pub mod pll1_configuration {
 
     bitfield::bitfield! {
         #[derive(Clone, Copy, PartialEq, Eq, derive_more::From, derive_more::Into)]
+        #[cfg_attr(feature = "defmt", derive(defmt::Format))]
         pub struct Pll1ConfigurationRegisterE(u8);
         impl Debug;
         pub pll1_1r_4_0, set_pll1_1r_4_0: 7, 3;
@@ -634,6 +997,7 @@ pub mod pll1_configuration {
 
     bitfield::bitfield! {
         #[derive(Clone, Copy, PartialEq, Eq, derive_more::From, derive_more::Into)]
+        #[cfg_attr(feature = "defmt", derive(defmt::Format))]
         pub struct Pll1ConfigurationRegisterF(u8);
         impl Debug;
         pub pll1_1q_2_0, set_pll1_1q_2_0: 7, 5;
@@ -641,8 +1005,19 @@ pub mod pll1_configuration {
         pub _vco1_1_range, set_vco1_1_range: 1, 0;
     }
 
+    impl Pll1ConfigurationRegisterF {
+        pub fn vco1_1_range_selection(&self) -> VcoRangeSelection {
+            u2::new(self._vco1_1_range()).into()
+        }
+
+        pub fn set_vco1_1_range_selection(&mut self, range: VcoRangeSelection) {
+            self.set_vco1_1_range(range as u8);
+        }
+    }
+
     bitfield::bitfield! {
         #[derive(Clone, Copy, PartialEq, Eq, derive_more::From, derive_more::Into)]
+        #[cfg_attr(feature = "defmt", derive(defmt::Format))]
         pub struct PllSettings(u32);
         impl Debug;
         u16;
@@ -662,5 +1037,23 @@ pub mod pll1_configuration {
         pub fn set_vco_range_selection(&mut self, range: VcoRangeSelection) {
             self.set_vcox_y_range(range as u8);
         }
+
+        /// The reference divider M, which isn't stored on the device but
+        /// follows from the datasheet's `N' = N * 2^P = M * Q + R`. Returns
+        /// `None` for settings that don't describe a valid divider.
+        pub fn m(&self) -> Option<u32> {
+            let n_prime = (self.pllx_yn() as u32) << self.pllx_yp();
+            let remainder = n_prime.checked_sub(self.pllx_yr() as u32)?;
+            let m = remainder.checked_div(self.pllx_yq() as u32)?;
+
+            (m != 0).then_some(m)
+        }
+
+        /// fVCO = fIN * N / M
+        pub fn vco_frequency_hz(&self, input_hz: u32) -> Option<u32> {
+            let m = self.m()? as u64;
+
+            u32::try_from(input_hz as u64 * self.pllx_yn() as u64 / m).ok()
+        }
     }
 }
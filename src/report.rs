@@ -0,0 +1,491 @@
+//! Register-level view of a configuration: which fields make up each register
+//! of the window and a plain-text register map built from them. Everything
+//! here writes into a [`core::fmt::Write`], so it works without a heap, e.g.
+//! for a `clk dump` command on a debug UART.
+
+use arbitrary_int::u2;
+use core::fmt::{self, Write};
+
+use crate::{
+    config::DeviceConfig,
+    registers::{
+        OutputStateDefinition, OutputStateSelection, ReservedEncoding,
+        generic_configuration::{
+            DeviceIdentification, EepromProgrammingStatus, InputClockSelection,
+            SerialInterfacePinMode, Y1ClockSource,
+        },
+        pll1_configuration::{
+            Fs1Selection, OutputY2Multiplexer, OutputY3Multiplexer, Pll1Multiplexer,
+            SscDownCenterSelection, VcoRangeSelection,
+        },
+    },
+};
+
+/// A field of a register, named as in the datasheet. Fields that are split
+/// across registers carry the bit range of the whole value in their name, e.g.
+/// `PDIV1[9:8]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Field {
+    pub name: &'static str,
+    /// Most significant bit of the field within the register
+    pub msb: u8,
+    /// Least significant bit of the field within the register
+    pub lsb: u8,
+    pub kind: FieldKind,
+}
+
+/// How the value of a [`Field`] is interpreted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum FieldKind {
+    /// A number, or part of one for fields split across registers
+    Number,
+    /// A single bit without further meaning
+    Flag,
+    DeviceIdentification,
+    EepromProgrammingStatus,
+    InputClock,
+    Y1ClockSource,
+    SerialInterfacePinMode,
+    OutputState,
+    StateSelection,
+    Fs1,
+    Pll1Multiplexer,
+    Y2Multiplexer,
+    Y3Multiplexer,
+    SscDownCenter,
+    VcoRange,
+}
+
+/// The value of a [`Field`], decoded according to its [`FieldKind`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum FieldValue {
+    Number(u8),
+    Flag(bool),
+    DeviceIdentification(DeviceIdentification),
+    EepromProgrammingStatus(EepromProgrammingStatus),
+    InputClock(Result<InputClockSelection, ReservedEncoding>),
+    Y1ClockSource(Y1ClockSource),
+    SerialInterfacePinMode(SerialInterfacePinMode),
+    OutputState(OutputStateDefinition),
+    StateSelection(OutputStateSelection),
+    Fs1(Fs1Selection),
+    Pll1Multiplexer(Pll1Multiplexer),
+    Y2Multiplexer(OutputY2Multiplexer),
+    Y3Multiplexer(OutputY3Multiplexer),
+    SscDownCenter(SscDownCenterSelection),
+    VcoRange(VcoRangeSelection),
+}
+
+impl fmt::Display for FieldValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(value) => write!(f, "{value}"),
+            Self::Flag(value) => write!(f, "{}", *value as u8),
+            Self::DeviceIdentification(value) => write!(f, "{value:?}"),
+            Self::EepromProgrammingStatus(value) => write!(f, "{value:?}"),
+            Self::InputClock(Ok(value)) => write!(f, "{value:?}"),
+            Self::InputClock(Err(ReservedEncoding(raw))) => write!(f, "reserved({raw})"),
+            Self::Y1ClockSource(value) => write!(f, "{value:?}"),
+            Self::SerialInterfacePinMode(value) => write!(f, "{value:?}"),
+            Self::OutputState(value) => write!(f, "{value:?}"),
+            Self::StateSelection(value) => write!(f, "{value:?}"),
+            Self::Fs1(value) => write!(f, "{value:?}"),
+            Self::Pll1Multiplexer(value) => write!(f, "{value:?}"),
+            Self::Y2Multiplexer(value) => write!(f, "{value:?}"),
+            Self::Y3Multiplexer(value) => write!(f, "{value:?}"),
+            Self::SscDownCenter(value) => write!(f, "{value:?}"),
+            Self::VcoRange(value) => write!(f, "{value:?}"),
+        }
+    }
+}
+
+impl Field {
+    const fn new(name: &'static str, msb: u8, lsb: u8) -> Self {
+        Self {
+            name,
+            msb,
+            lsb,
+            kind: FieldKind::Number,
+        }
+    }
+
+    const fn bit(name: &'static str, bit: u8) -> Self {
+        Self {
+            kind: FieldKind::Flag,
+            ..Self::new(name, bit, bit)
+        }
+    }
+
+    const fn with_kind(self, kind: FieldKind) -> Self {
+        Self { kind, ..self }
+    }
+
+    pub const fn width(&self) -> u8 {
+        self.msb - self.lsb + 1
+    }
+
+    /// The bits of the register the field occupies
+    pub const fn mask(&self) -> u8 {
+        (((1u16 << self.width()) - 1) << self.lsb) as u8
+    }
+
+    /// Extracts the field from a raw register value
+    pub const fn value(&self, byte: u8) -> u8 {
+        ((byte as u16 >> self.lsb) & ((1 << self.width()) - 1)) as u8
+    }
+
+    /// Extracts the field from a raw register value and interprets it
+    pub fn decode(&self, byte: u8) -> FieldValue {
+        let raw = self.value(byte);
+        let bit = raw != 0;
+        // Two-bit kinds only ever come from two-bit fields
+        let two_bits = u2::extract_u8(raw, 0);
+
+        match self.kind {
+            FieldKind::Number => FieldValue::Number(raw),
+            FieldKind::Flag => FieldValue::Flag(bit),
+            FieldKind::DeviceIdentification => FieldValue::DeviceIdentification(if bit {
+                DeviceIdentification::CDCE913
+            } else {
+                DeviceIdentification::CDCEL913
+            }),
+            FieldKind::EepromProgrammingStatus => FieldValue::EepromProgrammingStatus(if bit {
+                EepromProgrammingStatus::InProgress
+            } else {
+                EepromProgrammingStatus::Completed
+            }),
+            FieldKind::InputClock => {
+                FieldValue::InputClock(InputClockSelection::try_from(two_bits))
+            }
+            FieldKind::Y1ClockSource => FieldValue::Y1ClockSource(if bit {
+                Y1ClockSource::Pll1Clock
+            } else {
+                Y1ClockSource::InputClock
+            }),
+            FieldKind::SerialInterfacePinMode => FieldValue::SerialInterfacePinMode(if bit {
+                SerialInterfacePinMode::ControlS1S2
+            } else {
+                SerialInterfacePinMode::SerialProgrammingInterface
+            }),
+            FieldKind::OutputState => FieldValue::OutputState(two_bits.into()),
+            FieldKind::StateSelection => FieldValue::StateSelection(if bit {
+                OutputStateSelection::State1
+            } else {
+                OutputStateSelection::State0
+            }),
+            FieldKind::Fs1 => FieldValue::Fs1(if bit {
+                Fs1Selection::Fvcxo1
+            } else {
+                Fs1Selection::Fvcxo0
+            }),
+            FieldKind::Pll1Multiplexer => FieldValue::Pll1Multiplexer(if bit {
+                Pll1Multiplexer::Pll1Bypass
+            } else {
+                Pll1Multiplexer::Pll1
+            }),
+            FieldKind::Y2Multiplexer => FieldValue::Y2Multiplexer(if bit {
+                OutputY2Multiplexer::Pdiv2
+            } else {
+                OutputY2Multiplexer::Pdiv1
+            }),
+            FieldKind::Y3Multiplexer => FieldValue::Y3Multiplexer(match two_bits.value() {
+                0b00 => OutputY3Multiplexer::Pdiv1,
+                0b01 => OutputY3Multiplexer::Pdiv2,
+                0b10 => OutputY3Multiplexer::Pdiv3,
+                _ => OutputY3Multiplexer::Reserved,
+            }),
+            FieldKind::SscDownCenter => FieldValue::SscDownCenter(if bit {
+                SscDownCenterSelection::Center
+            } else {
+                SscDownCenterSelection::Down
+            }),
+            FieldKind::VcoRange => FieldValue::VcoRange(two_bits.into()),
+        }
+    }
+}
+
+/// The fields of one register, see [`decode_register`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DecodedFields {
+    offset: u8,
+    raw: u8,
+}
+
+impl DecodedFields {
+    pub fn offset(&self) -> u8 {
+        self.offset
+    }
+
+    pub fn raw(&self) -> u8 {
+        self.raw
+    }
+
+    /// Whether the offset holds no fields, i.e. is reserved or outside of the
+    /// register window
+    pub fn is_reserved(&self) -> bool {
+        register_fields(self.offset).is_empty()
+    }
+
+    /// Each field with its decoded value, most significant first
+    pub fn iter(&self) -> impl Iterator<Item = (&'static Field, FieldValue)> + '_ {
+        register_fields(self.offset)
+            .iter()
+            .map(|field| (field, field.decode(self.raw)))
+    }
+}
+
+/// `NAME=value` pairs separated by spaces, or `reserved`
+impl fmt::Display for DecodedFields {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_reserved() {
+            return f.write_str("reserved");
+        }
+
+        for (index, (field, value)) in self.iter().enumerate() {
+            if index > 0 {
+                f.write_char(' ')?;
+            }
+            write!(f, "{}={value}", field.name)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Maps a raw byte, e.g. captured with a logic analyzer, to the named fields
+/// of the register at `offset` and their values. PLL2 and later blocks of the
+/// larger devices share the PLL1 layout, so pass their offset modulo 0x10 plus
+/// 0x10.
+pub fn decode_register(offset: u8, raw: u8) -> DecodedFields {
+    DecodedFields { offset, raw }
+}
+
+const GENERIC_0: &[Field] = &[
+    Field::bit("E_EL", 7).with_kind(FieldKind::DeviceIdentification),
+    Field::new("RID", 6, 4),
+    Field::new("VID", 3, 0),
+];
+const GENERIC_1: &[Field] = &[
+    Field::new("RESERVED", 7, 7),
+    Field::bit("EEPIP", 6).with_kind(FieldKind::EepromProgrammingStatus),
+    Field::bit("EELOCK", 5),
+    Field::bit("PWDN", 4),
+    Field::new("INCLK", 3, 2).with_kind(FieldKind::InputClock),
+    Field::new("TARGET_ADR", 1, 0),
+];
+const GENERIC_2: &[Field] = &[
+    Field::bit("M1", 7).with_kind(FieldKind::Y1ClockSource),
+    Field::bit("SPICON", 6).with_kind(FieldKind::SerialInterfacePinMode),
+    Field::new("Y1_ST1", 5, 4).with_kind(FieldKind::OutputState),
+    Field::new("Y1_ST0", 3, 2).with_kind(FieldKind::OutputState),
+    Field::new("PDIV1[9:8]", 1, 0),
+];
+const GENERIC_3: &[Field] = &[Field::new("PDIV1[7:0]", 7, 0)];
+const GENERIC_4: &[Field] = &[
+    Field::bit("Y1_7", 7).with_kind(FieldKind::StateSelection),
+    Field::bit("Y1_6", 6).with_kind(FieldKind::StateSelection),
+    Field::bit("Y1_5", 5).with_kind(FieldKind::StateSelection),
+    Field::bit("Y1_4", 4).with_kind(FieldKind::StateSelection),
+    Field::bit("Y1_3", 3).with_kind(FieldKind::StateSelection),
+    Field::bit("Y1_2", 2).with_kind(FieldKind::StateSelection),
+    Field::bit("Y1_1", 1).with_kind(FieldKind::StateSelection),
+    Field::bit("Y1_0", 0).with_kind(FieldKind::StateSelection),
+];
+const GENERIC_5: &[Field] = &[Field::new("XCSEL", 7, 3), Field::new("RESERVED", 2, 0)];
+const GENERIC_6: &[Field] = &[Field::new("BCOUNT", 7, 1), Field::bit("EEWRITE", 0)];
+const PLL1_0: &[Field] = &[
+    Field::new("SSC1_7", 7, 5),
+    Field::new("SSC1_6", 4, 2),
+    Field::new("SSC1_5[2:1]", 1, 0),
+];
+const PLL1_1: &[Field] = &[
+    Field::new("SSC1_5[0]", 7, 7),
+    Field::new("SSC1_4", 6, 4),
+    Field::new("SSC1_3", 3, 1),
+    Field::new("SSC1_2[2]", 0, 0),
+];
+const PLL1_2: &[Field] = &[
+    Field::new("SSC1_2[1:0]", 7, 6),
+    Field::new("SSC1_1", 5, 3),
+    Field::new("SSC1_0", 2, 0),
+];
+const PLL1_3: &[Field] = &[
+    Field::bit("FS1_7", 7).with_kind(FieldKind::Fs1),
+    Field::bit("FS1_6", 6).with_kind(FieldKind::Fs1),
+    Field::bit("FS1_5", 5).with_kind(FieldKind::Fs1),
+    Field::bit("FS1_4", 4).with_kind(FieldKind::Fs1),
+    Field::bit("FS1_3", 3).with_kind(FieldKind::Fs1),
+    Field::bit("FS1_2", 2).with_kind(FieldKind::Fs1),
+    Field::bit("FS1_1", 1).with_kind(FieldKind::Fs1),
+    Field::bit("FS1_0", 0).with_kind(FieldKind::Fs1),
+];
+const PLL1_4: &[Field] = &[
+    Field::bit("MUX1", 7).with_kind(FieldKind::Pll1Multiplexer),
+    Field::bit("M2", 6).with_kind(FieldKind::Y2Multiplexer),
+    Field::new("M3", 5, 4).with_kind(FieldKind::Y3Multiplexer),
+    Field::new("Y2Y3_ST1", 3, 2).with_kind(FieldKind::OutputState),
+    Field::new("Y2Y3_ST0", 1, 0).with_kind(FieldKind::OutputState),
+];
+const PLL1_5: &[Field] = &[
+    Field::bit("Y2Y3_7", 7).with_kind(FieldKind::StateSelection),
+    Field::bit("Y2Y3_6", 6).with_kind(FieldKind::StateSelection),
+    Field::bit("Y2Y3_5", 5).with_kind(FieldKind::StateSelection),
+    Field::bit("Y2Y3_4", 4).with_kind(FieldKind::StateSelection),
+    Field::bit("Y2Y3_3", 3).with_kind(FieldKind::StateSelection),
+    Field::bit("Y2Y3_2", 2).with_kind(FieldKind::StateSelection),
+    Field::bit("Y2Y3_1", 1).with_kind(FieldKind::StateSelection),
+    Field::bit("Y2Y3_0", 0).with_kind(FieldKind::StateSelection),
+];
+const PLL1_6: &[Field] = &[
+    Field::bit("SSC1DC", 7).with_kind(FieldKind::SscDownCenter),
+    Field::new("PDIV2", 6, 0),
+];
+const PLL1_7: &[Field] = &[Field::new("RESERVED", 7, 7), Field::new("PDIV3", 6, 0)];
+const PLL1_8: &[Field] = &[Field::new("PLL1_0N[11:4]", 7, 0)];
+const PLL1_9: &[Field] = &[
+    Field::new("PLL1_0N[3:0]", 7, 4),
+    Field::new("PLL1_0R[8:5]", 3, 0),
+];
+const PLL1_A: &[Field] = &[
+    Field::new("PLL1_0R[4:0]", 7, 3),
+    Field::new("PLL1_0Q[5:3]", 2, 0),
+];
+const PLL1_B: &[Field] = &[
+    Field::new("PLL1_0Q[2:0]", 7, 5),
+    Field::new("PLL1_0P", 4, 2),
+    Field::new("VCO1_0_RANGE", 1, 0).with_kind(FieldKind::VcoRange),
+];
+const PLL1_C: &[Field] = &[Field::new("PLL1_1N[11:4]", 7, 0)];
+const PLL1_D: &[Field] = &[
+    Field::new("PLL1_1N[3:0]", 7, 4),
+    Field::new("PLL1_1R[8:5]", 3, 0),
+];
+const PLL1_E: &[Field] = &[
+    Field::new("PLL1_1R[4:0]", 7, 3),
+    Field::new("PLL1_1Q[5:3]", 2, 0),
+];
+const PLL1_F: &[Field] = &[
+    Field::new("PLL1_1Q[2:0]", 7, 5),
+    Field::new("PLL1_1P", 4, 2),
+    Field::new("VCO1_1_RANGE", 1, 0).with_kind(FieldKind::VcoRange),
+];
+
+/// The fields of the register at `offset`, most significant first. Empty for
+/// the reserved offsets 0x07-0x0F and anything outside of the register window.
+pub const fn register_fields(offset: u8) -> &'static [Field] {
+    match offset {
+        0x00 => GENERIC_0,
+        0x01 => GENERIC_1,
+        0x02 => GENERIC_2,
+        0x03 => GENERIC_3,
+        0x04 => GENERIC_4,
+        0x05 => GENERIC_5,
+        0x06 => GENERIC_6,
+        0x10 => PLL1_0,
+        0x11 => PLL1_1,
+        0x12 => PLL1_2,
+        0x13 => PLL1_3,
+        0x14 => PLL1_4,
+        0x15 => PLL1_5,
+        0x16 => PLL1_6,
+        0x17 => PLL1_7,
+        0x18 => PLL1_8,
+        0x19 => PLL1_9,
+        0x1A => PLL1_A,
+        0x1B => PLL1_B,
+        0x1C => PLL1_C,
+        0x1D => PLL1_D,
+        0x1E => PLL1_E,
+        0x1F => PLL1_F,
+        _ => &[],
+    }
+}
+
+/// Writes the fields of `byte` as `NAME=value` pairs separated by spaces, or
+/// `reserved` if the offset holds no fields
+pub fn write_fields<W: Write>(out: &mut W, offset: u8, byte: u8) -> fmt::Result {
+    write!(out, "{}", decode_register(offset, byte))
+}
+
+/// Renders the whole register window as a table with one line per register:
+///
+/// ```text
+/// OFF  RAW   FIELDS
+/// 0x00 0x81  E_EL=1 RID=0 VID=1
+/// ```
+pub fn write_register_map<W: Write>(out: &mut W, config: &DeviceConfig) -> fmt::Result {
+    out.write_str("OFF  RAW   FIELDS\n")?;
+
+    for (offset, byte) in config.as_bytes().iter().enumerate() {
+        write!(out, "0x{offset:02X} 0x{byte:02X}  ")?;
+        write_fields(out, offset as u8, *byte)?;
+        out.write_char('\n')?;
+    }
+
+    Ok(())
+}
+
+/// Renders two configurations side by side, e.g. a failing board against a
+/// golden one, with one line per register. Registers that differ are marked
+/// with `*`, and each field that differs shows both values in brackets:
+///
+/// ```text
+///   OFF  EXP  ACT   FIELDS
+///   0x14 0x6D 0x6D  MUX1=Pll1 M2=Pdiv2 M3=Pdiv3 Y2Y3_ST1=Enabled Y2Y3_ST0=Disabled3State
+/// * 0x16 0x04 0x06  SSC1DC=Down [PDIV2=4->6]
+/// ```
+///
+/// Every bit is compared, including status bits such as EEPIP, which
+/// [`DeviceConfig::diff`] leaves out.
+pub fn write_register_diff<W: Write>(
+    out: &mut W,
+    expected: &DeviceConfig,
+    actual: &DeviceConfig,
+) -> fmt::Result {
+    out.write_str("  OFF  EXP  ACT   FIELDS\n")?;
+
+    for (offset, (&expected, &actual)) in expected
+        .as_bytes()
+        .iter()
+        .zip(actual.as_bytes())
+        .enumerate()
+    {
+        let marker = if expected == actual { ' ' } else { '*' };
+        write!(
+            out,
+            "{marker} 0x{offset:02X} 0x{expected:02X} 0x{actual:02X}  "
+        )?;
+        write_field_diff(out, offset as u8, expected, actual)?;
+        out.write_char('\n')?;
+    }
+
+    Ok(())
+}
+
+// Like `write_fields`, with `[NAME=expected->actual]` for fields that differ
+fn write_field_diff<W: Write>(out: &mut W, offset: u8, expected: u8, actual: u8) -> fmt::Result {
+    let fields = register_fields(offset);
+
+    if fields.is_empty() {
+        return out.write_str("reserved");
+    }
+
+    for (index, field) in fields.iter().enumerate() {
+        if index > 0 {
+            out.write_char(' ')?;
+        }
+
+        let value = field.decode(expected);
+        if field.value(expected) == field.value(actual) {
+            write!(out, "{}={value}", field.name)?;
+        } else {
+            write!(out, "[{}={value}->{}]", field.name, field.decode(actual))?;
+        }
+    }
+
+    Ok(())
+}
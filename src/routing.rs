@@ -0,0 +1,215 @@
+//! Declarative routing of the PLL1 block. Four multiplexers decide which clock
+//! reaches Y1..Y3, and they interact: M1 picks the source of PDIV1, MUX1 picks
+//! whether PDIV2, PDIV3 and (through M1) PDIV1 see PLL1 or the input clock,
+//! and M2 and M3 pick the divider behind Y2 and Y3. A [`Routing`] describes
+//! the path each output should take, and [`Routing::mux_settings`] works out
+//! the multiplexer settings, or why no setting gives those paths.
+//!
+//! ```ignore
+//! driver
+//!     .set_routing(Routing {
+//!         y1: Source::Input,
+//!         y2: Route::new(Source::Pll1, Divider::Pdiv2),
+//!         y3: Route::new(Source::Pll1, Divider::Pdiv3),
+//!     })
+//!     .await?;
+//! ```
+
+use crate::{
+    Register,
+    config::DeviceConfig,
+    registers::{
+        ReservedEncoding,
+        generic_configuration::{GenericConfigurationRegister2, Y1ClockSource},
+        pll1_configuration::{
+            OutputY2Multiplexer, OutputY3Source, Pll1ConfigurationRegister4, Pll1Multiplexer,
+        },
+    },
+};
+
+/// Clock feeding an output divider
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Source {
+    /// The input clock directly, through M1. Only PDIV1 has this path.
+    Input,
+    /// PLL1, through MUX1
+    Pll1,
+    /// The input clock around PLL1, through MUX1
+    Pll1Bypass,
+}
+
+/// Output divider of the PLL1 block
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Divider {
+    Pdiv1,
+    Pdiv2,
+    Pdiv3,
+}
+
+/// Path from a clock through a divider to an output
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Route {
+    pub source: Source,
+    pub divider: Divider,
+}
+
+impl Route {
+    pub const fn new(source: Source, divider: Divider) -> Self {
+        Self { source, divider }
+    }
+}
+
+/// Desired paths for Y1..Y3. Y1 always sits behind PDIV1, so only its source
+/// is given.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Routing {
+    pub y1: Source,
+    pub y2: Route,
+    pub y3: Route,
+}
+
+/// Why a [`Routing`] can't be set up
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum RoutingError {
+    /// Output Yn has no multiplexer input for the divider, i.e. Y2 from PDIV3
+    Unreachable { output: u8, divider: Divider },
+    /// The divider can't take the input clock directly, only through
+    /// [`Source::Pll1Bypass`]
+    InputUnavailable(Divider),
+    /// Outputs sharing the divider ask for different sources or values
+    DividerConflict(Divider),
+    /// Some outputs ask for PLL1 and others for its bypass, but there's only
+    /// one MUX1
+    Mux1Conflict,
+}
+
+impl core::fmt::Display for RoutingError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Unreachable { output, divider } => {
+                write!(f, "Y{output} can't be fed from {divider:?}")
+            }
+            Self::InputUnavailable(divider) => {
+                write!(f, "{divider:?} can't take the input clock directly")
+            }
+            Self::DividerConflict(divider) => {
+                write!(f, "outputs ask for different settings of {divider:?}")
+            }
+            Self::Mux1Conflict => f.write_str("outputs ask for both PLL1 and its bypass"),
+        }
+    }
+}
+
+/// Multiplexer settings implementing a [`Routing`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct MuxSettings {
+    pub m1: Y1ClockSource,
+    /// `None` if no output goes through MUX1, so it can stay as it is
+    pub mux1: Option<Pll1Multiplexer>,
+    pub m2: OutputY2Multiplexer,
+    pub m3: OutputY3Source,
+}
+
+impl Routing {
+    /// The routing `config` sets up. Fails if M3 holds the reserved encoding.
+    pub fn from_config(config: &DeviceConfig) -> Result<Self, ReservedEncoding> {
+        let reg2 = GenericConfigurationRegister2::from(
+            config.register(Register::GenericConfiguration as u8 + 2),
+        );
+        let reg4 = Pll1ConfigurationRegister4::from(
+            config.register(Register::Pll1Configuration as u8 + 4),
+        );
+
+        let mux1 = match reg4.pll1_multiplexer() {
+            Pll1Multiplexer::Pll1 => Source::Pll1,
+            Pll1Multiplexer::Pll1Bypass => Source::Pll1Bypass,
+        };
+        let y1 = match reg2.y1_clock_source() {
+            Y1ClockSource::InputClock => Source::Input,
+            Y1ClockSource::Pll1Clock => mux1,
+        };
+        let route = |divider| match divider {
+            Divider::Pdiv1 => Route::new(y1, divider),
+            _ => Route::new(mux1, divider),
+        };
+
+        Ok(Self {
+            y1,
+            y2: route(match reg4.output_y2_multiplexer() {
+                OutputY2Multiplexer::Pdiv1 => Divider::Pdiv1,
+                OutputY2Multiplexer::Pdiv2 => Divider::Pdiv2,
+            }),
+            y3: route(match reg4.output_y3_source()? {
+                OutputY3Source::Pdiv1 => Divider::Pdiv1,
+                OutputY3Source::Pdiv2 => Divider::Pdiv2,
+                OutputY3Source::Pdiv3 => Divider::Pdiv3,
+            }),
+        })
+    }
+
+    /// Works out the multiplexer settings giving every output its path, or
+    /// the first reason there are none
+    pub fn mux_settings(&self) -> Result<MuxSettings, RoutingError> {
+        let routes = [Route::new(self.y1, Divider::Pdiv1), self.y2, self.y3];
+
+        let m2 = match self.y2.divider {
+            Divider::Pdiv1 => OutputY2Multiplexer::Pdiv1,
+            Divider::Pdiv2 => OutputY2Multiplexer::Pdiv2,
+            Divider::Pdiv3 => {
+                return Err(RoutingError::Unreachable {
+                    output: 2,
+                    divider: Divider::Pdiv3,
+                });
+            }
+        };
+        let m3 = match self.y3.divider {
+            Divider::Pdiv1 => OutputY3Source::Pdiv1,
+            Divider::Pdiv2 => OutputY3Source::Pdiv2,
+            Divider::Pdiv3 => OutputY3Source::Pdiv3,
+        };
+
+        let mut sources: [Option<Source>; 3] = [None; 3];
+        for route in routes {
+            if route.source == Source::Input && route.divider != Divider::Pdiv1 {
+                return Err(RoutingError::InputUnavailable(route.divider));
+            }
+
+            match &mut sources[route.divider as usize] {
+                Some(source) if *source != route.source => {
+                    return Err(RoutingError::DividerConflict(route.divider));
+                }
+                slot => *slot = Some(route.source),
+            }
+        }
+
+        let mut mux1 = None;
+        for source in sources.into_iter().flatten() {
+            let wanted = match source {
+                Source::Input => continue,
+                Source::Pll1 => Pll1Multiplexer::Pll1,
+                Source::Pll1Bypass => Pll1Multiplexer::Pll1Bypass,
+            };
+
+            match mux1 {
+                Some(mux1) if mux1 != wanted => return Err(RoutingError::Mux1Conflict),
+                _ => mux1 = Some(wanted),
+            }
+        }
+
+        Ok(MuxSettings {
+            m1: match self.y1 {
+                Source::Input => Y1ClockSource::InputClock,
+                Source::Pll1 | Source::Pll1Bypass => Y1ClockSource::Pll1Clock,
+            },
+            mux1,
+            m2,
+            m3,
+        })
+    }
+}
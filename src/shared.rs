@@ -0,0 +1,46 @@
+//! A driver that can be used from several tasks at once, e.g. a clock manager
+//! and a debug console, without funneling every access through one task.
+
+use embassy_sync::{
+    blocking_mutex::raw::RawMutex,
+    mutex::{Mutex, MutexGuard},
+};
+use embedded_hal_async::i2c::I2c;
+
+use crate::{CDCx913, family::Cdce913, timing::NoDelay};
+
+/// [`CDCx913`] behind an async [`Mutex`]. `M` picks the kind of critical
+/// section, e.g. `CriticalSectionRawMutex` when the driver is shared with
+/// interrupt executors or `NoopRawMutex` within one executor.
+pub struct SharedCdcx913<M, I2C, D = Cdce913, DELAY = NoDelay>
+where
+    M: RawMutex,
+{
+    driver: Mutex<M, CDCx913<I2C, D, DELAY>>,
+}
+
+impl<M: RawMutex, I2C: I2c, D, DELAY> SharedCdcx913<M, I2C, D, DELAY> {
+    pub const fn new(driver: CDCx913<I2C, D, DELAY>) -> Self {
+        Self {
+            driver: Mutex::new(driver),
+        }
+    }
+
+    /// Waits for exclusive access to the driver. The guard dereferences to
+    /// [`CDCx913`], so the whole driver API is available while it is held.
+    pub async fn lock(&self) -> MutexGuard<'_, M, CDCx913<I2C, D, DELAY>> {
+        self.driver.lock().await
+    }
+
+    /// Runs `f` with exclusive access to the driver, e.g. to keep a
+    /// read-modify-write sequence spanning several calls atomic
+    pub async fn with<R>(&self, f: impl AsyncFnOnce(&mut CDCx913<I2C, D, DELAY>) -> R) -> R {
+        let mut driver = self.driver.lock().await;
+
+        f(&mut driver).await
+    }
+
+    pub fn into_inner(self) -> CDCx913<I2C, D, DELAY> {
+        self.driver.into_inner()
+    }
+}
@@ -0,0 +1,474 @@
+//! A simulated CDCx913 for host tests. It models the register window the way
+//! the device exposes it over I2C, including read-only bits, the EEPROM with
+//! EEPIP staying set while a write is in progress, EELOCK and the switch of
+//! the serial interface pins to control inputs. [`SimulatedCdce913::set_device`]
+//! turns it into a larger family member, answering on its address and with
+//! the PLL blocks that follow the window.
+//!
+//! The EEPROM can be backed by [`EepromStorage`] that outlives the simulated
+//! device, so a test can commit a configuration, drop the device and check
+//! what a new one powers up with:
+//!
+//! ```ignore
+//! let mut image = SimulatedCdce913::new().eeprom().to_bytes();
+//!
+//! {
+//!     let mut sim = SimulatedCdce913::with_storage(&mut image);
+//!     // ... configure and commit through the driver ...
+//! }
+//!
+//! let sim = SimulatedCdce913::with_storage(&mut image);
+//! ```
+
+use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource, Operation};
+use embedded_hal_async::i2c::{ErrorType, I2c};
+
+use crate::{
+    config::{DeviceConfig, REGISTER_WINDOW_LEN},
+    family::{Cdce913, Cdce949, Device},
+    i2c::CommandCode,
+    registers::generic_configuration::{
+        DeviceIdentification, GenericConfigurationRegister1, GenericConfigurationRegister2,
+        GenericConfigurationRegister6, SerialInterfacePinMode,
+    },
+};
+
+/// Register defaults of a factory-fresh CDCE913 as listed in the datasheet
+const FACTORY_DEFAULTS: [u8; REGISTER_WINDOW_LEN] = [
+    0x81, 0x01, 0xB4, 0x01, 0x02, 0x50, 0x40, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x6D, 0x02, 0x00, 0x00, 0x00, 0x40, 0x02, 0x08, 0x00, 0x40, 0x02, 0x08,
+];
+
+/// Register map of the largest family member, which every simulated device
+/// keeps room for
+const MAX_REGISTER_MAP_LEN: usize = Cdce949::REGISTER_MAP_LEN;
+
+/// Register reads for which EEPIP stays set after starting an EEPROM write,
+/// unless changed with [`SimulatedCdce913::set_eeprom_write_duration`]
+const DEFAULT_EEPROM_WRITE_DURATION: u32 = 3;
+
+/// Bits of each register the bus can change. Register 0x00 and EEPIP are
+/// read-only and 0x07-0x0F are reserved.
+const fn writable_mask(offset: u8) -> u8 {
+    match offset {
+        0x00 | 0x07..=0x0F => 0x00,
+        0x01 => 0b1011_1111,
+        _ => 0xFF,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SimError {
+    /// Nothing answered on the address, either because it doesn't match or
+    /// because the serial interface pins were switched to control inputs
+    Nack,
+    /// A read wasn't preceded by a write carrying the command code, or a
+    /// write didn't contain one
+    MissingCommandCode,
+    /// A block read didn't stop right after the byte count and the BCOUNT
+    /// registers, all of which the device expects to be read
+    BlockReadLength,
+}
+
+impl core::fmt::Display for SimError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Nack => f.write_str("no acknowledge from the simulated device"),
+            Self::MissingCommandCode => f.write_str("transfer without a command code"),
+            Self::BlockReadLength => f.write_str("block read not matching BCOUNT"),
+        }
+    }
+}
+
+impl core::error::Error for SimError {}
+
+impl embedded_hal::i2c::Error for SimError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Nack => ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address),
+            Self::MissingCommandCode | Self::BlockReadLength => ErrorKind::Other,
+        }
+    }
+}
+
+/// Where a [`SimulatedCdce913`] keeps its EEPROM contents beyond its own
+/// lifetime
+pub trait EepromStorage {
+    /// The stored EEPROM image, or `None` for a factory-fresh device
+    fn load(&mut self) -> Option<[u8; REGISTER_WINDOW_LEN]>;
+
+    /// Called with the new EEPROM image after every change to it
+    fn store(&mut self, image: &[u8; REGISTER_WINDOW_LEN]);
+}
+
+/// EEPROM that lives and dies with the simulated device
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct NoStorage;
+
+impl EepromStorage for NoStorage {
+    fn load(&mut self) -> Option<[u8; REGISTER_WINDOW_LEN]> {
+        None
+    }
+
+    fn store(&mut self, _image: &[u8; REGISTER_WINDOW_LEN]) {}
+}
+
+/// A byte array holding the image in register order, e.g. one a test saves
+/// to and loads from a file
+impl EepromStorage for [u8; REGISTER_WINDOW_LEN] {
+    fn load(&mut self) -> Option<[u8; REGISTER_WINDOW_LEN]> {
+        Some(*self)
+    }
+
+    fn store(&mut self, image: &[u8; REGISTER_WINDOW_LEN]) {
+        *self = *image;
+    }
+}
+
+impl<T: EepromStorage + ?Sized> EepromStorage for &mut T {
+    fn load(&mut self) -> Option<[u8; REGISTER_WINDOW_LEN]> {
+        (**self).load()
+    }
+
+    fn store(&mut self, image: &[u8; REGISTER_WINDOW_LEN]) {
+        (**self).store(image)
+    }
+}
+
+/// Simulated CDCE913 answering on the I2C bus it is handed to, with its EEPROM
+/// backed by `S`. `S` only keeps the register window; the EEPROM of the PLL
+/// blocks beyond it lives and dies with the simulated device.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SimulatedCdce913<S = NoStorage> {
+    registers: [u8; MAX_REGISTER_MAP_LEN],
+    eeprom: [u8; MAX_REGISTER_MAP_LEN],
+    // Registers past this read as 0 and ignore writes
+    register_map_len: usize,
+    default_address: u8,
+    storage: S,
+    serial_interface_enabled: bool,
+    // Remaining reads of register 0x01 with EEPIP set before the EEPROM write
+    // in progress completes
+    eeprom_write_remaining: Option<u32>,
+    eeprom_write_duration: u32,
+    eeprom_writes: u32,
+    rejected_eeprom_writes: u32,
+}
+
+impl Default for SimulatedCdce913 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SimulatedCdce913 {
+    /// A factory-fresh device with the datasheet defaults in its EEPROM
+    pub fn new() -> Self {
+        Self::from_eeprom(&DeviceConfig::from_bytes(FACTORY_DEFAULTS))
+    }
+
+    /// A device that just powered up with `config` in its EEPROM. Register
+    /// 0x00 keeps identifying a CDCE913, use [`Self::set_variant`] to change
+    /// that.
+    pub fn from_eeprom(config: &DeviceConfig) -> Self {
+        let mut eeprom = config.to_bytes();
+        eeprom[0] = FACTORY_DEFAULTS[0];
+
+        Self::with_image(eeprom, NoStorage)
+    }
+}
+
+impl<S: EepromStorage> SimulatedCdce913<S> {
+    /// A device that just powered up with the EEPROM image `storage` holds,
+    /// or the datasheet defaults if it holds none. Every completed EEPROM
+    /// write and [`Self::set_variant`] is stored back.
+    pub fn with_storage(mut storage: S) -> Self {
+        let eeprom = storage.load().unwrap_or(FACTORY_DEFAULTS);
+
+        Self::with_image(eeprom, storage)
+    }
+
+    fn with_image(window: [u8; REGISTER_WINDOW_LEN], storage: S) -> Self {
+        let mut eeprom = [0; MAX_REGISTER_MAP_LEN];
+        eeprom[..REGISTER_WINDOW_LEN].copy_from_slice(&window);
+        // The PLL blocks past the window power up like the PLL1 block
+        for block in eeprom[REGISTER_WINDOW_LEN..].chunks_exact_mut(0x10) {
+            block.copy_from_slice(&FACTORY_DEFAULTS[0x10..]);
+        }
+
+        let mut sim = Self {
+            registers: eeprom,
+            eeprom,
+            register_map_len: Cdce913::REGISTER_MAP_LEN,
+            default_address: Cdce913::DEFAULT_ADDRESS,
+            storage,
+            serial_interface_enabled: true,
+            eeprom_write_remaining: None,
+            eeprom_write_duration: DEFAULT_EEPROM_WRITE_DURATION,
+            eeprom_writes: 0,
+            rejected_eeprom_writes: 0,
+        };
+        sim.power_cycle();
+
+        sim
+    }
+
+    /// Turns the simulated device into `device`: it answers on the default
+    /// address of `device` and has its register map, so e.g. a CDCE925 gets
+    /// the PLL2 block at 0x20-0x2F. TARGET_ADR and BCOUNT are set to the
+    /// defaults of `device` in the EEPROM and the registers.
+    pub fn set_device<D: Device>(&mut self, _device: D) {
+        self.register_map_len = D::REGISTER_MAP_LEN;
+        self.default_address = D::DEFAULT_ADDRESS;
+
+        let target_adr = D::DEFAULT_ADDRESS & 0b11;
+        for image in [&mut self.eeprom, &mut self.registers] {
+            image[1] = (image[1] & !0b11) | target_adr;
+
+            // BCOUNT defaults to the size of the register map
+            let mut reg6 = GenericConfigurationRegister6::from(image[6]);
+            reg6.set_bcount(D::REGISTER_MAP_LEN as u8);
+            image[6] = reg6.into();
+        }
+        self.store_eeprom();
+    }
+
+    /// Changes the voltage variant reported by the E_EL bit
+    pub fn set_variant(&mut self, variant: DeviceIdentification) {
+        let e_el = (variant == DeviceIdentification::CDCE913) as u8;
+
+        self.eeprom[0] = (self.eeprom[0] & 0x7F) | (e_el << 7);
+        self.registers[0] = self.eeprom[0];
+        self.store_eeprom();
+    }
+
+    /// Number of register 0x01 reads EEPIP stays set for after an EEPROM write
+    /// is started. The driver reads it once per
+    /// [`crate::timing::EEPROM_POLL_INTERVAL_MS`], so `reads` beyond
+    /// [`crate::timing::EEPROM_WRITE_TIMEOUT_MS`] make it time out.
+    pub fn set_eeprom_write_duration(&mut self, reads: u32) {
+        self.eeprom_write_duration = reads;
+    }
+
+    /// Reloads the registers from the EEPROM, aborting any EEPROM write in
+    /// progress
+    pub fn power_cycle(&mut self) {
+        self.eeprom_write_remaining = None;
+        self.load_eeprom();
+    }
+
+    /// Current contents of the register window
+    pub fn registers(&self) -> DeviceConfig {
+        DeviceConfig::from_bytes(window(&self.registers))
+    }
+
+    /// Current contents of the EEPROM
+    pub fn eeprom(&self) -> DeviceConfig {
+        DeviceConfig::from_bytes(window(&self.eeprom))
+    }
+
+    /// Current contents of the whole register map, including the PLL blocks
+    /// past the window. The slice is as long as the map of the simulated
+    /// device.
+    pub fn register_map(&self) -> &[u8] {
+        &self.registers[..self.register_map_len]
+    }
+
+    /// Number of completed EEPROM writes
+    pub fn eeprom_writes(&self) -> u32 {
+        self.eeprom_writes
+    }
+
+    /// Number of EEPROM writes refused because EELOCK was committed. The
+    /// device gives no sign of refusing one: EEPIP never gets set.
+    pub fn rejected_eeprom_writes(&self) -> u32 {
+        self.rejected_eeprom_writes
+    }
+
+    pub fn storage(&self) -> &S {
+        &self.storage
+    }
+
+    pub fn into_storage(self) -> S {
+        self.storage
+    }
+
+    /// Whether the device still listens on the bus, i.e. SPICON is cleared
+    pub fn serial_interface_enabled(&self) -> bool {
+        self.serial_interface_enabled
+    }
+
+    /// Address the device currently answers on, which follows TARGET_ADR
+    pub fn address(&self) -> u8 {
+        let reg = GenericConfigurationRegister1::from(self.registers[1]);
+
+        (self.default_address & !0b11) | reg.target_adr()
+    }
+
+    fn eeprom_locked(&self) -> bool {
+        GenericConfigurationRegister1::from(self.eeprom[1]).eelock()
+    }
+
+    fn load_eeprom(&mut self) {
+        self.registers = self.eeprom;
+        self.registers[1] &= !(1 << 6);
+        self.serial_interface_enabled = GenericConfigurationRegister2::from(self.registers[2])
+            .serial_interface_pin_mode()
+            == SerialInterfacePinMode::SerialProgrammingInterface;
+    }
+
+    fn store_eeprom(&mut self) {
+        self.storage.store(&window(&self.eeprom));
+    }
+
+    fn read_register(&mut self, offset: u8) -> u8 {
+        let Some(&value) = self.register_map().get(offset as usize) else {
+            return 0;
+        };
+
+        if offset == 1
+            && let Some(remaining) = self.eeprom_write_remaining
+        {
+            if remaining == 0 {
+                self.complete_eeprom_write();
+            } else {
+                self.eeprom_write_remaining = Some(remaining - 1);
+
+                return value | 1 << 6;
+            }
+        }
+
+        value
+    }
+
+    fn write_register(&mut self, offset: u8, value: u8) {
+        if offset as usize >= self.register_map_len {
+            return;
+        }
+
+        let mask = writable_mask(offset);
+        let previous = self.registers[offset as usize];
+        self.registers[offset as usize] = (previous & !mask) | (value & mask);
+
+        match offset {
+            1 => {
+                let was_powered_down = GenericConfigurationRegister1::from(previous).pwdn();
+                let powered_down = GenericConfigurationRegister1::from(value).pwdn();
+
+                // Leaving power-down re-loads the EEPROM
+                if was_powered_down && !powered_down {
+                    self.load_eeprom();
+                }
+            }
+            2 if GenericConfigurationRegister2::from(value).serial_interface_pin_mode()
+                == SerialInterfacePinMode::ControlS1S2 =>
+            {
+                self.serial_interface_enabled = false;
+            }
+            6 => {
+                let start = GenericConfigurationRegister6::from(value).eewrite()
+                    && !GenericConfigurationRegister6::from(previous).eewrite();
+
+                // A locked EEPROM silently ignores EEWRITE
+                if start && self.eeprom_write_remaining.is_none() {
+                    if self.eeprom_locked() {
+                        self.rejected_eeprom_writes += 1;
+                    } else {
+                        self.eeprom_write_remaining = Some(self.eeprom_write_duration);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn complete_eeprom_write(&mut self) {
+        self.eeprom_write_remaining = None;
+
+        let identification = self.eeprom[0];
+        self.eeprom = self.registers;
+        self.eeprom[0] = identification;
+        // EEWRITE is a command, not part of the stored configuration
+        self.eeprom[6] &= !1;
+
+        self.store_eeprom();
+        self.eeprom_writes += 1;
+    }
+}
+
+fn window(image: &[u8; MAX_REGISTER_MAP_LEN]) -> [u8; REGISTER_WINDOW_LEN] {
+    let mut window = [0; REGISTER_WINDOW_LEN];
+    window.copy_from_slice(&image[..REGISTER_WINDOW_LEN]);
+
+    window
+}
+
+impl<S> ErrorType for SimulatedCdce913<S> {
+    type Error = SimError;
+}
+
+impl<S: EepromStorage> I2c for SimulatedCdce913<S> {
+    async fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        if address != self.address() || !self.serial_interface_enabled {
+            return Err(SimError::Nack);
+        }
+
+        let mut command = None;
+
+        for operation in operations {
+            match operation {
+                Operation::Write(bytes) => {
+                    let (&code, data) = bytes.split_first().ok_or(SimError::MissingCommandCode)?;
+                    let code = CommandCode::from(code);
+                    let offset = code.offset();
+
+                    // Block writes start with the number of bytes that follow
+                    let data = if code.mode() {
+                        data
+                    } else {
+                        data.get(1..).unwrap_or_default()
+                    };
+
+                    for (index, &value) in data.iter().enumerate() {
+                        self.write_register(offset + index as u8, value);
+                    }
+
+                    command = Some(code);
+                }
+                Operation::Read(buf) => {
+                    let code = command.ok_or(SimError::MissingCommandCode)?;
+                    let offset = code.offset();
+
+                    // Block reads start with the byte count, followed by
+                    // exactly BCOUNT registers
+                    let buf = if code.mode() {
+                        &mut buf[..]
+                    } else {
+                        let bcount =
+                            GenericConfigurationRegister6::from(self.registers[6]).bcount();
+                        let (count, rest) = buf
+                            .split_first_mut()
+                            .filter(|(_, rest)| rest.len() == bcount as usize)
+                            .ok_or(SimError::BlockReadLength)?;
+
+                        *count = bcount;
+                        rest
+                    };
+
+                    for (index, byte) in buf.iter_mut().enumerate() {
+                        *byte = self.read_register(offset + index as u8);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
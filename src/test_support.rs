@@ -0,0 +1,273 @@
+//! Builds the [`embedded_hal_mock`] I2C transactions the driver performs for
+//! common operations, so code using [`crate::CDCx913`] can be tested against
+//! a mock without spelling out the driver's byte sequences by hand.
+//!
+//! [`Expectations`] tracks the register contents the mock pretends to have,
+//! so the values of read-modify-write sequences stay consistent:
+//!
+//! ```ignore
+//! let expectations = Expectations::new(ADDRESS, DeviceConfig::from_bytes(defaults))
+//!     .pdiv1_update(u10::new(4))
+//!     .into_transactions();
+//! let mut i2c = embedded_hal_mock::eh1::i2c::Mock::new(&expectations);
+//! ```
+
+use alloc::{vec, vec::Vec};
+
+use arbitrary_int::u10;
+use embedded_hal_mock::eh1::i2c::Transaction;
+
+use crate::{
+    config::{ChangeReport, DeviceConfig, REGISTER_WINDOW_LEN, WRITABLE_OFFSETS},
+    i2c::{CommandCode, OpCode},
+    registers::{
+        generic_configuration::{
+            GenericConfigurationRegister1, GenericConfigurationRegister2,
+            GenericConfigurationRegister3, GenericConfigurationRegister6, SerialInterfacePinMode,
+        },
+        pll1_configuration::{
+            Fs1Selection, Pll1ConfigurationRegister6, Pll1ConfigurationRegister7, PllSettings,
+        },
+    },
+};
+
+const OUTSIDE_WINDOW: &str = "offset outside of the register window";
+
+/// Expectation for a byte read of the register at `offset` returning `value`
+pub fn read_register(address: u8, offset: u8, value: u8) -> Transaction {
+    Transaction::write_read(
+        address,
+        vec![CommandCode::new(OpCode::Byte, offset).into()],
+        vec![value],
+    )
+}
+
+/// Expectation for a byte write of `value` to the register at `offset`
+pub fn write_register(address: u8, offset: u8, value: u8) -> Transaction {
+    Transaction::write(
+        address,
+        vec![CommandCode::new(OpCode::Byte, offset).into(), value],
+    )
+}
+
+/// Expectation for a block read of the registers at `offset` and up returning
+/// `values`, preceded by the byte count `bcount` like on the device
+pub fn read_registers(address: u8, offset: u8, bcount: u8, values: &[u8]) -> Transaction {
+    let mut response = vec![bcount];
+    response.extend_from_slice(values);
+
+    Transaction::write_read(
+        address,
+        vec![CommandCode::new(OpCode::Block, offset).into()],
+        response,
+    )
+}
+
+/// Expectation for a block write of `values` to the registers at `offset` and
+/// up
+pub fn write_registers(address: u8, offset: u8, values: &[u8]) -> Transaction {
+    let mut request = vec![
+        CommandCode::new(OpCode::Block, offset).into(),
+        values.len() as u8,
+    ];
+    request.extend_from_slice(values);
+
+    Transaction::write(address, request)
+}
+
+/// Sequence of expected transactions for a device with known register
+/// contents
+#[derive(Debug, Clone)]
+pub struct Expectations {
+    address: u8,
+    registers: DeviceConfig,
+    transactions: Vec<Transaction>,
+    // Whether the driver knows BCOUNT, which it reads before its first block
+    // read and after leaving power-down
+    bcount_known: bool,
+}
+
+impl Expectations {
+    pub fn new(address: u8, registers: DeviceConfig) -> Self {
+        Self {
+            address,
+            registers,
+            transactions: Vec::new(),
+            bcount_known: false,
+        }
+    }
+
+    /// Register contents after all expected transactions
+    pub fn registers(&self) -> &DeviceConfig {
+        &self.registers
+    }
+
+    pub fn into_transactions(self) -> Vec<Transaction> {
+        self.transactions
+    }
+
+    /// A byte read of the register at `offset`. Like the mock itself, this
+    /// panics on misuse: `offset` must lie inside the register window.
+    pub fn read(mut self, offset: u8) -> Self {
+        let value = self.registers.byte(offset).expect(OUTSIDE_WINDOW);
+
+        self.transactions
+            .push(read_register(self.address, offset, value));
+        self
+    }
+
+    /// A byte write of `value` to the register at `offset`, which must lie
+    /// inside the register window
+    pub fn write(mut self, offset: u8, value: u8) -> Self {
+        self.registers
+            .set_byte(offset, value)
+            .expect(OUTSIDE_WINDOW);
+        self.track_write(offset, value);
+
+        self.transactions
+            .push(write_register(self.address, offset, value));
+        self
+    }
+
+    /// The block read the driver fetches `len` registers starting at `offset`
+    /// with: all BCOUNT registers from 0x00, which must lie inside the register
+    /// window and include the requested ones
+    pub fn read_block(mut self, offset: u8, len: usize) -> Self {
+        if !self.bcount_known {
+            self = self.read(0x06);
+            self.bcount_known = true;
+        }
+
+        let bcount = GenericConfigurationRegister6::from(self.registers.register(0x06)).bcount();
+        assert!(
+            offset as usize + len <= bcount as usize,
+            "read beyond BCOUNT"
+        );
+
+        let values: Vec<u8> = (0..bcount)
+            .map(|offset| self.registers.byte(offset).expect(OUTSIDE_WINDOW))
+            .collect();
+
+        self.transactions
+            .push(read_registers(self.address, 0x00, bcount, &values));
+        self
+    }
+
+    /// A block write of `values` to the registers starting at `offset`, all of
+    /// which must lie inside the register window
+    pub fn write_block(mut self, offset: u8, values: &[u8]) -> Self {
+        for (register, &value) in (offset..).zip(values) {
+            self.registers
+                .set_byte(register, value)
+                .expect(OUTSIDE_WINDOW);
+            self.track_write(register, value);
+        }
+
+        self.transactions
+            .push(write_registers(self.address, offset, values));
+        self
+    }
+
+    // Follows the driver in forgetting BCOUNT when leaving power-down and
+    // learning it from a write
+    fn track_write(&mut self, offset: u8, value: u8) {
+        match offset {
+            0x01 if !GenericConfigurationRegister1::from(value).pwdn() => self.bcount_known = false,
+            0x06 => self.bcount_known = true,
+            _ => {}
+        }
+    }
+
+    /// The block read and block write the driver stages multi-register
+    /// updates with
+    pub fn update_block<const N: usize>(self, offset: u8, f: impl FnOnce(&mut [u8; N])) -> Self {
+        let mut values: [u8; N] = core::array::from_fn(|index| {
+            self.registers
+                .byte(offset + index as u8)
+                .expect(OUTSIDE_WINDOW)
+        });
+        f(&mut values);
+
+        self.read_block(offset, N).write_block(offset, &values)
+    }
+
+    /// The read-modify-write every single-register setter of the driver does
+    pub fn modify<T: From<u8> + Into<u8>>(self, offset: u8, f: impl FnOnce(&mut T)) -> Self {
+        let mut reg = T::from(self.registers.byte(offset).expect(OUTSIDE_WINDOW));
+        f(&mut reg);
+
+        self.read(offset).write(offset, reg.into())
+    }
+
+    /// [`crate::CDCx913::set_y1_output_divider`]
+    pub fn pdiv1_update(self, value: u10) -> Self {
+        self.update_block(0x02, |bytes: &mut [u8; 2]| {
+            let mut reg2 = GenericConfigurationRegister2::from(bytes[0]);
+            let mut reg3 = GenericConfigurationRegister3::from(bytes[1]);
+
+            reg2.set_pdiv1_9_8((value.value() >> 8) as _);
+            reg3.set_pdiv1_7_0((value.value() & 0xFF) as _);
+
+            *bytes = [reg2.into(), reg3.into()];
+        })
+    }
+
+    /// [`crate::CDCx913::set_y2_output_divider`]
+    pub fn pdiv2_update(self, value: u8) -> Self {
+        self.modify(0x16, |reg: &mut Pll1ConfigurationRegister6| {
+            reg.set_pdiv2(value)
+        })
+    }
+
+    /// [`crate::CDCx913::set_y3_output_divider`]
+    pub fn pdiv3_update(self, value: u8) -> Self {
+        self.modify(0x17, |reg: &mut Pll1ConfigurationRegister7| {
+            reg.set_pdiv3(value)
+        })
+    }
+
+    /// [`crate::CDCx913::set_pll_settings`] with relaxed sequencing
+    pub fn pll1_settings_update(self, bank: Fs1Selection, value: PllSettings) -> Self {
+        let base = match bank {
+            Fs1Selection::Fvcxo0 => 0x18,
+            Fs1Selection::Fvcxo1 => 0x1C,
+        };
+
+        self.write_block(base, &value.0.to_be_bytes())
+    }
+
+    /// [`crate::CDCx913::read_config`]
+    pub fn read_config(self) -> Self {
+        (0..REGISTER_WINDOW_LEN as u8).fold(self, Self::read)
+    }
+
+    /// [`crate::CDCx913::apply_config`]
+    pub fn apply_config(self, config: &DeviceConfig) -> Self {
+        let report = ChangeReport::new(self.registers, *config, true);
+        let mut registers = WRITABLE_OFFSETS.map(|offset| report.after().register(offset));
+
+        // SPICON goes out last
+        let reg2 = GenericConfigurationRegister2::from(registers[1]);
+        let mut staged = reg2;
+        staged.set_serial_interface_pin_mode(SerialInterfacePinMode::SerialProgrammingInterface);
+        registers[1] = staged.into();
+
+        let (generic, pll1) = registers.split_at(6);
+
+        let expectations = self
+            .read_block(0x00, REGISTER_WINDOW_LEN)
+            .write_block(WRITABLE_OFFSETS[0], generic)
+            .write_block(0x10, pll1);
+
+        if staged != reg2 {
+            expectations.write(0x02, reg2.into())
+        } else {
+            expectations
+        }
+    }
+
+    /// [`crate::CDCx913::verify_config`]
+    pub fn verify_config(self) -> Self {
+        self.read_block(0x00, REGISTER_WINDOW_LEN)
+    }
+}
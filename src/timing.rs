@@ -0,0 +1,64 @@
+//! Timing figures from the datasheet, used by the driver wherever it has to
+//! wait for the device. They're public so applications can budget their own
+//! startup sequencing consistently with the driver.
+
+/// Typical time the device needs to program the EEPROM after EEWRITE is set
+pub const EEPROM_WRITE_TIME_MS: u32 = 20;
+
+/// Upper bound on how long EEPIP may stay set after starting an EEPROM write
+/// before the write is considered failed. Leaves generous headroom over
+/// [`EEPROM_WRITE_TIME_MS`].
+pub const EEPROM_WRITE_TIMEOUT_MS: u32 = 5 * EEPROM_WRITE_TIME_MS;
+
+/// Interval between EEPIP polls while waiting for an EEPROM write
+pub const EEPROM_POLL_INTERVAL_MS: u32 = 1;
+
+/// Interval between reads while [`crate::CDCx913::poll_register_until`] waits
+/// for a register to change
+pub const POLL_INTERVAL_MS: u32 = 1;
+
+/// Start-up time of the device (oscillator and internal supplies) after power
+/// is applied or power-down is released
+pub const POWER_UP_TIME_US: u32 = 500;
+
+/// Time for the PLL to lock after it's been (re)programmed or powered up
+pub const PLL_LOCK_TIME_US: u32 = 100;
+
+/// Time from releasing power-down until the outputs are stable, covering the
+/// EEPROM re-load, start-up and PLL lock
+pub const STABILIZATION_TIME_US: u32 = POWER_UP_TIME_US + PLL_LOCK_TIME_US;
+
+/// Stand-in for the delay of a driver created without one, e.g. with
+/// `CDCx913::new`. Such a driver can do everything that doesn't have to wait
+/// for the device; `CDCx913::with_delay` hands it a real delay later on.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct NoDelay;
+
+/// Delay provider backed by `embassy_time::Timer`, for the driver methods that
+/// wait for the device. It carries no state, so embassy users don't have to
+/// thread a delay through their code:
+///
+/// ```ignore
+/// let mut driver = CDCE913::new_with_delay(i2c, EmbassyTimer);
+/// driver.write_config_to_eeprom(&config).await?;
+/// ```
+#[cfg(feature = "embassy-time")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct EmbassyTimer;
+
+#[cfg(feature = "embassy-time")]
+impl embedded_hal_async::delay::DelayNs for EmbassyTimer {
+    async fn delay_ns(&mut self, ns: u32) {
+        embassy_time::Timer::after_nanos(ns.into()).await
+    }
+
+    async fn delay_us(&mut self, us: u32) {
+        embassy_time::Timer::after_micros(us.into()).await
+    }
+
+    async fn delay_ms(&mut self, ms: u32) {
+        embassy_time::Timer::after_millis(ms.into()).await
+    }
+}
@@ -0,0 +1,224 @@
+//! Parser for a human-editable TOML description of a configuration, planned
+//! and validated into a [`DeviceConfig`], e.g. for host-side provisioning
+//! tools:
+//!
+//! ```toml
+//! reference = "25MHz"
+//! # Optional: the family member whose default TARGET_ADR to keep, the 1.8 V
+//! # limits, center or down spread, even PDIVs first
+//! device = "CDCE925"
+//! variant = "CDCEL913"
+//! ssc = "center 0.5%"
+//! even_dividers = true
+//!
+//! [y1]
+//! frequency = "25MHz"
+//!
+//! [y2]
+//! frequency = "48MHz"
+//! ```
+//!
+//! Frequencies are strings with a `MHz`, `kHz` or `Hz` unit, decimals allowed
+//! as long as they come out as whole Hz, or plain integers in Hz. Outputs
+//! without a table are 3-stated, like with [`ConfigRequest`].
+
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+use core::fmt;
+use toml::{Table, Value};
+
+use crate::{
+    config::{DeviceConfig, SscSetting, ValidationError},
+    family::{Cdce913, Cdce925, Cdce937, Cdce949},
+    planner::{ConfigRequest, PlanError, ssc_center, ssc_down},
+    registers::generic_configuration::DeviceIdentification,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TomlError {
+    /// The input isn't valid TOML
+    Syntax(String),
+    /// A required key is missing, e.g. `reference` or `y2.frequency`
+    Missing(&'static str),
+    /// A key the schema doesn't have, by its path, e.g. `y2.freq`
+    UnknownKey(String),
+    /// A value of the wrong type or format, by the path of its key
+    InvalidValue(String),
+    /// No configuration produces the requested frequencies
+    Plan(PlanError),
+    /// The planned configuration exceeds the limits of the variant
+    Validation(ValidationError),
+}
+
+impl fmt::Display for TomlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Syntax(message) => write!(f, "invalid TOML: {message}"),
+            Self::Missing(key) => write!(f, "`{key}` is missing"),
+            Self::UnknownKey(key) => write!(f, "unknown key `{key}`"),
+            Self::InvalidValue(key) => write!(f, "invalid value for `{key}`"),
+            Self::Plan(error) => write!(f, "can't plan the configuration: {error:?}"),
+            Self::Validation(error) => write!(f, "planned configuration is invalid: {error:?}"),
+        }
+    }
+}
+
+impl std::error::Error for TomlError {}
+
+/// Parses `input`, plans the configuration it describes and validates it
+/// against the limits of its variant
+pub fn parse(input: &str) -> Result<DeviceConfig, TomlError> {
+    let table: Table = input
+        .parse()
+        .map_err(|error: toml::de::Error| TomlError::Syntax(error.message().to_string()))?;
+
+    let mut request = ConfigRequest::new();
+    let mut reference_hz = None;
+    let mut variant = DeviceIdentification::CDCE913;
+
+    for (key, value) in &table {
+        let invalid = || TomlError::InvalidValue(key.clone());
+
+        match key.as_str() {
+            "reference" => {
+                let hz = frequency(value).ok_or_else(invalid)?;
+                reference_hz = Some(hz);
+                request = request.with_reference_hz(hz);
+            }
+            "variant" => {
+                variant = match value.as_str() {
+                    Some("CDCE913") => DeviceIdentification::CDCE913,
+                    Some("CDCEL913") => DeviceIdentification::CDCEL913,
+                    _ => return Err(invalid()),
+                };
+            }
+            "device" => {
+                request = match value.as_str() {
+                    Some("CDCE913") => request.with_device(Cdce913),
+                    Some("CDCE925") => request.with_device(Cdce925),
+                    Some("CDCE937") => request.with_device(Cdce937),
+                    Some("CDCE949") => request.with_device(Cdce949),
+                    _ => return Err(invalid()),
+                };
+            }
+            "ssc" => request = request.with_ssc(ssc(value).ok_or_else(invalid)?),
+            "even_dividers" => match value.as_bool() {
+                Some(true) => request = request.with_even_dividers_preferred(),
+                Some(false) => {}
+                None => return Err(invalid()),
+            },
+            "y1" | "y2" | "y3" => {
+                let hz = output_frequency(key, value)?;
+
+                request = match key.as_str() {
+                    "y1" => request.with_y1_hz(hz),
+                    "y2" => request.with_y2_hz(hz),
+                    _ => request.with_y3_hz(hz),
+                };
+            }
+            _ => return Err(TomlError::UnknownKey(key.clone())),
+        }
+    }
+
+    let reference_hz = reference_hz.ok_or(TomlError::Missing("reference"))?;
+    let config = request
+        .with_variant(variant)
+        .build()
+        .map_err(TomlError::Plan)?;
+
+    config
+        .validate(variant, reference_hz)
+        .map_err(TomlError::Validation)?;
+
+    Ok(config)
+}
+
+// The frequency in the table of output `key`
+fn output_frequency(key: &str, value: &Value) -> Result<u32, TomlError> {
+    let table = value
+        .as_table()
+        .ok_or_else(|| TomlError::InvalidValue(key.to_string()))?;
+    let mut hz = None;
+
+    for (inner, value) in table {
+        match inner.as_str() {
+            "frequency" => {
+                hz = Some(
+                    frequency(value)
+                        .ok_or_else(|| TomlError::InvalidValue(format!("{key}.{inner}")))?,
+                );
+            }
+            _ => return Err(TomlError::UnknownKey(format!("{key}.{inner}"))),
+        }
+    }
+
+    hz.ok_or(TomlError::Missing(match key {
+        "y1" => "y1.frequency",
+        "y2" => "y2.frequency",
+        _ => "y3.frequency",
+    }))
+}
+
+// "45.1584MHz", "12 kHz" or a plain integer in Hz
+fn frequency(value: &Value) -> Option<u32> {
+    if let Some(hz) = value.as_integer() {
+        return u32::try_from(hz).ok();
+    }
+
+    let text = value.as_str()?.trim();
+    let (number, digits) = if let Some(number) = text.strip_suffix("MHz") {
+        (number, 6)
+    } else if let Some(number) = text.strip_suffix("kHz") {
+        (number, 3)
+    } else {
+        (text.strip_suffix("Hz")?, 0)
+    };
+
+    u32::try_from(decimal(number.trim(), digits)?).ok()
+}
+
+// "center 0.5%" or "down 1%"
+fn ssc(value: &Value) -> Option<SscSetting> {
+    let (kind, amount) = value.as_str()?.trim().split_once(' ')?;
+    let hundredths = u32::try_from(decimal(amount.trim().strip_suffix('%')?, 2)?).ok()?;
+
+    match kind {
+        "center" => ssc_center(hundredths),
+        "down" => ssc_down(hundredths),
+        _ => None,
+    }
+}
+
+// `number` times 10^`digits`, if that is a whole number
+fn decimal(number: &str, digits: u32) -> Option<u64> {
+    let (whole, fraction) = number.split_once('.').unwrap_or((number, ""));
+
+    if whole.is_empty()
+        || !whole
+            .bytes()
+            .chain(fraction.bytes())
+            .all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.len() > digits as usize {
+        return None;
+    }
+
+    let scale = 10u64.pow(digits - fraction.len() as u32);
+    let fraction = if fraction.is_empty() {
+        0
+    } else {
+        fraction.parse::<u64>().ok()?
+    };
+
+    whole
+        .parse::<u64>()
+        .ok()?
+        .checked_mul(10u64.pow(digits))?
+        .checked_add(fraction * scale)
+}
@@ -0,0 +1,92 @@
+//! Drivers seeded from a known configuration instead of the bus.
+
+use cdcx913::{
+    CDCE913, Error,
+    control::ControlInputState,
+    registers::{
+        generic_configuration::{
+            GenericConfigurationRegister1, GenericConfigurationRegister2, SerialInterfacePinMode,
+        },
+        pll1_configuration::{Pll1ConfigurationRegister2, SscModulationAmountDown},
+    },
+    sim::SimulatedCdce913,
+    u3,
+};
+use embassy_futures::block_on;
+
+#[test]
+fn address_follows_target_adr() {
+    let mut config = SimulatedCdce913::new().eeprom();
+    config
+        .modify(0x01, |reg: &mut GenericConfigurationRegister1| {
+            reg.set_target_adr(0b10)
+        })
+        .unwrap();
+
+    let mut sim = SimulatedCdce913::from_eeprom(&config);
+    let mut driver = CDCE913::assume_config(&mut sim, &config);
+
+    assert_eq!(block_on(driver.target_address()).unwrap().value(), 0b10);
+}
+
+#[test]
+fn released_serial_interface_is_respected() {
+    let mut config = SimulatedCdce913::new().eeprom();
+    config
+        .modify(0x02, |reg: &mut GenericConfigurationRegister2| {
+            reg.set_serial_interface_pin_mode(SerialInterfacePinMode::ControlS1S2)
+        })
+        .unwrap();
+
+    let mut sim = SimulatedCdce913::from_eeprom(&config);
+    let mut driver = CDCE913::assume_config(&mut sim, &config);
+
+    assert_eq!(
+        block_on(driver.read_config()),
+        Err(Error::SerialInterfaceDisabled)
+    );
+}
+
+#[test]
+fn spread_spectrum_amounts_are_remembered() {
+    let state = ControlInputState::S000;
+    let mut config = SimulatedCdce913::new().eeprom();
+    config
+        .modify(0x12, |reg: &mut Pll1ConfigurationRegister2| {
+            reg.set_ssc1_0(SscModulationAmountDown::Minus1Percent as u8)
+        })
+        .unwrap();
+
+    // The device has since lost the amount, e.g. to a register write the
+    // driver didn't see
+    let mut sim = SimulatedCdce913::from_eeprom(&config);
+    let mut driver = CDCE913::new(&mut sim);
+    block_on(driver.set_spread_spectrum_clocking_selection_raw(state, u3::new(0))).unwrap();
+
+    let mut driver = CDCE913::assume_config(&mut sim, &config);
+    block_on(async {
+        driver
+            .set_spread_spectrum_enabled(state, true)
+            .await
+            .unwrap();
+        assert_eq!(
+            driver
+                .spread_spectrum_clocking_selection_as_down(state)
+                .await
+                .unwrap(),
+            SscModulationAmountDown::Minus1Percent
+        );
+    });
+}
+
+#[test]
+fn nothing_is_seeded_from_a_default_config() {
+    let config = SimulatedCdce913::new().eeprom();
+    let mut sim = SimulatedCdce913::new();
+    let mut driver = CDCE913::assume_config(&mut sim, &config);
+
+    assert_eq!(
+        block_on(driver.set_spread_spectrum_enabled(ControlInputState::S000, true)),
+        Err(Error::UnknownSscAmount)
+    );
+}
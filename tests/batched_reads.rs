@@ -0,0 +1,39 @@
+//! Several registers read in one I2C transaction.
+
+use cdcx913::{CDCE913, Error, sim::SimulatedCdce913};
+use embassy_futures::block_on;
+
+#[test]
+fn batched_reads_match_single_reads() {
+    let mut sim = SimulatedCdce913::new();
+    let mut driver = CDCE913::new(&mut sim);
+
+    block_on(async {
+        let offsets = [0x02, 0x14, 0x06, 0x17];
+
+        let batched = driver.read_bytes_unchecked(offsets).await.unwrap();
+        assert_eq!(driver.stats().transactions, 1);
+
+        for (offset, value) in offsets.into_iter().zip(batched) {
+            assert_eq!(driver.read_byte_unchecked(offset).await.unwrap(), value);
+        }
+    });
+}
+
+#[test]
+fn released_serial_interface_is_not_read() {
+    let mut sim = SimulatedCdce913::new();
+    let mut driver = CDCE913::new(&mut sim);
+
+    block_on(async {
+        driver
+            .write_byte_unchecked(0x02, 0b0100_0000)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            driver.read_bytes_unchecked([0x01, 0x02]).await,
+            Err(Error::SerialInterfaceDisabled)
+        );
+    });
+}
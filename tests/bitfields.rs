@@ -0,0 +1,522 @@
+//! Round-trip and isolation properties of the register bitfields: every field
+//! setter must only touch its own bits, and every typed accessor must read back
+//! what was written.
+
+use arbitrary_int::{u2, u3};
+use cdcx913::{
+    i2c::CommandCode,
+    registers::{
+        OutputStateDefinition, OutputStateSelection, RegisterOffset, ReservedEncoding,
+        generic_configuration::*, pll1_configuration::*,
+    },
+    report::{FieldValue, decode_register, register_fields},
+};
+use proptest::prelude::*;
+
+/// Writes `value` into bits `hi..=lo` of a register holding `raw` and checks
+/// that exactly those bits changed
+fn check_field<T: From<u8> + Into<u8> + Copy>(
+    raw: u8,
+    value: u8,
+    (hi, lo): (u32, u32),
+    set: impl Fn(&mut T, u8),
+    get: impl Fn(&T) -> u8,
+) -> Result<(), TestCaseError> {
+    let width = hi - lo + 1;
+    let value = (value as u32 & ((1 << width) - 1)) as u8;
+    let mask = (((1u32 << width) - 1) << lo) as u8;
+
+    let mut reg = T::from(raw);
+    set(&mut reg, value);
+    let out: u8 = reg.into();
+
+    prop_assert_eq!(
+        out & !mask,
+        raw & !mask,
+        "bits outside {}..={} changed",
+        hi,
+        lo
+    );
+    prop_assert_eq!((out & mask) >> lo, value);
+    prop_assert_eq!(get(&reg), value);
+
+    Ok(())
+}
+
+fn check_bit<T: From<u8> + Into<u8> + Copy>(
+    raw: u8,
+    value: bool,
+    bit: u32,
+    set: impl Fn(&mut T, bool),
+    get: impl Fn(&T) -> bool,
+) -> Result<(), TestCaseError> {
+    check_field(
+        raw,
+        value as u8,
+        (bit, bit),
+        |reg: &mut T, value| set(reg, value != 0),
+        |reg| get(reg) as u8,
+    )
+}
+
+macro_rules! field {
+    ($raw:expr, $value:expr, $ty:ty, $get:ident, $set:ident, $hi:literal, $lo:literal) => {
+        check_field::<$ty>(
+            $raw,
+            $value,
+            ($hi, $lo),
+            |reg, value| reg.$set(value),
+            |reg| reg.$get(),
+        )?
+    };
+}
+
+macro_rules! bit {
+    ($raw:expr, $value:expr, $ty:ty, $get:ident, $set:ident, $bit:literal) => {
+        check_bit::<$ty>(
+            $raw,
+            $value,
+            $bit,
+            |reg, value| reg.$set(value),
+            |reg| reg.$get(),
+        )?
+    };
+}
+
+proptest! {
+    #[test]
+    fn registers_round_trip_through_u8(raw: u8) {
+        macro_rules! round_trip {
+            ($($ty:ty),*) => {
+                $(prop_assert_eq!(u8::from(<$ty>::from(raw)), raw);)*
+            };
+        }
+
+        round_trip!(
+            CommandCode,
+            GenericConfigurationRegister0, GenericConfigurationRegister1,
+            GenericConfigurationRegister2, GenericConfigurationRegister3,
+            GenericConfigurationRegister4, GenericConfigurationRegister5,
+            GenericConfigurationRegister6,
+            Pll1ConfigurationRegister0, Pll1ConfigurationRegister1, Pll1ConfigurationRegister2,
+            Pll1ConfigurationRegister3, Pll1ConfigurationRegister4, Pll1ConfigurationRegister5,
+            Pll1ConfigurationRegister6, Pll1ConfigurationRegister7, Pll1ConfigurationRegister8,
+            Pll1ConfigurationRegister9, Pll1ConfigurationRegisterA, Pll1ConfigurationRegisterB,
+            Pll1ConfigurationRegisterC, Pll1ConfigurationRegisterD, Pll1ConfigurationRegisterE,
+            Pll1ConfigurationRegisterF
+        );
+    }
+
+    #[test]
+    fn command_code_fields_are_isolated(raw: u8, value: u8, flag: bool) {
+        bit!(raw, flag, CommandCode, mode, set_mode, 7);
+        field!(raw, value, CommandCode, offset, set_offset, 6, 0);
+    }
+
+    #[test]
+    fn generic_configuration_fields_are_isolated(raw: u8, value: u8, flag: bool) {
+        bit!(raw, flag, GenericConfigurationRegister1, _eepip, set_eepip, 6);
+        bit!(raw, flag, GenericConfigurationRegister1, eelock, set_eelock, 5);
+        bit!(raw, flag, GenericConfigurationRegister1, pwdn, set_pwdn, 4);
+        field!(raw, value, GenericConfigurationRegister1, _inclk, set_inclk, 3, 2);
+        field!(raw, value, GenericConfigurationRegister1, target_adr, set_target_adr, 1, 0);
+
+        bit!(raw, flag, GenericConfigurationRegister2, _m1, set_m1, 7);
+        bit!(raw, flag, GenericConfigurationRegister2, _spicon, set_spicon, 6);
+        field!(raw, value, GenericConfigurationRegister2, y1_st1, set_y1_st1, 5, 4);
+        field!(raw, value, GenericConfigurationRegister2, y1_st0, set_y1_st0, 3, 2);
+        field!(raw, value, GenericConfigurationRegister2, pdiv1_9_8, set_pdiv1_9_8, 1, 0);
+
+        field!(raw, value, GenericConfigurationRegister3, pdiv1_7_0, set_pdiv1_7_0, 7, 0);
+
+        bit!(raw, flag, GenericConfigurationRegister4, y1_7, set_y1_7, 7);
+        bit!(raw, flag, GenericConfigurationRegister4, y1_6, set_y1_6, 6);
+        bit!(raw, flag, GenericConfigurationRegister4, y1_5, set_y1_5, 5);
+        bit!(raw, flag, GenericConfigurationRegister4, y1_4, set_y1_4, 4);
+        bit!(raw, flag, GenericConfigurationRegister4, y1_3, set_y1_3, 3);
+        bit!(raw, flag, GenericConfigurationRegister4, y1_2, set_y1_2, 2);
+        bit!(raw, flag, GenericConfigurationRegister4, y1_1, set_y1_1, 1);
+        bit!(raw, flag, GenericConfigurationRegister4, y1_0, set_y1_0, 0);
+
+        field!(raw, value, GenericConfigurationRegister5, xcsel, set_xcsel, 7, 3);
+
+        field!(raw, value, GenericConfigurationRegister6, bcount, set_bcount, 7, 1);
+        bit!(raw, flag, GenericConfigurationRegister6, eewrite, set_eewrite, 0);
+    }
+
+    #[test]
+    fn pll1_configuration_fields_are_isolated(raw: u8, value: u8, flag: bool) {
+        field!(raw, value, Pll1ConfigurationRegister0, ssc1_7, set_ssc1_7, 7, 5);
+        field!(raw, value, Pll1ConfigurationRegister0, ssc1_6, set_ssc1_6, 4, 2);
+        field!(raw, value, Pll1ConfigurationRegister0, ssc1_5, set_ssc1_5, 1, 0);
+
+        bit!(raw, flag, Pll1ConfigurationRegister1, ssc1_5, set_ssc1_5, 7);
+        field!(raw, value, Pll1ConfigurationRegister1, ssc1_4, set_ssc1_4, 6, 4);
+        field!(raw, value, Pll1ConfigurationRegister1, ssc1_3, set_ssc1_3, 3, 1);
+        bit!(raw, flag, Pll1ConfigurationRegister1, ssc1_2, set_ssc1_2, 0);
+
+        field!(raw, value, Pll1ConfigurationRegister2, ssc1_2, set_ssc1_2, 7, 6);
+        field!(raw, value, Pll1ConfigurationRegister2, ssc1_1, set_ssc1_1, 5, 3);
+        field!(raw, value, Pll1ConfigurationRegister2, ssc1_0, set_ssc1_0, 2, 0);
+
+        bit!(raw, flag, Pll1ConfigurationRegister3, fs1_7, set_fs1_7, 7);
+        bit!(raw, flag, Pll1ConfigurationRegister3, fs1_6, set_fs1_6, 6);
+        bit!(raw, flag, Pll1ConfigurationRegister3, fs1_5, set_fs1_5, 5);
+        bit!(raw, flag, Pll1ConfigurationRegister3, fs1_4, set_fs1_4, 4);
+        bit!(raw, flag, Pll1ConfigurationRegister3, fs1_3, set_fs1_3, 3);
+        bit!(raw, flag, Pll1ConfigurationRegister3, fs1_2, set_fs1_2, 2);
+        bit!(raw, flag, Pll1ConfigurationRegister3, fs1_1, set_fs1_1, 1);
+        bit!(raw, flag, Pll1ConfigurationRegister3, fs1_0, set_fs1_0, 0);
+
+        bit!(raw, flag, Pll1ConfigurationRegister4, _mux1, set_mux1, 7);
+        bit!(raw, flag, Pll1ConfigurationRegister4, _m2, set_m2, 6);
+        field!(raw, value, Pll1ConfigurationRegister4, _m3, set_m3, 5, 4);
+        field!(raw, value, Pll1ConfigurationRegister4, y2y3_st1, set_y2y3_st1, 3, 2);
+        field!(raw, value, Pll1ConfigurationRegister4, y2y3_st0, set_y2y3_st0, 1, 0);
+
+        bit!(raw, flag, Pll1ConfigurationRegister5, y2y3_7, set_y2y3_7, 7);
+        bit!(raw, flag, Pll1ConfigurationRegister5, y2y3_6, set_y2y3_6, 6);
+        bit!(raw, flag, Pll1ConfigurationRegister5, y2y3_5, set_y2y3_5, 5);
+        bit!(raw, flag, Pll1ConfigurationRegister5, y2y3_4, set_y2y3_4, 4);
+        bit!(raw, flag, Pll1ConfigurationRegister5, y2y3_3, set_y2y3_3, 3);
+        bit!(raw, flag, Pll1ConfigurationRegister5, y2y3_2, set_y2y3_2, 2);
+        bit!(raw, flag, Pll1ConfigurationRegister5, y2y3_1, set_y2y3_1, 1);
+        bit!(raw, flag, Pll1ConfigurationRegister5, y2y3_0, set_y2y3_0, 0);
+
+        bit!(raw, flag, Pll1ConfigurationRegister6, _ssc1dc, set_ssc1dc, 7);
+        field!(raw, value, Pll1ConfigurationRegister6, pdiv2, set_pdiv2, 6, 0);
+
+        field!(raw, value, Pll1ConfigurationRegister7, pdiv3, set_pdiv3, 6, 0);
+
+        field!(raw, value, Pll1ConfigurationRegister8, pll1_0n_11_4, set_pll1_0n_11_4, 7, 0);
+        field!(raw, value, Pll1ConfigurationRegister9, pll1_0n_3_0, set_pll1_0n_3_0, 7, 4);
+        field!(raw, value, Pll1ConfigurationRegister9, pll1_0r_8_5, set_pll1_0r_8_5, 3, 0);
+        field!(raw, value, Pll1ConfigurationRegisterA, pll1_0r_4_0, set_pll1_0r_4_0, 7, 3);
+        field!(raw, value, Pll1ConfigurationRegisterA, pll1_0q_5_3, set_pll1_0q_5_3, 2, 0);
+        field!(raw, value, Pll1ConfigurationRegisterB, pll1_0q_2_0, set_pll1_0q_2_0, 7, 5);
+        field!(raw, value, Pll1ConfigurationRegisterB, pll1_0p_2_0, set_pll1_0p_2_0, 4, 2);
+        field!(raw, value, Pll1ConfigurationRegisterB, _vco1_0_range, set_vco1_0_range, 1, 0);
+
+        field!(raw, value, Pll1ConfigurationRegisterC, pll1_1n_11_4, set_pll1_1n_11_4, 7, 0);
+        field!(raw, value, Pll1ConfigurationRegisterD, pll1_1n_3_0, set_pll1_1n_3_0, 7, 4);
+        field!(raw, value, Pll1ConfigurationRegisterD, pll1_1r_8_5, set_pll1_1r_8_5, 3, 0);
+        field!(raw, value, Pll1ConfigurationRegisterE, pll1_1r_4_0, set_pll1_1r_4_0, 7, 3);
+        field!(raw, value, Pll1ConfigurationRegisterE, pll1_1q_5_3, set_pll1_1q_5_3, 2, 0);
+        field!(raw, value, Pll1ConfigurationRegisterF, pll1_1q_2_0, set_pll1_1q_2_0, 7, 5);
+        field!(raw, value, Pll1ConfigurationRegisterF, pll1_1p_2_0, set_pll1_1p_2_0, 4, 2);
+        field!(raw, value, Pll1ConfigurationRegisterF, _vco1_1_range, set_vco1_1_range, 1, 0);
+    }
+
+    #[test]
+    fn pll_settings_round_trip_through_u32(raw: u32) {
+        prop_assert_eq!(u32::from(PllSettings::from(raw)), raw);
+    }
+
+    #[test]
+    fn pll_settings_fields_are_isolated(raw: u32, n in 0u16..4096, r in 0u16..512, q in 0u8..64, p in 0u8..8, range in 0u8..4) {
+        let mut settings = PllSettings(raw);
+        settings.set_pllx_yn(n);
+        prop_assert_eq!(settings.0 & 0x000F_FFFF, raw & 0x000F_FFFF);
+        prop_assert_eq!(settings.pllx_yn(), n);
+
+        let mut settings = PllSettings(raw);
+        settings.set_pllx_yr(r);
+        prop_assert_eq!(settings.0 & !0x000F_F800, raw & !0x000F_F800);
+        prop_assert_eq!(settings.pllx_yr(), r);
+
+        let mut settings = PllSettings(raw);
+        settings.set_pllx_yq(q);
+        prop_assert_eq!(settings.0 & !0x0000_07E0, raw & !0x0000_07E0);
+        prop_assert_eq!(settings.pllx_yq(), q);
+
+        let mut settings = PllSettings(raw);
+        settings.set_pllx_yp(p);
+        prop_assert_eq!(settings.0 & !0x0000_001C, raw & !0x0000_001C);
+        prop_assert_eq!(settings.pllx_yp(), p);
+
+        let mut settings = PllSettings(raw);
+        settings.set_vcox_y_range(range);
+        prop_assert_eq!(settings.0 & !0x0000_0003, raw & !0x0000_0003);
+        prop_assert_eq!(settings._vco1x_y_range(), range);
+    }
+
+    #[test]
+    fn ssc1_selections_round_trip(codes in prop::array::uniform8(0u8..8)) {
+        let codes = codes.map(u3::new);
+
+        prop_assert_eq!(unpack_ssc1_selections(pack_ssc1_selections(codes)), codes);
+    }
+
+    #[test]
+    fn ssc1_selections_match_the_register_fields(registers: [u8; 3]) {
+        let codes = unpack_ssc1_selections(registers).map(|code| code.value());
+        let reg0 = Pll1ConfigurationRegister0::from(registers[0]);
+        let reg1 = Pll1ConfigurationRegister1::from(registers[1]);
+        let reg2 = Pll1ConfigurationRegister2::from(registers[2]);
+
+        prop_assert_eq!(codes[7], reg0.ssc1_7());
+        prop_assert_eq!(codes[6], reg0.ssc1_6());
+        prop_assert_eq!(codes[5], reg0.ssc1_5() << 1 | reg1.ssc1_5() as u8);
+        prop_assert_eq!(codes[4], reg1.ssc1_4());
+        prop_assert_eq!(codes[3], reg1.ssc1_3());
+        prop_assert_eq!(codes[2], (reg1.ssc1_2() as u8) << 2 | reg2.ssc1_2());
+        prop_assert_eq!(codes[1], reg2.ssc1_1());
+        prop_assert_eq!(codes[0], reg2.ssc1_0());
+    }
+
+    #[test]
+    fn indexed_selections_round_trip(raw: u8, index in 0u8..8, flag: bool) {
+        let index = u3::new(index);
+        let selection = if flag { OutputStateSelection::State1 } else { OutputStateSelection::State0 };
+        let bank = if flag { Fs1Selection::Fvcxo1 } else { Fs1Selection::Fvcxo0 };
+        let others = !(1u8 << index.value());
+
+        let mut reg = GenericConfigurationRegister4::from(raw);
+        reg.set_y1_state_selection(index, selection);
+        prop_assert_eq!(reg.y1_state_selection(index), selection);
+        prop_assert_eq!(u8::from(reg) & others, raw & others);
+
+        let mut reg = Pll1ConfigurationRegister5::from(raw);
+        reg.set_y2y3_state_selection(index, selection);
+        prop_assert_eq!(reg.y2y3_state_selection(index), selection);
+        prop_assert_eq!(u8::from(reg) & others, raw & others);
+
+        let mut reg = Pll1ConfigurationRegister3::from(raw);
+        reg.set_fs1_selection(index, bank);
+        prop_assert_eq!(reg.fs1_selection(index), bank);
+        prop_assert_eq!(u8::from(reg) & others, raw & others);
+    }
+}
+
+#[test]
+fn enums_round_trip_through_their_encoding() {
+    for value in 0..4 {
+        assert_eq!(OutputStateDefinition::from(u2::new(value)) as u8, value);
+        assert_eq!(VcoRangeSelection::from(u2::new(value)) as u8, value);
+    }
+
+    for value in 0..8 {
+        assert_eq!(SscModulationAmountDown::from(u3::new(value)) as u8, value);
+        assert_eq!(SscModulationAmountCenter::from(u3::new(value)) as u8, value);
+    }
+}
+
+#[test]
+fn crystal_load_capacitance_saturates_like_the_device() {
+    assert_eq!(CrystalLoadCapacitance::from_pf(21), None);
+
+    for code in 20..32 {
+        let reg = GenericConfigurationRegister5::from(code << 3);
+
+        assert_eq!(reg.crystal_load_capacitance().pf(), 20);
+    }
+}
+
+#[test]
+fn register_offsets_cover_the_documented_registers() {
+    for offset in 0..=u8::MAX {
+        let documented = offset <= 0x06 || (0x10..0x50).contains(&offset);
+
+        assert_eq!(
+            RegisterOffset::new(offset).map(RegisterOffset::value),
+            documented.then_some(offset)
+        );
+    }
+
+    assert_eq!(RegisterOffset::generic::<6>().value(), 0x06);
+    assert_eq!(RegisterOffset::pll::<1, 0xB>().value(), 0x1B);
+    assert_eq!(RegisterOffset::pll::<4, 0xF>().value(), 0x4F);
+}
+
+#[test]
+fn reserved_input_clock_encoding_is_reported() {
+    let reg = GenericConfigurationRegister1::from(0b0000_1100);
+
+    assert_eq!(reg.input_clock_selection(), Err(ReservedEncoding(0b11)));
+}
+
+#[test]
+fn reserved_y3_multiplexer_encoding_is_reported() {
+    let reg = Pll1ConfigurationRegister4::from(0b0011_0000);
+
+    assert_eq!(reg.output_y3_multiplexer(), OutputY3Multiplexer::Reserved);
+    assert_eq!(reg.output_y3_source(), Err(ReservedEncoding(0b11)));
+}
+
+#[test]
+fn typed_accessors_read_back_what_they_wrote() {
+    for raw in 0..=u8::MAX {
+        for selection in [
+            InputClockSelection::Xtal,
+            InputClockSelection::Vcxo,
+            InputClockSelection::LvCmos,
+        ] {
+            let mut reg = GenericConfigurationRegister1::from(raw);
+            reg.set_input_clock_selection(selection);
+            assert_eq!(reg.input_clock_selection(), Ok(selection));
+        }
+
+        for pf in 0..=CrystalLoadCapacitance::MAX_PF {
+            let capacitance = CrystalLoadCapacitance::from_pf(pf).unwrap();
+
+            let mut reg = GenericConfigurationRegister5::from(raw);
+            reg.set_crystal_load_capacitance(capacitance);
+            assert_eq!(reg.crystal_load_capacitance(), capacitance);
+            assert_eq!(reg.xcsel(), pf);
+        }
+
+        for source in [Y1ClockSource::InputClock, Y1ClockSource::Pll1Clock] {
+            let mut reg = GenericConfigurationRegister2::from(raw);
+            reg.set_y1_clock_source(source);
+            assert_eq!(reg.y1_clock_source(), source);
+        }
+
+        for mode in [
+            SerialInterfacePinMode::SerialProgrammingInterface,
+            SerialInterfacePinMode::ControlS1S2,
+        ] {
+            let mut reg = GenericConfigurationRegister2::from(raw);
+            reg.set_serial_interface_pin_mode(mode);
+            assert_eq!(reg.serial_interface_pin_mode(), mode);
+        }
+
+        for value in 0..4 {
+            let definition = OutputStateDefinition::from(u2::new(value));
+
+            let mut reg = GenericConfigurationRegister2::from(raw);
+            reg.set_y1_state0_definition(definition);
+            reg.set_y1_state1_definition(definition);
+            assert_eq!(reg.y1_state0_definition(), definition);
+            assert_eq!(reg.y1_state1_definition(), definition);
+
+            let mut reg = Pll1ConfigurationRegister4::from(raw);
+            reg.set_y2y3_state0_definition(definition);
+            reg.set_y2y3_state1_definition(definition);
+            assert_eq!(reg.y2y3_state0_definition(), definition);
+            assert_eq!(reg.y2y3_state1_definition(), definition);
+
+            let range = VcoRangeSelection::from(u2::new(value));
+
+            let mut reg = Pll1ConfigurationRegisterB::from(raw);
+            reg.set_vco1_0_range_selection(range);
+            assert_eq!(reg.vco1_0_range_selection(), range);
+
+            let mut reg = Pll1ConfigurationRegisterF::from(raw);
+            reg.set_vco1_1_range_selection(range);
+            assert_eq!(reg.vco1_1_range_selection(), range);
+        }
+
+        for mux in [Pll1Multiplexer::Pll1, Pll1Multiplexer::Pll1Bypass] {
+            let mut reg = Pll1ConfigurationRegister4::from(raw);
+            reg.set_pll1_multiplexer(mux);
+            assert_eq!(reg.pll1_multiplexer(), mux);
+        }
+
+        for mux in [OutputY2Multiplexer::Pdiv1, OutputY2Multiplexer::Pdiv2] {
+            let mut reg = Pll1ConfigurationRegister4::from(raw);
+            reg.set_output_y2_multiplexer(mux);
+            assert_eq!(reg.output_y2_multiplexer(), mux);
+        }
+
+        for mux in [
+            OutputY3Multiplexer::Pdiv1,
+            OutputY3Multiplexer::Pdiv2,
+            OutputY3Multiplexer::Pdiv3,
+        ] {
+            let mut reg = Pll1ConfigurationRegister4::from(raw);
+            reg.set_output_y3_multiplexer(mux);
+            assert_eq!(reg.output_y3_multiplexer(), mux);
+        }
+
+        for source in [
+            OutputY3Source::Pdiv1,
+            OutputY3Source::Pdiv2,
+            OutputY3Source::Pdiv3,
+        ] {
+            let mut reg = Pll1ConfigurationRegister4::from(raw);
+            reg.set_output_y3_source(source);
+            assert_eq!(reg.output_y3_source(), Ok(source));
+            assert_eq!(reg.output_y3_multiplexer(), source.into());
+        }
+
+        for selection in [SscDownCenterSelection::Down, SscDownCenterSelection::Center] {
+            let mut reg = Pll1ConfigurationRegister6::from(raw);
+            reg.set_pll1_ssc_down_center_selection(selection);
+            assert_eq!(reg.pll1_ssc_down_center_selection(), selection);
+        }
+    }
+}
+
+#[test]
+fn register_fields_cover_every_bit_once() {
+    for offset in 0..0x20u8 {
+        let fields = register_fields(offset);
+        let reserved = (0x07..=0x0F).contains(&offset);
+
+        assert_eq!(fields.is_empty(), reserved, "offset {offset:#04x}");
+        if reserved {
+            continue;
+        }
+
+        let mut covered = 0u8;
+        for field in fields {
+            let mask = (((1u16 << field.width()) - 1) << field.lsb) as u8;
+
+            assert_eq!(covered & mask, 0, "{} overlaps", field.name);
+            covered |= mask;
+        }
+        assert_eq!(covered, 0xFF, "offset {offset:#04x}");
+    }
+}
+
+#[test]
+fn decoded_fields_agree_with_typed_accessors() {
+    let field = |offset, raw, name| {
+        decode_register(offset, raw)
+            .iter()
+            .find(|(field, _)| field.name == name)
+            .map(|(_, value)| value)
+            .unwrap()
+    };
+
+    for raw in 0..=u8::MAX {
+        let reg1 = GenericConfigurationRegister1::from(raw);
+        assert_eq!(
+            field(0x01, raw, "INCLK"),
+            FieldValue::InputClock(reg1.input_clock_selection())
+        );
+
+        let reg2 = GenericConfigurationRegister2::from(raw);
+        assert_eq!(
+            field(0x02, raw, "M1"),
+            FieldValue::Y1ClockSource(reg2.y1_clock_source())
+        );
+        assert_eq!(
+            field(0x02, raw, "Y1_ST1"),
+            FieldValue::OutputState(reg2.y1_state1_definition())
+        );
+
+        let reg4 = Pll1ConfigurationRegister4::from(raw);
+        assert_eq!(
+            field(0x14, raw, "MUX1"),
+            FieldValue::Pll1Multiplexer(reg4.pll1_multiplexer())
+        );
+        assert_eq!(
+            field(0x14, raw, "M3"),
+            FieldValue::Y3Multiplexer(reg4.output_y3_multiplexer())
+        );
+
+        let reg6 = Pll1ConfigurationRegister6::from(raw);
+        assert_eq!(
+            field(0x16, raw, "SSC1DC"),
+            FieldValue::SscDownCenter(reg6.pll1_ssc_down_center_selection())
+        );
+        assert_eq!(field(0x16, raw, "PDIV2"), FieldValue::Number(reg6.pdiv2()));
+
+        let regb = Pll1ConfigurationRegisterB::from(raw);
+        assert_eq!(
+            field(0x1B, raw, "VCO1_0_RANGE"),
+            FieldValue::VcoRange(regb.vco1_0_range_selection())
+        );
+    }
+}
@@ -0,0 +1,95 @@
+//! Block reads, which the device answers with all BCOUNT registers from 0x00.
+
+use arbitrary_int::u7;
+use cdcx913::{
+    CDCE913,
+    registers::pll1_configuration::Fs1Selection,
+    sim::{SimError, SimulatedCdce913},
+};
+use embassy_futures::block_on;
+use embedded_hal_async::i2c::I2c;
+
+#[test]
+fn simulated_device_rejects_block_reads_not_matching_bcount() {
+    let mut sim = SimulatedCdce913::new();
+    let address = sim.address();
+
+    block_on(async {
+        for len in [9, 0x22] {
+            let mut buf = [0u8; 0x22];
+            assert_eq!(
+                sim.write_read(address, &[0x00], &mut buf[..len]).await,
+                Err(SimError::BlockReadLength)
+            );
+        }
+
+        let mut full = [0u8; 0x21];
+        sim.write_read(address, &[0x00], &mut full).await.unwrap();
+        assert_eq!(full[0], 0x20);
+        assert_eq!(&full[1..], &sim.registers().to_bytes()[..]);
+    });
+}
+
+#[test]
+fn reads_follow_bcount() {
+    let mut sim = SimulatedCdce913::new();
+    let expected = sim.registers();
+    let mut driver = CDCE913::new(&mut sim);
+
+    block_on(async {
+        let settings = expected.pll1_settings(Fs1Selection::Fvcxo1);
+        assert_eq!(driver.pll1_1_settings().await.unwrap(), settings);
+
+        // BCOUNT only covering the generic block leaves PLL1 to byte reads
+        driver.set_block_byte_count(u7::new(0x10)).await.unwrap();
+        assert_eq!(driver.pll1_1_settings().await.unwrap(), settings);
+
+        driver.set_block_byte_count(u7::new(0x20)).await.unwrap();
+        assert_eq!(driver.read_config().await.unwrap(), expected);
+    });
+}
+
+#[test]
+fn bcount_is_read_from_the_device() {
+    let mut sim = SimulatedCdce913::new();
+    let expected = sim.registers().to_bytes();
+    let address = sim.address();
+
+    block_on(async {
+        // Another master shrinks BCOUNT, then grows it again
+        for bcount in [0x18, 0x28] {
+            sim.write(address, &[0x86, bcount << 1]).await.unwrap();
+
+            let mut driver = CDCE913::new(&mut sim);
+            let mut expected = expected;
+            expected[6] = bcount << 1;
+
+            let config = driver.read_config().await.unwrap();
+            assert_eq!(config.to_bytes(), expected);
+            assert_eq!(
+                driver.pll1_0_settings().await.unwrap(),
+                config.pll1_settings(Fs1Selection::Fvcxo0)
+            );
+        }
+    });
+}
+
+#[test]
+fn bcount_is_read_again_after_leaving_power_down() {
+    let mut sim = SimulatedCdce913::new();
+    let expected = sim.registers();
+    let mut driver = CDCE913::new(&mut sim);
+
+    block_on(async {
+        driver.set_block_byte_count(u7::new(0x28)).await.unwrap();
+        driver.set_power_down(true).await.unwrap();
+
+        // The EEPROM still holds the default BCOUNT, which is reloaded here
+        driver.set_power_down(false).await.unwrap();
+        assert_eq!(driver.read_config().await.unwrap(), expected);
+        assert_eq!(
+            driver.pll1_1_settings().await.unwrap(),
+            expected.pll1_settings(Fs1Selection::Fvcxo1)
+        );
+    });
+}
@@ -0,0 +1,62 @@
+//! The driver on a blocking bus through `Blocking` and `block_on`, as on hosts
+//! with `linux-embedded-hal` or firmware without an executor.
+
+use cdcx913::{
+    CDCE913,
+    blocking::{Blocking, block_on},
+    config::DeviceConfig,
+    i2c::ADDRESS,
+    registers::generic_configuration::GenericConfigurationRegister3,
+    test_support::Expectations,
+};
+use embedded_hal_mock::eh1::i2c::Mock;
+
+fn defaults() -> DeviceConfig {
+    let mut registers = [0; 32];
+    registers[0x00] = 0x81;
+    registers[0x01] = 0x01;
+    registers[0x03] = 0x01;
+    registers[0x06] = 0x40;
+
+    DeviceConfig::from_bytes(registers)
+}
+
+#[test]
+fn reads_the_configuration_over_a_blocking_bus() {
+    let expectations = Expectations::new(ADDRESS, defaults())
+        .read_config()
+        .into_transactions();
+    let mut i2c = Mock::new(&expectations);
+
+    let config = block_on(CDCE913::new(Blocking(i2c.clone())).read_config()).unwrap();
+
+    assert_eq!(config, defaults());
+    i2c.done();
+}
+
+#[test]
+fn applies_a_configuration_over_a_blocking_bus() {
+    let mut config = defaults();
+    config
+        .modify(0x03, |reg: &mut GenericConfigurationRegister3| {
+            reg.set_pdiv1_7_0(8)
+        })
+        .unwrap();
+
+    let expectations = Expectations::new(ADDRESS, defaults())
+        .apply_config(&config)
+        .into_transactions();
+    let mut i2c = Mock::new(&expectations);
+    let mut driver = CDCE913::new(Blocking(i2c.clone()));
+
+    let report = block_on(driver.apply_config(&config)).unwrap();
+
+    assert_eq!(
+        report
+            .changed_registers()
+            .map(|change| change.offset)
+            .collect::<Vec<_>>(),
+        [0x03]
+    );
+    i2c.done();
+}
@@ -0,0 +1,147 @@
+//! Drops multi-register updates after every possible number of completed bus
+//! transactions, the way `select!` or a timeout would, and checks that the
+//! device is never left half-configured.
+
+use core::{
+    future::Future,
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+
+use arbitrary_int::u10;
+use cdcx913::{
+    CDCE913,
+    output::OutputConfig,
+    registers::{
+        OutputStateDefinition, OutputStateSelection,
+        generic_configuration::GenericConfigurationRegister2,
+        pll1_configuration::{Fs1Selection, Pll1ConfigurationRegister4, PllSettings},
+    },
+    routing::{Divider, Route, Routing, Source},
+    sim::SimulatedCdce913,
+};
+use embassy_futures::block_on;
+use embedded_hal_async::i2c::{ErrorType, I2c, Operation};
+
+/// Lets `budget` transactions through and then never completes another one
+struct StallingBus<'a> {
+    sim: &'a mut SimulatedCdce913,
+    budget: usize,
+}
+
+impl ErrorType for StallingBus<'_> {
+    type Error = cdcx913::sim::SimError;
+}
+
+impl I2c for StallingBus<'_> {
+    async fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        if self.budget == 0 {
+            return core::future::pending().await;
+        }
+
+        self.budget -= 1;
+        self.sim.transaction(address, operations).await
+    }
+}
+
+/// Runs `f` until it stalls after `budget` transactions and drops it there.
+/// Returns whether it ran to completion.
+fn run_until_dropped<F>(sim: &mut SimulatedCdce913, budget: usize, f: F) -> bool
+where
+    F: AsyncFnOnce(&mut CDCE913<StallingBus<'_>>),
+{
+    let mut driver = CDCE913::new(StallingBus { sim, budget });
+    let mut future = pin!(f(&mut driver));
+    let mut cx = Context::from_waker(Waker::noop());
+
+    future.as_mut().poll(&mut cx) == Poll::Ready(())
+}
+
+#[test]
+fn dropped_pll_settings_update_leaves_old_or_new_settings() {
+    let new = PllSettings(0x1234_5678);
+
+    for budget in 0.. {
+        let mut sim = SimulatedCdce913::new();
+        let old = block_on(CDCE913::new(&mut sim).pll_settings(Fs1Selection::Fvcxo1)).unwrap();
+
+        let completed = run_until_dropped(&mut sim, budget, async |driver| {
+            driver
+                .set_pll_settings(Fs1Selection::Fvcxo1, new)
+                .await
+                .unwrap()
+        });
+
+        let after = block_on(CDCE913::new(&mut sim).pll_settings(Fs1Selection::Fvcxo1)).unwrap();
+        assert!(after == old || after == new, "{after:?} after {budget}");
+
+        if completed {
+            assert_eq!(after, new);
+            break;
+        }
+    }
+}
+
+#[test]
+fn dropped_output_config_update_leaves_old_or_new_config() {
+    for budget in 0.. {
+        let mut sim = SimulatedCdce913::new();
+        let old = block_on(CDCE913::new(&mut sim).output_config(2)).unwrap();
+        let new = OutputConfig {
+            divider: u10::new(12),
+            state1: OutputStateDefinition::DisabledLow,
+            selections: [OutputStateSelection::State1; 8],
+            ..old
+        };
+
+        let completed = run_until_dropped(&mut sim, budget, async |driver| {
+            driver.set_output_config(2, &new).await.unwrap()
+        });
+
+        let after = block_on(CDCE913::new(&mut sim).output_config(2)).unwrap();
+        assert!(after == old || after == new, "{after:?} after {budget}");
+
+        if completed {
+            assert_eq!(after, new);
+            break;
+        }
+    }
+}
+
+#[test]
+fn dropped_routing_update_leaves_old_or_new_routing() {
+    let new = Routing {
+        y1: Source::Input,
+        y2: Route::new(Source::Pll1, Divider::Pdiv2),
+        y3: Route::new(Source::Input, Divider::Pdiv1),
+    };
+
+    for budget in 0.. {
+        let mut sim = SimulatedCdce913::new();
+        let old = sim.registers();
+
+        let completed = run_until_dropped(&mut sim, budget, async |driver| {
+            driver.set_routing(new).await.unwrap()
+        });
+
+        let bytes = sim.registers().to_bytes();
+        let reg2 = GenericConfigurationRegister2::from(bytes[0x02]);
+        let pll4 = Pll1ConfigurationRegister4::from(bytes[0x14]);
+        let settings = new.mux_settings().unwrap();
+        let routed = reg2.y1_clock_source() == settings.m1
+            && Some(pll4.pll1_multiplexer()) == settings.mux1
+            && pll4.output_y2_multiplexer() == settings.m2
+            && pll4.output_y3_source() == Ok(settings.m3);
+        let after = sim.registers();
+        assert!(after == old || routed, "{after:?} after {budget}");
+
+        if completed {
+            assert!(routed);
+            break;
+        }
+    }
+}
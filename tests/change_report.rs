@@ -0,0 +1,198 @@
+//! The report `apply_config` returns for manufacturing logs, checked against
+//! the simulated device.
+
+use cdcx913::{
+    CDCE913, Error,
+    config::ChangeReport,
+    registers::{
+        generic_configuration::{
+            GenericConfigurationRegister1, GenericConfigurationRegister2,
+            GenericConfigurationRegister3, SerialInterfacePinMode,
+        },
+        pll1_configuration::Pll1ConfigurationRegisterF,
+    },
+    report::FieldValue,
+    sim::SimulatedCdce913,
+};
+use embassy_futures::block_on;
+
+fn field_names(report: &ChangeReport) -> Vec<&'static str> {
+    report
+        .changed_fields()
+        .map(|change| change.field.name)
+        .collect()
+}
+
+#[test]
+fn apply_config_reports_changed_and_skipped_fields() {
+    let mut sim = SimulatedCdce913::new();
+    let mut driver = CDCE913::new(&mut sim);
+
+    block_on(async {
+        let mut config = driver.read_config().await.unwrap();
+        config
+            .modify(0x03, |reg: &mut GenericConfigurationRegister3| {
+                reg.set_pdiv1_7_0(8)
+            })
+            .unwrap();
+        config
+            .modify(0x01, |reg: &mut GenericConfigurationRegister1| {
+                reg.set_eelock(true)
+            })
+            .unwrap();
+
+        let report = driver.apply_config(&config).await.unwrap();
+
+        assert!(report.is_written());
+        assert_eq!(report.writes().count(), 22);
+        assert_eq!(
+            report
+                .changed_registers()
+                .map(|change| change.offset)
+                .collect::<Vec<_>>(),
+            [0x03]
+        );
+        assert_eq!(field_names(&report), ["PDIV1[7:0]"]);
+
+        let change = report.changed_fields().next().unwrap();
+        assert_eq!(change.after, FieldValue::Number(8));
+
+        let skipped: Vec<_> = report.skipped_fields().collect();
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0].field.name, "EELOCK");
+        assert_eq!(skipped[0].requested, FieldValue::Flag(true));
+        assert_eq!(skipped[0].written, FieldValue::Flag(false));
+
+        assert_eq!(report.after(), &driver.read_config().await.unwrap());
+    });
+}
+
+#[test]
+fn verify_config_reports_without_writing() {
+    let mut sim = SimulatedCdce913::new();
+    let mut driver = CDCE913::new(&mut sim);
+
+    block_on(async {
+        let current = driver.read_config().await.unwrap();
+        assert!(driver.verify_config(&current).await.unwrap().is_unchanged());
+
+        let mut config = current;
+        config
+            .modify(0x03, |reg: &mut GenericConfigurationRegister3| {
+                reg.set_pdiv1_7_0(3)
+            })
+            .unwrap();
+
+        let report = driver.verify_config(&config).await.unwrap();
+
+        assert!(!report.is_written());
+        assert_eq!(report.writes().count(), 0);
+        assert_eq!(field_names(&report), ["PDIV1[7:0]"]);
+        assert_eq!(driver.read_config().await.unwrap(), current);
+    });
+}
+
+#[test]
+fn compare_and_apply_refuses_when_the_baseline_moved() {
+    let mut sim = SimulatedCdce913::new();
+    let mut driver = CDCE913::new(&mut sim);
+
+    block_on(async {
+        let baseline = driver.read_config().await.unwrap();
+
+        let mut config = baseline;
+        config
+            .modify(0x03, |reg: &mut GenericConfigurationRegister3| {
+                reg.set_pdiv1_7_0(8)
+            })
+            .unwrap();
+
+        // The other master got there first
+        let mut theirs = baseline;
+        theirs
+            .modify(0x03, |reg: &mut GenericConfigurationRegister3| {
+                reg.set_pdiv1_7_0(5)
+            })
+            .unwrap();
+        driver.apply_config(&theirs).await.unwrap();
+
+        let Err(Error::Conflict(mismatch)) = driver.compare_and_apply(&baseline, &config).await
+        else {
+            panic!("expected a conflict");
+        };
+        assert_eq!(mismatch.offset, 0x03);
+        assert_eq!(mismatch.actual, 5);
+        assert_eq!(driver.read_config().await.unwrap(), theirs);
+
+        let report = driver.compare_and_apply(&theirs, &config).await.unwrap();
+        assert_eq!(field_names(&report), ["PDIV1[7:0]"]);
+        assert_eq!(driver.read_config().await.unwrap(), config);
+    });
+}
+
+#[test]
+fn apply_config_keeps_the_address() {
+    let mut sim = SimulatedCdce913::new();
+    let address = sim.address();
+    let mut driver = CDCE913::new(&mut sim);
+
+    block_on(async {
+        let mut config = driver.read_config().await.unwrap();
+        config
+            .modify(0x01, |reg: &mut GenericConfigurationRegister1| {
+                reg.set_target_adr(0b10)
+            })
+            .unwrap();
+        config
+            .modify(0x03, |reg: &mut GenericConfigurationRegister3| {
+                reg.set_pdiv1_7_0(8)
+            })
+            .unwrap();
+        config
+            .modify(0x1F, |reg: &mut Pll1ConfigurationRegisterF| {
+                reg.set_pll1_1q_2_0(3)
+            })
+            .unwrap();
+
+        let report = driver.apply_config(&config).await.unwrap();
+
+        assert_eq!(field_names(&report), ["PDIV1[7:0]", "PLL1_1Q[2:0]"]);
+        let skipped: Vec<_> = report.skipped_fields().collect();
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0].field.name, "TARGET_ADR");
+        assert_eq!(report.after(), &driver.read_config().await.unwrap());
+    });
+
+    assert_eq!(sim.address(), address);
+}
+
+#[test]
+fn apply_config_sets_spicon_last() {
+    let mut sim = SimulatedCdce913::new();
+    let mut driver = CDCE913::new(&mut sim);
+
+    let report = block_on(async {
+        let mut config = driver.read_config().await.unwrap();
+        config
+            .modify(0x02, |reg: &mut GenericConfigurationRegister2| {
+                reg.set_serial_interface_pin_mode(SerialInterfacePinMode::ControlS1S2)
+            })
+            .unwrap();
+        config
+            .modify(0x1F, |reg: &mut Pll1ConfigurationRegisterF| {
+                reg.set_pll1_1q_2_0(3)
+            })
+            .unwrap();
+
+        let report = driver.apply_config(&config).await.unwrap();
+        assert_eq!(
+            driver.read_config().await,
+            Err(Error::SerialInterfaceDisabled)
+        );
+
+        report
+    });
+
+    assert!(!sim.serial_interface_enabled());
+    assert_eq!(&sim.registers(), report.after());
+}
@@ -0,0 +1,147 @@
+//! Describing the clock routing of the simulated device.
+
+use cdcx913::{
+    CDCE913, Error,
+    config::{ClockSource, DividerNode},
+    control::ControlInputState,
+    registers::{
+        generic_configuration::Y1ClockSource,
+        pll1_configuration::{Fs1Selection, OutputY2Multiplexer},
+    },
+    sim::SimulatedCdce913,
+    u7, u10,
+};
+use embassy_futures::block_on;
+
+#[test]
+fn y2_fed_from_pdiv1_is_visible() {
+    let mut sim = SimulatedCdce913::new();
+    let mut driver = CDCE913::new(&mut sim);
+    driver.set_reference_frequency_hz(Some(27_000_000));
+
+    block_on(async {
+        driver.set_y2_output_divider(u7::new(4)).await.unwrap();
+        let tree = driver.clock_tree(ControlInputState::S000).await.unwrap();
+        assert_eq!(tree.pll, ClockSource::Pll1(Fs1Selection::Fvcxo0));
+        assert_eq!(
+            tree.dividers[1],
+            DividerNode {
+                source: ClockSource::Pll1(Fs1Selection::Fvcxo0),
+                divider: 4,
+                hz: tree.vco_hz.map(|hz| hz / 4),
+            }
+        );
+        assert_eq!(tree.outputs[1].divider, Some(2));
+        assert_eq!(tree.outputs[1].hz, tree.dividers[1].hz);
+
+        driver
+            .set_y2_multiplexer(OutputY2Multiplexer::Pdiv1)
+            .await
+            .unwrap();
+        let tree = driver.clock_tree(ControlInputState::S000).await.unwrap();
+        assert_eq!(tree.outputs[1].divider, Some(1));
+        assert_eq!(tree.outputs[1].hz, tree.dividers[0].hz);
+    });
+}
+
+#[test]
+fn bypass_feeds_the_dividers_from_the_input() {
+    let mut sim = SimulatedCdce913::new();
+    let mut driver = CDCE913::new(&mut sim);
+    driver.set_reference_frequency_hz(Some(27_000_000));
+
+    block_on(async {
+        driver.bypass_pll().await.unwrap();
+        let tree = driver.clock_tree(ControlInputState::S000).await.unwrap();
+        assert_eq!(tree.pll, ClockSource::Pll1Bypass);
+        assert_eq!(tree.vco_hz, None);
+        assert_eq!(tree.dividers[0].hz, Some(27_000_000));
+        assert!(tree.to_string().contains("PDIV1: PLL1 bypass ÷1 = 27 MHz"));
+    });
+}
+
+#[test]
+fn frequencies_need_the_reference() {
+    let mut sim = SimulatedCdce913::new();
+    let mut driver = CDCE913::new(&mut sim);
+
+    block_on(async {
+        let tree = driver.clock_tree(ControlInputState::S000).await.unwrap();
+        assert_eq!(tree.reference_hz, None);
+        assert_eq!(tree.vco_hz, None);
+        assert!(tree.outputs.iter().all(|output| output.hz.is_none()));
+    });
+}
+
+#[test]
+fn y1_follows_m1() {
+    let mut sim = SimulatedCdce913::new();
+    let before = sim.registers();
+    let mut driver = CDCE913::new(&mut sim);
+
+    block_on(async {
+        assert_eq!(
+            driver.y1_frequency(ControlInputState::S000).await,
+            Err(Error::UnknownReferenceFrequency)
+        );
+        driver.set_reference_frequency_hz(Some(27_000_000));
+
+        let from_pll = driver.effective_config_for(ControlInputState::S000).await;
+        let from_pll = from_pll.unwrap().frequencies.unwrap().y1_hz;
+        assert_eq!(
+            driver.y1_frequency(ControlInputState::S000).await,
+            Ok(from_pll)
+        );
+
+        driver.set_y1_division_of_input(u10::new(3)).await.unwrap();
+        assert_eq!(
+            driver.y1_clock_source().await,
+            Ok(Y1ClockSource::InputClock)
+        );
+        assert_eq!(
+            driver.y1_frequency(ControlInputState::S000).await,
+            Ok(Some(9_000_000))
+        );
+
+        driver.set_y1_division_of_input(u10::new(0)).await.unwrap();
+        assert_eq!(driver.y1_frequency(ControlInputState::S000).await, Ok(None));
+    });
+
+    // PLL1 isn't touched
+    assert!(
+        sim.registers()
+            .diff(&before)
+            .mismatches()
+            .all(|m| m.offset < 0x10)
+    );
+}
+
+#[test]
+fn y1_follows_the_selected_pll_bank() {
+    let mut sim = SimulatedCdce913::new();
+    let mut driver = CDCE913::new(&mut sim);
+    driver.set_reference_frequency_hz(Some(27_000_000));
+
+    block_on(async {
+        let mut settings = driver.pll1_0_settings().await.unwrap();
+        settings.set_pllx_yq(settings.pllx_yq() / 2);
+        driver.set_pll1_1_settings(settings).await.unwrap();
+        driver
+            .set_pll1_frequency_selection(ControlInputState::S001, Fs1Selection::Fvcxo1)
+            .await
+            .unwrap();
+
+        for state in [ControlInputState::S000, ControlInputState::S001] {
+            let effective = driver.effective_config_for(state).await.unwrap();
+            assert_eq!(
+                driver.y1_frequency(state).await,
+                Ok(effective.frequencies.unwrap().y1_hz)
+            );
+        }
+
+        assert_ne!(
+            driver.y1_frequency(ControlInputState::S000).await,
+            driver.y1_frequency(ControlInputState::S001).await
+        );
+    });
+}
@@ -0,0 +1,137 @@
+//! Fluent configuration against the simulated device, including how many
+//! transactions it takes.
+
+use cdcx913::{
+    CDCE913, Error,
+    config::SscSetting,
+    control::ControlInputState,
+    output::DisableMode,
+    registers::{
+        OutputStateDefinition,
+        generic_configuration::InputClockSelection,
+        pll1_configuration::{OutputY2Multiplexer, SscModulationAmountCenter},
+    },
+    routing::{Divider, RoutingError},
+    sim::SimulatedCdce913,
+};
+use embassy_futures::block_on;
+
+#[test]
+fn staged_settings_are_applied() {
+    let mut sim = SimulatedCdce913::new();
+    let mut driver = CDCE913::new(&mut sim);
+    driver.set_reference_frequency_hz(Some(27_000_000));
+
+    block_on(async {
+        driver
+            .configure()
+            .input(InputClockSelection::LvCmos)
+            .y2(|y| y.from_pll().divider(4).enabled())
+            .ssc(SscSetting::Center(
+                SscModulationAmountCenter::PlusMinus05Percent,
+            ))
+            .apply()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            driver.input_clock().await.unwrap(),
+            InputClockSelection::LvCmos
+        );
+        assert_eq!(
+            driver.y2_multiplexer().await.unwrap(),
+            OutputY2Multiplexer::Pdiv2
+        );
+        assert_eq!(
+            driver
+                .spread_spectrum_clocking_selection_as_center(ControlInputState::S101)
+                .await
+                .unwrap(),
+            SscModulationAmountCenter::PlusMinus05Percent
+        );
+
+        let tree = driver.clock_tree(ControlInputState::S000).await.unwrap();
+        assert_eq!(tree.outputs[1].state, OutputStateDefinition::Enabled);
+        assert_eq!(tree.outputs[1].hz, tree.vco_hz.map(|hz| hz / 4));
+    });
+}
+
+#[test]
+fn only_changed_registers_are_written() {
+    let mut sim = SimulatedCdce913::new();
+    // Knowing BCOUNT up front saves reading it before the first block read
+    let registers = sim.registers();
+    let mut driver = CDCE913::assume_config(&mut sim, &registers);
+
+    block_on(async {
+        driver.configure().apply().await.unwrap();
+        assert_eq!(driver.stats().transactions, 1);
+
+        // Y2 and Y3 share the state definitions, which sit in one register
+        driver
+            .configure()
+            .y3(|y| y.disabled(DisableMode::Low))
+            .apply()
+            .await
+            .unwrap();
+        assert_eq!(driver.stats().transactions, 3);
+
+        // Already as requested, so nothing is written
+        driver
+            .configure()
+            .y2(|y| y.disabled(DisableMode::Low))
+            .apply()
+            .await
+            .unwrap();
+        assert_eq!(driver.stats().transactions, 4);
+    });
+
+    let tree = sim.registers().clock_tree(ControlInputState::S000, None);
+    assert_eq!(tree.outputs[2].state, OutputStateDefinition::DisabledLow);
+}
+
+#[test]
+fn contradictions_are_rejected_before_writing() {
+    let mut sim = SimulatedCdce913::new();
+    let before = sim.registers();
+    let mut driver = CDCE913::new(&mut sim);
+
+    block_on(async {
+        assert_eq!(
+            driver
+                .configure()
+                .y1(|y| y.divider(2))
+                .y2(|y| y.through(Divider::Pdiv1).divider(4))
+                .apply()
+                .await,
+            Err(Error::InvalidRouting(RoutingError::DividerConflict(
+                Divider::Pdiv1
+            )))
+        );
+
+        assert_eq!(
+            driver
+                .configure()
+                .input(InputClockSelection::LvCmos)
+                .y2(|y| y.enabled())
+                .y3(|y| y.disabled(DisableMode::PowerDown))
+                .apply()
+                .await,
+            Err(Error::SharedOutputState)
+        );
+
+        assert_eq!(
+            driver
+                .configure()
+                .y1(|y| y.through(Divider::Pdiv2))
+                .apply()
+                .await,
+            Err(Error::InvalidRouting(RoutingError::Unreachable {
+                output: 1,
+                divider: Divider::Pdiv2,
+            }))
+        );
+    });
+
+    assert_eq!(sim.registers(), before);
+}
@@ -0,0 +1,87 @@
+//! Matching XCSEL to the load capacitance a crystal is specified for.
+
+use cdcx913::{
+    CDCE913, Error, ValidationPolicy,
+    crystal::{CrystalSpec, DEVICE_PARASITIC_CAPACITANCE_FF},
+    registers::generic_configuration::CrystalLoadCapacitance,
+    sim::SimulatedCdce913,
+};
+use embassy_futures::block_on;
+
+fn pf(pf: u8) -> CrystalLoadCapacitance {
+    CrystalLoadCapacitance::from_pf(pf).unwrap()
+}
+
+#[test]
+fn pins_and_board_are_taken_off_cl() {
+    let crystal = CrystalSpec::new(27_000_000, 12_500).with_board_capacitance_ff(2_000);
+    let recommended = crystal.recommended_load_capacitance();
+
+    assert_eq!(DEVICE_PARASITIC_CAPACITANCE_FF, 1_500);
+    assert_eq!(recommended.capacitance, pf(9));
+    assert_eq!(recommended.total_load_ff, 12_500);
+    assert_eq!(recommended.error_ff(), 0);
+}
+
+#[test]
+fn half_steps_round_up() {
+    let crystal = CrystalSpec::new(25_000_000, 18_000).with_board_capacitance_ff(3_000);
+    let recommended = crystal.recommended_load_capacitance();
+
+    assert_eq!(recommended.capacitance, pf(14));
+    assert_eq!(recommended.error_ff(), 500);
+    assert!(recommended.is_within_step());
+}
+
+#[test]
+fn out_of_reach_cl_is_reported() {
+    let heavy = CrystalSpec::new(25_000_000, 30_000).recommended_load_capacitance();
+    assert_eq!(heavy.capacitance, pf(20));
+    assert_eq!(heavy.error_ff(), -8_500);
+    assert!(!heavy.is_within_step());
+
+    let light = CrystalSpec::new(25_000_000, 2_000)
+        .with_board_capacitance_ff(2_000)
+        .recommended_load_capacitance();
+    assert_eq!(light.capacitance, pf(0));
+    assert_eq!(light.error_ff(), 1_500);
+    assert!(!light.is_within_step());
+}
+
+#[test]
+fn applying_sets_xcsel_and_the_reference() {
+    let mut sim = SimulatedCdce913::new();
+    let mut driver = CDCE913::new(&mut sim);
+    let crystal = CrystalSpec::new(27_000_000, 12_500).with_board_capacitance_ff(2_000);
+
+    block_on(async {
+        let applied = driver.apply_crystal_spec(&crystal).await.unwrap();
+        assert_eq!(applied, crystal.recommended_load_capacitance());
+        assert_eq!(driver.crystal_load_capacitance().await, Ok(pf(9)));
+        assert_eq!(driver.reference_frequency_hz(), Some(27_000_000));
+    });
+}
+
+#[test]
+fn out_of_reach_cl_follows_the_validation_policy() {
+    let mut sim = SimulatedCdce913::new();
+    let mut driver = CDCE913::new(&mut sim);
+    let crystal = CrystalSpec::new(25_000_000, 30_000);
+
+    block_on(async {
+        assert_eq!(
+            driver.apply_crystal_spec(&crystal).await,
+            Err(Error::ValueOutOfRange)
+        );
+        assert_eq!(
+            driver.crystal_load_capacitance().await,
+            Ok(CrystalLoadCapacitance::DEFAULT)
+        );
+        assert_eq!(driver.reference_frequency_hz(), None);
+
+        driver.set_validation_policy(ValidationPolicy::Lenient);
+        driver.apply_crystal_spec(&crystal).await.unwrap();
+        assert!(driver.take_clamped());
+        assert_eq!(driver.crystal_load_capacitance().await, Ok(pf(20)));
+    });
+}
@@ -0,0 +1,143 @@
+//! Duty cycle estimates and plans avoiding odd output dividers.
+
+use cdcx913::{
+    CDCE913, CDCE925,
+    config::DeviceConfig,
+    family::Cdce925,
+    output::DutyCycle,
+    planner::{ConfigRequest, Limits, plan, plan_preferring_even},
+    registers::{
+        generic_configuration::DeviceIdentification,
+        pll1_configuration::{OutputY2Multiplexer, OutputY3Source},
+    },
+    sim::SimulatedCdce913,
+    u7, u10,
+};
+use embassy_futures::block_on;
+
+const LIMITS: Limits = Limits::for_variant(DeviceIdentification::CDCE913);
+
+#[test]
+fn odd_dividers_are_unbalanced() {
+    assert_eq!(DutyCycle::for_divider(0), None);
+    assert!(DutyCycle::for_divider(1).unwrap().is_balanced());
+    assert!(DutyCycle::for_divider(4).unwrap().is_balanced());
+
+    let three = DutyCycle::for_divider(3).unwrap();
+    assert_eq!(three.permille(), 333);
+    assert_eq!(three.deviation_permille(), 167);
+    assert!(DutyCycle::for_divider(127).unwrap().permille() > three.permille());
+}
+
+#[test]
+fn estimate_follows_the_multiplexer() {
+    let mut sim = SimulatedCdce913::new();
+    let mut driver = CDCE913::new(&mut sim);
+
+    block_on(async {
+        driver.set_y1_output_divider(u10::new(5)).await.unwrap();
+        driver.set_y2_output_divider(u7::new(4)).await.unwrap();
+
+        let mut y2 = driver.output(2).unwrap();
+        assert!(
+            y2.estimated_duty_cycle()
+                .await
+                .unwrap()
+                .unwrap()
+                .is_balanced()
+        );
+
+        driver
+            .set_y2_multiplexer(OutputY2Multiplexer::Pdiv1)
+            .await
+            .unwrap();
+        let mut y2 = driver.output(2).unwrap();
+        assert_eq!(
+            y2.estimated_duty_cycle().await.unwrap(),
+            DutyCycle::for_divider(5)
+        );
+    });
+}
+
+#[test]
+fn pdiv1_outside_the_pll1_block_is_pdiv2() {
+    let mut sim = SimulatedCdce913::new();
+    sim.set_device(Cdce925);
+    let mut driver: CDCE925<_> = CDCE925::for_device(&mut sim, Cdce925);
+
+    block_on(async {
+        driver.set_y1_output_divider(u10::new(4)).await.unwrap();
+        driver.set_y2_output_divider(u7::new(3)).await.unwrap();
+        for n in [4, 5] {
+            driver
+                .output(n)
+                .unwrap()
+                .set_divider(u10::new(2))
+                .await
+                .unwrap();
+        }
+
+        let mut pll2 = driver.pll(2).unwrap();
+        pll2.set_first_output_multiplexer(OutputY2Multiplexer::Pdiv1)
+            .await
+            .unwrap();
+        pll2.set_second_output_source(OutputY3Source::Pdiv1)
+            .await
+            .unwrap();
+
+        // PDIV(2n-2) of PLL2 is PDIV2, not Y1's PDIV1
+        for n in [4, 5] {
+            let mut output = driver.output(n).unwrap();
+            assert_eq!(
+                output.estimated_duty_cycle().await.unwrap(),
+                DutyCycle::for_divider(3)
+            );
+        }
+    });
+}
+
+#[test]
+fn planner_prefers_even_dividers() {
+    let odd = plan(27_000_000, 10_000_000, &LIMITS).unwrap();
+    assert!(odd.pdiv % 2 == 1, "{odd:?}");
+
+    let even = plan_preferring_even(27_000_000, 10_000_000, &LIMITS).unwrap();
+    assert_eq!(even.pdiv % 2, 0);
+    assert_eq!(even.vco_hz / even.pdiv as u32, 10_000_000);
+}
+
+#[test]
+fn planner_falls_back_to_odd_dividers() {
+    // With the VCO kept to 80..=100 MHz, 90 MHz / 3 is the only way to 30 MHz
+    let limits = Limits {
+        vco_max_hz: 100_000_000,
+        ..LIMITS
+    };
+
+    let plan = plan_preferring_even(27_000_000, 30_000_000, &limits).unwrap();
+    assert_eq!(plan.pdiv, 3);
+}
+
+#[test]
+fn config_request_prefers_even_dividers() {
+    let request = ConfigRequest::new()
+        .with_reference_hz(27_000_000)
+        .with_y2_hz(10_000_000)
+        .with_y3_hz(2_000_000);
+
+    let dividers = |config: DeviceConfig| {
+        let [pdiv2, pdiv3] = [0x16, 0x17].map(|offset| config.byte(offset).unwrap() & 0x7F);
+        [pdiv2, pdiv3]
+    };
+
+    assert!(
+        dividers(request.build().unwrap())
+            .iter()
+            .any(|d| d % 2 == 1)
+    );
+    assert!(
+        dividers(request.with_even_dividers_preferred().build().unwrap())
+            .iter()
+            .all(|d| d % 2 == 0)
+    );
+}
@@ -0,0 +1,44 @@
+//! Drives two simulated devices sitting on different bus types through the
+//! same type-erased driver.
+
+use arbitrary_int::u10;
+use cdcx913::{CDCE913, Error, dyn_i2c::DynI2c, sim::SimulatedCdce913};
+use embassy_futures::block_on;
+use embedded_hal_async::i2c::{ErrorKind, ErrorType, I2c, Operation};
+
+/// Bus whose transfers all fail, standing in for a second bus type
+struct BrokenBus;
+
+impl ErrorType for BrokenBus {
+    type Error = ErrorKind;
+}
+
+impl I2c for BrokenBus {
+    async fn transaction(
+        &mut self,
+        _address: u8,
+        _operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        Err(ErrorKind::Bus)
+    }
+}
+
+#[test]
+fn drivers_on_different_buses_share_one_type() {
+    let mut sim = SimulatedCdce913::new();
+    let mut broken = BrokenBus;
+
+    let drivers: [CDCE913<DynI2c<'_>>; 2] =
+        [CDCE913::new_dyn(&mut sim), CDCE913::new_dyn(&mut broken)];
+    let [mut working, mut failing] = drivers;
+
+    block_on(async {
+        working.set_y1_output_divider(u10::new(7)).await.unwrap();
+        assert_eq!(working.y1_output_divider().await.unwrap(), u10::new(7));
+
+        assert_eq!(
+            failing.y1_output_divider().await,
+            Err(Error::I2c(ErrorKind::Bus))
+        );
+    });
+}
@@ -0,0 +1,103 @@
+//! Configure, commit, power-cycle and verify flows against a simulated device
+//! whose EEPROM outlives it in a byte array.
+
+use arbitrary_int::u10;
+use cdcx913::{
+    CDCE913,
+    sim::{EepromStorage, SimulatedCdce913},
+};
+use embassy_futures::block_on;
+use embedded_hal_async::delay::DelayNs;
+
+struct NoWait;
+
+impl DelayNs for NoWait {
+    async fn delay_ns(&mut self, _ns: u32) {}
+}
+
+#[test]
+fn committed_configuration_survives_a_new_device() {
+    let mut image = SimulatedCdce913::new().eeprom().to_bytes();
+
+    let committed = block_on(async {
+        let mut sim = SimulatedCdce913::with_storage(&mut image);
+        let mut driver = CDCE913::new_with_delay(&mut sim, NoWait);
+
+        driver.set_y1_output_divider(u10::new(5)).await.unwrap();
+        let config = driver.read_config().await.unwrap();
+        driver.write_config_to_eeprom(&config).await.unwrap();
+
+        config
+    });
+
+    let mut sim = SimulatedCdce913::with_storage(&mut image);
+    block_on(async {
+        let mut driver = CDCE913::new_with_delay(&mut sim, NoWait);
+
+        assert_eq!(driver.y1_output_divider().await.unwrap(), u10::new(5));
+        assert!(
+            driver
+                .verify_eeprom_commit(&committed)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    });
+}
+
+#[test]
+fn uncommitted_changes_are_lost() {
+    let mut image = SimulatedCdce913::new().eeprom().to_bytes();
+    let defaults = image;
+
+    block_on(async {
+        let mut sim = SimulatedCdce913::with_storage(&mut image);
+        let mut driver = CDCE913::new(&mut sim);
+
+        driver.set_y1_output_divider(u10::new(5)).await.unwrap();
+    });
+
+    assert_eq!(image, defaults);
+    let sim = SimulatedCdce913::with_storage(&mut image);
+    assert_eq!(sim.registers().to_bytes()[..], defaults[..]);
+}
+
+/// Storage a test might keep in a file, counting the saves
+#[derive(Default)]
+struct Counted {
+    image: Option<[u8; 32]>,
+    stores: u32,
+}
+
+impl EepromStorage for Counted {
+    fn load(&mut self) -> Option<[u8; 32]> {
+        self.image
+    }
+
+    fn store(&mut self, image: &[u8; 32]) {
+        self.image = Some(*image);
+        self.stores += 1;
+    }
+}
+
+#[test]
+fn empty_storage_starts_factory_fresh_and_stores_every_commit() {
+    let mut sim = SimulatedCdce913::with_storage(Counted::default());
+    assert_eq!(sim.eeprom(), SimulatedCdce913::new().eeprom());
+
+    block_on(async {
+        let mut driver = CDCE913::new_with_delay(&mut sim, NoWait);
+
+        for divider in [3, 4] {
+            driver
+                .set_y1_output_divider(u10::new(divider))
+                .await
+                .unwrap();
+            driver.initiate_eeprom_write().await.unwrap();
+        }
+    });
+
+    let storage = sim.into_storage();
+    assert_eq!(storage.stores, 2);
+    assert_eq!(storage.image.unwrap()[0x03], 4);
+}
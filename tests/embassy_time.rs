@@ -0,0 +1,34 @@
+//! EEPROM workflows waiting through `embassy_time::Timer`, with the `std` time
+//! driver standing in for a hardware timer.
+
+use arbitrary_int::u10;
+use cdcx913::{CDCE913, sim::SimulatedCdce913, timing::EmbassyTimer};
+use embassy_futures::block_on;
+
+#[test]
+fn eeprom_commit_waits_with_the_embassy_timer() {
+    let mut sim = SimulatedCdce913::new();
+    sim.set_eeprom_write_duration(3);
+
+    let mut driver = CDCE913::new_with_delay(&mut sim, EmbassyTimer);
+
+    let config = block_on(async {
+        driver.set_y1_output_divider(u10::new(5)).await.unwrap();
+
+        let config = driver.read_config().await.unwrap();
+        driver.write_config_to_eeprom(&config).await.unwrap();
+
+        assert!(
+            driver
+                .verify_eeprom_commit(&config)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        config
+    });
+
+    assert_eq!(sim.eeprom_writes(), 1);
+    assert!(sim.eeprom().diff(&config).is_empty());
+}
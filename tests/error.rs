@@ -0,0 +1,65 @@
+//! The driver error sorts bus errors by kind and reports them as its source,
+//! the way error reporting stacks on hosted targets walk the chain.
+
+use core::error::Error as _;
+
+use cdcx913::{
+    CDCE913, CDCE925, Error,
+    family::Cdce925,
+    sim::{SimError, SimulatedCdce913},
+};
+use embassy_futures::block_on;
+use embedded_hal_async::i2c::{ErrorKind, ErrorType, I2c, NoAcknowledgeSource, Operation};
+
+#[test]
+fn bus_errors_are_chained_as_the_source() {
+    // Nothing answers on the CDCE925 address
+    let mut driver: CDCE925<_> = CDCE925::for_device(SimulatedCdce913::new(), Cdce925);
+
+    let error = block_on(driver.revision_number()).unwrap_err();
+
+    assert_eq!(error, Error::AddressNack(SimError::Nack));
+    assert_eq!(
+        error.source().and_then(|source| source.downcast_ref()),
+        Some(&SimError::Nack)
+    );
+    assert!(Error::<SimError>::EepromLocked.source().is_none());
+}
+
+/// Bus failing every transfer with the given kind
+struct FailingBus(ErrorKind);
+
+impl ErrorType for FailingBus {
+    type Error = ErrorKind;
+}
+
+impl I2c for FailingBus {
+    async fn transaction(
+        &mut self,
+        _address: u8,
+        _operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        Err(self.0)
+    }
+}
+
+#[test]
+fn bus_errors_are_sorted_by_kind() {
+    let fail = |kind| block_on(CDCE913::new(FailingBus(kind)).revision_number()).unwrap_err();
+
+    let address = ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address);
+    let data = ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data);
+    let unknown = ErrorKind::NoAcknowledge(NoAcknowledgeSource::Unknown);
+
+    assert_eq!(fail(address), Error::AddressNack(address));
+    assert_eq!(fail(data), Error::DataNack(data));
+    assert_eq!(
+        fail(ErrorKind::ArbitrationLoss),
+        Error::ArbitrationLoss(ErrorKind::ArbitrationLoss)
+    );
+    assert_eq!(fail(unknown), Error::I2c(unknown));
+    assert_eq!(fail(ErrorKind::Bus), Error::I2c(ErrorKind::Bus));
+
+    assert_eq!(fail(data).bus_error(), Some(&data));
+    assert_eq!(Error::<ErrorKind>::PollTimeout.bus_error(), None);
+}
@@ -0,0 +1,107 @@
+//! Larger family members on the simulated device: their PLL blocks past the
+//! register window and how far DeviceConfig reaches, and telling them apart.
+
+use arbitrary_int::u10;
+use cdcx913::{
+    CDCE949, Error,
+    family::{Cdce925, Cdce937, Cdce949, Device, DeviceKind, TI_VENDOR_ID, detect},
+    sim::SimulatedCdce913,
+};
+use embassy_futures::block_on;
+use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
+use embedded_hal_async::i2c::{ErrorType, I2c, Operation};
+
+#[test]
+fn cdce949_has_its_full_register_map() {
+    let mut sim = SimulatedCdce913::new();
+    sim.set_device(Cdce949);
+    assert_eq!(sim.address(), Cdce949::DEFAULT_ADDRESS);
+    assert_eq!(sim.register_map().len(), 0x50);
+
+    assert_eq!(
+        block_on(detect(&mut sim)).unwrap().kind,
+        DeviceKind::CDCE949
+    );
+
+    let window = sim.registers();
+    let mut driver: CDCE949<_> = CDCE949::for_device(&mut sim, Cdce949);
+
+    block_on(async {
+        driver
+            .output(9)
+            .unwrap()
+            .set_divider(u10::new(7))
+            .await
+            .unwrap();
+        assert_eq!(
+            driver.output(9).unwrap().divider().await.unwrap().value(),
+            7
+        );
+
+        // PLL4 lies past the window DeviceConfig covers
+        assert_eq!(driver.read_config().await.unwrap(), window);
+    });
+
+    assert_eq!(sim.register_map()[0x47], 7);
+}
+
+/// Answers on `address` with `response` to every read and NACKs every other
+/// address
+struct ProbedBus {
+    address: u8,
+    response: Result<u8, ErrorKind>,
+}
+
+impl ErrorType for ProbedBus {
+    type Error = ErrorKind;
+}
+
+impl I2c for ProbedBus {
+    async fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        if address != self.address {
+            return Err(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address));
+        }
+
+        for operation in operations {
+            if let Operation::Read(buf) = operation {
+                buf.fill(self.response?);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[test]
+fn detect_skips_only_missing_devices() {
+    // 3.3 V variant, revision 0, TI
+    let mut bus = ProbedBus {
+        address: Cdce937::DEFAULT_ADDRESS,
+        response: Ok(0x81),
+    };
+    let detected = block_on(detect(&mut bus)).unwrap();
+    assert_eq!(detected.kind, DeviceKind::CDCE937);
+    assert_eq!(detected.vendor, TI_VENDOR_ID);
+
+    let mut bus = ProbedBus {
+        address: Cdce937::DEFAULT_ADDRESS,
+        response: Err(ErrorKind::ArbitrationLoss),
+    };
+    assert_eq!(
+        block_on(detect(&mut bus)),
+        Err(Error::ArbitrationLoss(ErrorKind::ArbitrationLoss))
+    );
+}
+
+#[test]
+fn detect_ignores_other_vendors() {
+    let mut bus = ProbedBus {
+        address: Cdce925::DEFAULT_ADDRESS,
+        response: Ok(0x82),
+    };
+    assert_eq!(block_on(detect(&mut bus)), Err(Error::NoDeviceFound));
+}
@@ -0,0 +1,72 @@
+//! Checks the P/Q/R encoding against register values produced by TI's tools,
+//! listed in `fixtures/golden_vectors.csv`.
+
+use cdcx913::{planner::pll_settings, registers::pll1_configuration::PllSettings};
+
+struct Vector {
+    name: String,
+    input_hz: u32,
+    n: u32,
+    m: u32,
+    bytes: [u8; 4],
+}
+
+fn vectors() -> Vec<Vector> {
+    include_str!("fixtures/golden_vectors.csv")
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let fields: Vec<_> = line.split(',').map(str::trim).collect();
+            let [name, input_hz, n, m, bytes] = fields[..] else {
+                panic!("malformed golden vector: {line}");
+            };
+
+            let bytes: Vec<u8> = bytes
+                .split_whitespace()
+                .map(|byte| u8::from_str_radix(byte, 16).unwrap())
+                .collect();
+
+            Vector {
+                name: name.into(),
+                input_hz: input_hz.parse().unwrap(),
+                n: n.parse().unwrap(),
+                m: m.parse().unwrap(),
+                bytes: bytes.try_into().unwrap(),
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn encoding_matches_ti_tools() {
+    for vector in vectors() {
+        let vco_hz = (vector.input_hz as u64 * vector.n as u64 / vector.m as u64) as u32;
+        let settings = pll_settings(vector.n, vector.m, vco_hz)
+            .unwrap_or_else(|| panic!("{}: no settings for N/M", vector.name));
+
+        assert_eq!(
+            settings.0.to_be_bytes(),
+            vector.bytes,
+            "{}: register bytes differ",
+            vector.name
+        );
+    }
+}
+
+#[test]
+fn decoding_matches_ti_tools() {
+    for vector in vectors() {
+        let settings = PllSettings(u32::from_be_bytes(vector.bytes));
+        let vco_hz = (vector.input_hz as u64 * vector.n as u64 / vector.m as u64) as u32;
+
+        assert_eq!(settings.pllx_yn() as u32, vector.n, "{}: N", vector.name);
+        assert_eq!(settings.m(), Some(vector.m), "{}: M", vector.name);
+        assert_eq!(
+            settings.vco_frequency_hz(vector.input_hz),
+            Some(vco_hz),
+            "{}: fVCO",
+            vector.name
+        );
+    }
+}
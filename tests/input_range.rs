@@ -0,0 +1,102 @@
+//! Reference frequencies checked against the range of the selected input.
+
+use cdcx913::{
+    CDCE913, Error,
+    config::ValidationError,
+    planner::{ConfigRequest, InputRange, PlanError},
+    registers::generic_configuration::{DeviceIdentification, InputClockSelection},
+    sim::SimulatedCdce913,
+};
+use embassy_futures::block_on;
+
+#[test]
+fn lvcmos_takes_more_than_a_crystal() {
+    let xtal = InputRange::for_input(InputClockSelection::Xtal);
+    let vcxo = InputRange::for_input(InputClockSelection::Vcxo);
+    let lvcmos = InputRange::for_input(InputClockSelection::LvCmos);
+
+    assert!(xtal.contains(8_000_000) && xtal.contains(32_000_000));
+    assert!(!xtal.contains(7_999_999) && !xtal.contains(32_000_001));
+    assert!(vcxo.contains(27_000_000) && !vcxo.contains(32_000_000));
+    assert!(lvcmos.contains(100_000_000) && !lvcmos.contains(160_000_001));
+}
+
+#[test]
+fn input_change_is_refused_for_the_reference() {
+    let mut sim = SimulatedCdce913::new();
+    let before = sim.registers();
+    let mut driver = CDCE913::new(&mut sim);
+    driver.set_reference_frequency_hz(Some(100_000_000));
+
+    block_on(async {
+        assert_eq!(
+            driver.set_input_clock(InputClockSelection::Vcxo).await,
+            Err(Error::ReferenceOutOfRange {
+                input: InputClockSelection::Vcxo,
+                reference_hz: 100_000_000,
+            })
+        );
+        assert_eq!(
+            driver
+                .configure()
+                .input(InputClockSelection::Xtal)
+                .apply()
+                .await,
+            Err(Error::ReferenceOutOfRange {
+                input: InputClockSelection::Xtal,
+                reference_hz: 100_000_000,
+            })
+        );
+    });
+
+    assert_eq!(sim.registers(), before);
+}
+
+#[test]
+fn planning_checks_the_selected_input() {
+    let mut sim = SimulatedCdce913::new();
+    let mut driver = CDCE913::new(&mut sim);
+    driver.set_reference_frequency_hz(Some(100_000_000));
+
+    block_on(async {
+        assert_eq!(
+            driver.set_output_frequency(2, 50_000_000).await,
+            Err(Error::ReferenceOutOfRange {
+                input: InputClockSelection::Xtal,
+                reference_hz: 100_000_000,
+            })
+        );
+
+        driver
+            .set_input_clock(InputClockSelection::LvCmos)
+            .await
+            .unwrap();
+        driver.set_output_frequency(2, 50_000_000).await.unwrap();
+    });
+
+    let config = sim.registers();
+    assert_eq!(
+        config.validate(DeviceIdentification::CDCE913, 100_000_000),
+        Ok(())
+    );
+}
+
+#[test]
+fn configurations_are_validated_against_their_input() {
+    let config = SimulatedCdce913::new().registers();
+
+    assert_eq!(
+        config.validate(DeviceIdentification::CDCE913, 40_000_000),
+        Err(ValidationError::ReferenceOutOfRange {
+            input: InputClockSelection::Xtal,
+            reference_hz: 40_000_000,
+        })
+    );
+    assert_eq!(
+        ConfigRequest::new()
+            .with_reference_hz(40_000_000)
+            .with_y2_hz(80_000_000)
+            .build(),
+        Err(PlanError::ReferenceOutOfRange)
+    );
+}
@@ -0,0 +1,48 @@
+//! JSON export and import of DeviceConfig.
+
+use cdcx913::{config::DeviceConfig, sim::SimulatedCdce913};
+
+fn configured() -> DeviceConfig {
+    let mut config = SimulatedCdce913::new().registers();
+    // PDIV1 = 0x2A5 and a few PLL1_0 bits, all split across registers
+    config.set_byte(0x02, 0xB6).unwrap();
+    config.set_byte(0x03, 0xA5).unwrap();
+    config.set_byte(0x19, 0x5A).unwrap();
+    config.set_byte(0x1A, 0xC3).unwrap();
+    config
+}
+
+#[test]
+fn round_trips_the_fields() {
+    let config = configured();
+
+    assert_eq!(DeviceConfig::from_json(&config.to_json()).unwrap(), config);
+}
+
+#[test]
+fn uses_datasheet_mnemonics_in_register_order() {
+    let json = configured().to_json();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(value["PDIV1"], 0x2A5);
+    assert_eq!(value["M1"], 1);
+    assert!(value.get("PDIV1[9:8]").is_none());
+    assert!(json.find("\"E_EL\"").unwrap() < json.find("\"PDIV1\"").unwrap());
+    assert!(json.find("\"PDIV1\"").unwrap() < json.find("\"PLL1_1P\"").unwrap());
+}
+
+#[test]
+fn rejects_malformed_input() {
+    let json = configured().to_json();
+
+    let unknown = json.replacen("{", "{\n  \"PDIV4\": 1,", 1);
+    assert!(DeviceConfig::from_json(&unknown).is_err());
+
+    let missing = json.replacen("\"M1\"", "\"NOT_M1\"", 1);
+    assert!(DeviceConfig::from_json(&missing).is_err());
+
+    let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    value["PDIV2"] = 128.into();
+    let error = DeviceConfig::from_json(&value.to_string()).unwrap_err();
+    assert!(error.to_string().contains("PDIV2"));
+}
@@ -0,0 +1,31 @@
+//! Runs the Linux example's bring-up against the simulated device, so the
+//! example keeps working as the driver changes.
+
+#[path = "../examples/linux/src/bring_up.rs"]
+mod bring_up;
+
+use cdcx913::{CDCE913, control::ControlInputState, sim::SimulatedCdce913};
+use embassy_futures::block_on;
+use embedded_hal_async::delay::DelayNs;
+
+struct NoWait;
+
+impl DelayNs for NoWait {
+    async fn delay_ns(&mut self, _ns: u32) {}
+}
+
+#[test]
+fn bring_up_runs_against_the_simulator() {
+    let mut sim = SimulatedCdce913::new();
+    let mut pll = CDCE913::new_with_delay(&mut sim, NoWait);
+
+    block_on(async {
+        bring_up::bring_up(&mut pll).await.unwrap();
+
+        let tree = pll.clock_tree(ControlInputState::S111).await.unwrap();
+        assert_eq!(tree.outputs[1].hz, Some(48_000_000));
+        assert!(tree.outputs[1].state.is_enabled());
+    });
+
+    assert_eq!(sim.eeprom_writes(), 1);
+}
@@ -0,0 +1,110 @@
+//! Polling registers of the simulated device with a timeout.
+
+use std::cell::Cell;
+
+use cdcx913::{
+    CDCE913, Error,
+    registers::{
+        generic_configuration::{
+            EepromProgrammingStatus, GenericConfigurationRegister1, GenericConfigurationRegister5,
+            GenericConfigurationRegister6,
+        },
+        pll1_configuration::Pll1ConfigurationRegister4,
+    },
+    sim::SimulatedCdce913,
+    timing::STABILIZATION_TIME_US,
+};
+use embassy_futures::block_on;
+use embedded_hal_async::delay::DelayNs;
+
+struct CountingDelay<'a>(&'a Cell<u32>);
+
+impl DelayNs for CountingDelay<'_> {
+    async fn delay_ns(&mut self, _ns: u32) {}
+
+    async fn delay_ms(&mut self, ms: u32) {
+        self.0.set(self.0.get() + ms);
+    }
+}
+
+#[test]
+fn returns_the_accepted_value() {
+    let waited_ms = Cell::new(0);
+    let mut sim = SimulatedCdce913::new();
+    let mut driver = CDCE913::new_with_delay(&mut sim, CountingDelay(&waited_ms));
+
+    let mut reads = 0;
+    let reg = block_on(driver.poll_register_until(
+        |_: &GenericConfigurationRegister5| {
+            reads += 1;
+            reads == 3
+        },
+        10,
+    ))
+    .unwrap();
+
+    assert_eq!(reg.crystal_load_capacitance().pf(), 10);
+    assert_eq!(waited_ms.get(), 2);
+}
+
+#[test]
+fn waits_for_eeprom_programming() {
+    let mut sim = SimulatedCdce913::new();
+    sim.set_eeprom_write_duration(5);
+    let waited_ms = Cell::new(0);
+    let mut driver = CDCE913::new_with_delay(&mut sim, CountingDelay(&waited_ms));
+
+    block_on(async {
+        driver
+            .modify_byte_unchecked(0x06, |reg: &mut GenericConfigurationRegister6| {
+                reg.set_eewrite(true)
+            })
+            .await
+            .unwrap();
+        driver
+            .poll_register_until(
+                |reg: &GenericConfigurationRegister1| {
+                    reg.eeprom_programming_status() == EepromProgrammingStatus::Completed
+                },
+                100,
+            )
+            .await
+            .unwrap();
+    });
+
+    assert_eq!(waited_ms.get(), 5);
+}
+
+#[test]
+fn gives_up_after_the_timeout() {
+    let waited_ms = Cell::new(0);
+    let mut sim = SimulatedCdce913::new();
+    let mut driver = CDCE913::new_with_delay(&mut sim, CountingDelay(&waited_ms));
+
+    assert_eq!(
+        block_on(driver.poll_register_until(|_: &Pll1ConfigurationRegister4| false, 7)),
+        Err(Error::PollTimeout)
+    );
+    assert_eq!(waited_ms.get(), 7);
+}
+
+#[test]
+fn startup_waits_the_stabilization_time() {
+    struct MicrosDelay<'a>(&'a Cell<u32>);
+
+    impl DelayNs for MicrosDelay<'_> {
+        async fn delay_ns(&mut self, _ns: u32) {}
+
+        async fn delay_us(&mut self, us: u32) {
+            self.0.set(self.0.get() + us);
+        }
+    }
+
+    let waited_us = Cell::new(0);
+    let mut sim = SimulatedCdce913::new();
+    let mut driver = CDCE913::new_with_delay(&mut sim, MicrosDelay(&waited_us));
+
+    block_on(driver.wait_for_startup());
+
+    assert_eq!(waited_us.get(), STABILIZATION_TIME_US);
+}
@@ -0,0 +1,91 @@
+//! The presets built on the planner, checked against the frequencies they're
+//! meant to produce.
+
+use cdcx913::{
+    control::ControlInputState,
+    output::OutputMask,
+    planner::{Limits, PlanError},
+    presets::{
+        MclkRatio, PhyClock, PhyPresetError, PpmReport, SampleRate, mclk_hz, phy_clock_config,
+        plan_audio_mclk,
+    },
+    registers::{OutputStateDefinition, generic_configuration::DeviceIdentification},
+};
+
+const SAMPLE_RATES: [SampleRate; 7] = [
+    SampleRate::Hz32000,
+    SampleRate::Hz44100,
+    SampleRate::Hz48000,
+    SampleRate::Hz88200,
+    SampleRate::Hz96000,
+    SampleRate::Hz176400,
+    SampleRate::Hz192000,
+];
+const RATIOS: [MclkRatio; 3] = [MclkRatio::Fs256, MclkRatio::Fs384, MclkRatio::Fs512];
+
+fn limits() -> Limits {
+    Limits::for_variant(DeviceIdentification::CDCE913)
+}
+
+#[test]
+fn audio_crystal_reaches_every_master_clock() {
+    for sample_rate in SAMPLE_RATES {
+        for ratio in RATIOS {
+            let plan = plan_audio_mclk(24_576_000, sample_rate, ratio, &limits())
+                .unwrap_or_else(|e| panic!("{sample_rate:?} {ratio:?}: {e:?}"));
+
+            assert_eq!(plan.vco_hz % plan.pdiv as u32, 0);
+            assert_eq!(plan.vco_hz / plan.pdiv as u32, mclk_hz(sample_rate, ratio));
+        }
+    }
+}
+
+#[test]
+fn inexact_master_clock_is_rejected() {
+    assert_eq!(mclk_hz(SampleRate::Hz44100, MclkRatio::Fs256), 11_289_600);
+    assert_eq!(
+        plan_audio_mclk(25_000_000, SampleRate::Hz44100, MclkRatio::Fs256, &limits()),
+        Err(PlanError::Unachievable)
+    );
+}
+
+#[test]
+fn phy_clock_config_drives_the_requested_outputs() {
+    let preset = phy_clock_config(
+        25_000_000,
+        20,
+        PhyClock::Mhz125,
+        OutputMask::Y2Y3,
+        DeviceIdentification::CDCE913,
+    )
+    .unwrap();
+
+    assert_eq!(preset.ppm.margin_ppm(), 30);
+
+    for state in ControlInputState::ALL {
+        let effective = preset.config.effective_config(state, Some(25_000_000));
+        let frequencies = effective.frequencies.unwrap();
+
+        assert_eq!(effective.y1, OutputStateDefinition::Disabled3State);
+        assert_eq!(effective.y2y3, OutputStateDefinition::Enabled);
+        assert_eq!(frequencies.y2_hz, Some(125_000_000));
+        assert_eq!(frequencies.y3_hz, Some(125_000_000));
+    }
+}
+
+#[test]
+fn phy_clock_config_rejects_a_loose_crystal() {
+    assert_eq!(
+        phy_clock_config(
+            27_000_000,
+            60,
+            PhyClock::Mhz50,
+            OutputMask::Y1,
+            DeviceIdentification::CDCE913,
+        ),
+        Err(PhyPresetError::OutOfTolerance(PpmReport {
+            crystal_ppm: 60,
+            allowed_ppm: 50,
+        }))
+    );
+}
@@ -0,0 +1,71 @@
+//! The read-only view of the driver, next to the main driver on a shared bus.
+
+use cdcx913::{
+    CDCE913,
+    control::ControlInputState,
+    output::{DisableMode, OutputMask},
+    read_only::ReadOnlyCdcx913,
+    sim::SimulatedCdce913,
+};
+use embassy_embedded_hal::shared_bus::asynch::i2c::I2cDevice;
+use embassy_futures::block_on;
+use embassy_sync::{blocking_mutex::raw::NoopRawMutex, mutex::Mutex};
+
+#[test]
+fn monitor_sees_what_the_main_driver_set_up() {
+    let mut sim = SimulatedCdce913::new();
+    let bus = Mutex::<NoopRawMutex, _>::new(&mut sim);
+
+    let mut driver = CDCE913::new(I2cDevice::new(&bus));
+    let mut monitor = ReadOnlyCdcx913::new(I2cDevice::new(&bus));
+    monitor.set_reference_frequency_hz(Some(27_000_000));
+
+    block_on(async {
+        driver
+            .disable_outputs(OutputMask::Y1, DisableMode::Low)
+            .await
+            .unwrap();
+
+        let config = monitor.read_config().await.unwrap();
+        assert!(monitor.verify_config(&config).await.unwrap().is_unchanged());
+        assert_eq!(
+            monitor
+                .is_output_enabled(1, ControlInputState::S000)
+                .await
+                .map(|(enabled, _)| enabled),
+            Ok(false)
+        );
+        assert_eq!(
+            monitor
+                .clock_tree(ControlInputState::S000)
+                .await
+                .unwrap()
+                .reference_hz,
+            Some(27_000_000)
+        );
+        assert_eq!(monitor.power_down().await, Ok(false));
+        assert_eq!(monitor.read_config().await, Ok(config));
+    });
+}
+
+#[test]
+fn converting_keeps_the_driver_settings() {
+    let mut sim = SimulatedCdce913::new();
+    let before = sim.registers();
+
+    let mut driver = CDCE913::new(&mut sim);
+    driver.set_reference_frequency_hz(Some(27_000_000));
+
+    let mut monitor = driver.into_read_only();
+    assert_eq!(monitor.reference_frequency_hz(), Some(27_000_000));
+    assert_eq!(
+        block_on(monitor.y1_frequency(ControlInputState::S000)),
+        Ok(before
+            .effective_config(ControlInputState::S000, Some(27_000_000))
+            .frequencies
+            .unwrap()
+            .y1_hz)
+    );
+
+    assert_eq!(sim.registers(), before);
+}
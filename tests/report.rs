@@ -0,0 +1,56 @@
+//! Plain-text register reports written through `core::fmt::Write`.
+
+use cdcx913::{config::DeviceConfig, report::write_register_diff};
+
+fn golden() -> DeviceConfig {
+    let mut registers = [0; 32];
+    registers[0x00] = 0x81;
+    registers[0x14] = 0x6D;
+    registers[0x16] = 0x04;
+
+    DeviceConfig::from_bytes(registers)
+}
+
+fn diff(expected: &DeviceConfig, actual: &DeviceConfig) -> String {
+    let mut out = String::new();
+    write_register_diff(&mut out, expected, actual).unwrap();
+
+    out
+}
+
+#[test]
+fn identical_configurations_mark_nothing() {
+    let out = diff(&golden(), &golden());
+    let mut lines = out.lines();
+
+    assert_eq!(lines.next(), Some("  OFF  EXP  ACT   FIELDS"));
+    assert_eq!(
+        lines.next(),
+        Some("  0x00 0x81 0x81  E_EL=CDCE913 RID=0 VID=1")
+    );
+    assert_eq!(lines.nth(6), Some("  0x07 0x00 0x00  reserved"));
+    assert_eq!(out.lines().count(), 1 + 32);
+    assert!(
+        out.lines()
+            .all(|line| !line.starts_with('*') && !line.contains("->"))
+    );
+}
+
+#[test]
+fn only_the_changed_fields_are_bracketed() {
+    let mut actual = golden();
+    actual.set_byte(0x16, 0x06).unwrap();
+    actual.set_byte(0x01, 0x40).unwrap();
+
+    let out = diff(&golden(), &actual);
+    let marked: Vec<_> = out.lines().filter(|line| line.starts_with('*')).collect();
+
+    assert_eq!(
+        marked,
+        [
+            "* 0x01 0x00 0x40  RESERVED=0 [EEPIP=Completed->InProgress] EELOCK=0 PWDN=0 \
+             INCLK=Xtal TARGET_ADR=0",
+            "* 0x16 0x04 0x06  SSC1DC=Down [PDIV2=4->6]",
+        ]
+    );
+}
@@ -0,0 +1,102 @@
+//! `apply_config` writing the previous configuration back when the bus fails
+//! part way through.
+
+use cdcx913::{
+    CDCE913, Error,
+    config::DeviceConfig,
+    registers::generic_configuration::GenericConfigurationRegister3,
+    sim::{SimError, SimulatedCdce913},
+};
+use embassy_futures::block_on;
+use embedded_hal_async::i2c::{ErrorType, I2c, Operation};
+
+/// Fails the write transactions whose index (counted from 0) `fails` picks
+struct FlakyBus<'a, F> {
+    sim: &'a mut SimulatedCdce913,
+    writes: usize,
+    fails: F,
+}
+
+impl<F> ErrorType for FlakyBus<'_, F> {
+    type Error = SimError;
+}
+
+impl<F: FnMut(usize) -> bool> I2c for FlakyBus<'_, F> {
+    async fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        if let [Operation::Write(_)] = operations {
+            let index = self.writes;
+            self.writes += 1;
+
+            if (self.fails)(index) {
+                return Err(SimError::Nack);
+            }
+        }
+
+        self.sim.transaction(address, operations).await
+    }
+}
+
+fn new_config(sim: &SimulatedCdce913) -> DeviceConfig {
+    let mut config = sim.registers();
+    config
+        .modify(0x03, |reg: &mut GenericConfigurationRegister3| {
+            reg.set_pdiv1_7_0(8)
+        })
+        .unwrap();
+    config.modify(0x13, |reg: &mut u8| *reg ^= 0xFF).unwrap();
+    config
+}
+
+#[test]
+fn failed_pll1_write_restores_the_generic_registers() {
+    let mut sim = SimulatedCdce913::new();
+    let before = sim.registers();
+    let config = new_config(&sim);
+
+    let bus = FlakyBus {
+        sim: &mut sim,
+        writes: 0,
+        // The PLL1 block, right after the generic one
+        fails: |index| index == 1,
+    };
+    let mut driver = CDCE913::new(bus);
+
+    let result = block_on(driver.apply_config(&config));
+
+    assert_eq!(
+        result,
+        Err(Error::ApplyFailed {
+            error: SimError::Nack,
+            rolled_back: true,
+        })
+    );
+    assert_eq!(sim.registers(), before);
+}
+
+#[test]
+fn failed_rollback_is_reported() {
+    let mut sim = SimulatedCdce913::new();
+    let config = new_config(&sim);
+
+    let bus = FlakyBus {
+        sim: &mut sim,
+        writes: 0,
+        fails: |index| index >= 1,
+    };
+    let mut driver = CDCE913::new(bus);
+
+    let result = block_on(driver.apply_config(&config));
+
+    assert_eq!(
+        result,
+        Err(Error::ApplyFailed {
+            error: SimError::Nack,
+            rolled_back: false,
+        })
+    );
+    assert_eq!(sim.registers().byte(0x03), Ok(8));
+}
@@ -0,0 +1,132 @@
+//! Declarative routing, checked on its own and against the simulated device.
+
+use cdcx913::{
+    CDCE913, Error,
+    config::ClockSource,
+    control::ControlInputState,
+    registers::{
+        generic_configuration::Y1ClockSource,
+        pll1_configuration::{Fs1Selection, OutputY2Multiplexer, OutputY3Source, Pll1Multiplexer},
+    },
+    routing::{Divider, MuxSettings, Route, Routing, RoutingError, Source},
+    sim::SimulatedCdce913,
+};
+use embassy_futures::block_on;
+
+#[test]
+fn settings_follow_the_routes() {
+    let routing = Routing {
+        y1: Source::Input,
+        y2: Route::new(Source::Pll1, Divider::Pdiv2),
+        y3: Route::new(Source::Input, Divider::Pdiv1),
+    };
+
+    assert_eq!(
+        routing.mux_settings(),
+        Ok(MuxSettings {
+            m1: Y1ClockSource::InputClock,
+            mux1: Some(Pll1Multiplexer::Pll1),
+            m2: OutputY2Multiplexer::Pdiv2,
+            m3: OutputY3Source::Pdiv1,
+        })
+    );
+}
+
+#[test]
+fn mux1_is_left_alone_when_unused() {
+    let routing = Routing {
+        y1: Source::Input,
+        y2: Route::new(Source::Input, Divider::Pdiv1),
+        y3: Route::new(Source::Input, Divider::Pdiv1),
+    };
+
+    assert_eq!(routing.mux_settings().unwrap().mux1, None);
+}
+
+#[test]
+fn impossible_combinations_are_rejected() {
+    let pll = |divider| Route::new(Source::Pll1, divider);
+
+    let cases = [
+        (
+            Routing {
+                y1: Source::Pll1,
+                y2: pll(Divider::Pdiv3),
+                y3: pll(Divider::Pdiv3),
+            },
+            RoutingError::Unreachable {
+                output: 2,
+                divider: Divider::Pdiv3,
+            },
+        ),
+        (
+            Routing {
+                y1: Source::Pll1,
+                y2: pll(Divider::Pdiv2),
+                y3: Route::new(Source::Input, Divider::Pdiv3),
+            },
+            RoutingError::InputUnavailable(Divider::Pdiv3),
+        ),
+        (
+            Routing {
+                y1: Source::Input,
+                y2: pll(Divider::Pdiv1),
+                y3: pll(Divider::Pdiv3),
+            },
+            RoutingError::DividerConflict(Divider::Pdiv1),
+        ),
+        (
+            Routing {
+                y1: Source::Pll1Bypass,
+                y2: pll(Divider::Pdiv2),
+                y3: pll(Divider::Pdiv3),
+            },
+            RoutingError::Mux1Conflict,
+        ),
+    ];
+
+    for (routing, error) in cases {
+        assert_eq!(routing.mux_settings(), Err(error), "{routing:?}");
+    }
+}
+
+#[test]
+fn set_routing_programs_all_multiplexers() {
+    let mut sim = SimulatedCdce913::new();
+    let mut driver = CDCE913::new(&mut sim);
+
+    block_on(async {
+        driver
+            .set_routing(Routing {
+                y1: Source::Input,
+                y2: Route::new(Source::Pll1, Divider::Pdiv2),
+                y3: Route::new(Source::Input, Divider::Pdiv1),
+            })
+            .await
+            .unwrap();
+
+        let tree = driver.clock_tree(ControlInputState::S000).await.unwrap();
+        assert_eq!(tree.dividers[0].source, ClockSource::Input);
+        assert_eq!(tree.pll, ClockSource::Pll1(Fs1Selection::Fvcxo0));
+        assert_eq!(tree.outputs[1].divider, Some(2));
+        assert_eq!(tree.outputs[2].divider, Some(1));
+    });
+}
+
+#[test]
+fn impossible_routing_writes_nothing() {
+    let mut sim = SimulatedCdce913::new();
+    let before = sim.registers();
+    let mut driver = CDCE913::new(&mut sim);
+
+    let result = block_on(driver.set_routing(Routing {
+        y1: Source::Pll1Bypass,
+        y2: Route::new(Source::Pll1, Divider::Pdiv2),
+        y3: Route::new(Source::Pll1, Divider::Pdiv3),
+    }));
+    assert_eq!(
+        result,
+        Err(Error::InvalidRouting(RoutingError::Mux1Conflict))
+    );
+    assert_eq!(sim.registers(), before);
+}
@@ -0,0 +1,136 @@
+//! Runs the driver through `embassy-embedded-hal` shared-bus devices, with the
+//! simulated CDCE913 and a small EEPROM hanging off the same bus.
+
+use arbitrary_int::u10;
+use cdcx913::{CDCE913, sim::SimulatedCdce913};
+use embassy_embedded_hal::shared_bus::asynch::i2c::I2cDevice;
+use embassy_futures::{block_on, join::join};
+use embassy_sync::{blocking_mutex::raw::NoopRawMutex, mutex::Mutex};
+use embedded_hal_async::i2c::{ErrorType, I2c, Operation};
+
+const EEPROM_ADDRESS: u8 = 0x50;
+
+/// Bus with a CDCE913 and a 256 byte EEPROM with an 8-bit address pointer
+struct Board {
+    pll: SimulatedCdce913,
+    eeprom: [u8; 256],
+    pointer: u8,
+}
+
+impl Board {
+    fn new() -> Self {
+        Self {
+            pll: SimulatedCdce913::new(),
+            eeprom: [0xFF; 256],
+            pointer: 0,
+        }
+    }
+}
+
+impl ErrorType for Board {
+    type Error = cdcx913::sim::SimError;
+}
+
+impl I2c for Board {
+    async fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        if address != EEPROM_ADDRESS {
+            return self.pll.transaction(address, operations).await;
+        }
+
+        for operation in operations {
+            match operation {
+                Operation::Write(bytes) => {
+                    if let Some((&pointer, data)) = bytes.split_first() {
+                        self.pointer = pointer;
+
+                        for &byte in data {
+                            self.eeprom[self.pointer as usize] = byte;
+                            self.pointer = self.pointer.wrapping_add(1);
+                        }
+                    }
+                }
+                Operation::Read(buf) => {
+                    for byte in buf.iter_mut() {
+                        *byte = self.eeprom[self.pointer as usize];
+                        self.pointer = self.pointer.wrapping_add(1);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[test]
+fn driver_and_eeprom_share_one_bus() {
+    let bus = Mutex::<NoopRawMutex, _>::new(Board::new());
+
+    let mut clock = CDCE913::new(I2cDevice::new(&bus));
+    let mut eeprom = I2cDevice::new(&bus);
+
+    block_on(join(
+        async {
+            for divider in 1..=16u16 {
+                clock
+                    .set_y1_output_divider(u10::new(divider))
+                    .await
+                    .unwrap();
+                assert_eq!(clock.y1_output_divider().await.unwrap().value(), divider);
+            }
+        },
+        async {
+            for pointer in 0..16u8 {
+                eeprom
+                    .write(EEPROM_ADDRESS, &[pointer, !pointer])
+                    .await
+                    .unwrap();
+            }
+        },
+    ));
+
+    let bus = bus.into_inner();
+    assert_eq!(bus.pll.registers().to_bytes()[3], 16);
+    assert!((0..16u8).all(|pointer| bus.eeprom[pointer as usize] == !pointer));
+}
+
+#[test]
+fn released_device_can_be_reused() {
+    let bus = Mutex::<NoopRawMutex, _>::new(Board::new());
+    let clock = CDCE913::new(I2cDevice::new(&bus));
+
+    let mut device = clock.release();
+    block_on(device.write(EEPROM_ADDRESS, &[0x10, 0xA5])).unwrap();
+
+    assert_eq!(bus.into_inner().eeprom[0x10], 0xA5);
+}
+
+#[cfg(feature = "shared")]
+#[test]
+fn shared_driver_on_shared_bus() {
+    use cdcx913::{
+        registers::generic_configuration::CrystalLoadCapacitance, shared::SharedCdcx913,
+    };
+
+    let bus = Mutex::<NoopRawMutex, _>::new(Board::new());
+    let clock = SharedCdcx913::<NoopRawMutex, _>::new(CDCE913::new(I2cDevice::new(&bus)));
+
+    block_on(join(
+        clock.with(async |clock| clock.set_y1_output_divider(u10::new(5)).await),
+        clock.with(async |clock| {
+            clock
+                .set_crystal_load_capacitance(CrystalLoadCapacitance::DEFAULT)
+                .await
+        }),
+    ))
+    .0
+    .unwrap();
+
+    let registers = bus.into_inner().pll.registers().to_bytes();
+    assert_eq!(registers[3], 5);
+    assert_ne!(registers[5], 0);
+}
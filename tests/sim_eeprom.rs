@@ -0,0 +1,88 @@
+//! EEPROM timeout and lock handling, driven deterministically by the
+//! simulated device's EEPIP duration and EELOCK enforcement.
+
+use std::cell::Cell;
+
+use arbitrary_int::u10;
+use cdcx913::{
+    CDCE913, Error, eeprom::EepromLockConfirmation,
+    registers::generic_configuration::EepromProgrammingStatus, sim::SimulatedCdce913,
+};
+use embassy_futures::block_on;
+use embedded_hal_async::delay::DelayNs;
+
+/// Delay that only adds up the time waited for
+struct CountingDelay<'a>(&'a Cell<u32>);
+
+impl DelayNs for CountingDelay<'_> {
+    async fn delay_ns(&mut self, ns: u32) {
+        self.0.set(self.0.get() + ns / 1_000_000);
+    }
+}
+
+#[test]
+fn eeprom_write_finishing_at_the_timeout_succeeds() {
+    let mut sim = SimulatedCdce913::new();
+    sim.set_eeprom_write_duration(5);
+    let waited_ms = Cell::new(0);
+    let mut driver = CDCE913::new_with_delay(&mut sim, CountingDelay(&waited_ms));
+
+    block_on(driver.initiate_eeprom_write_with_timeout(5)).unwrap();
+
+    assert_eq!(waited_ms.get(), 5);
+    assert_eq!(sim.eeprom_writes(), 1);
+}
+
+#[test]
+fn eeprom_write_outlasting_the_timeout_fails() {
+    let mut sim = SimulatedCdce913::new();
+    sim.set_eeprom_write_duration(6);
+    let waited_ms = Cell::new(0);
+    let mut driver = CDCE913::new_with_delay(&mut sim, CountingDelay(&waited_ms));
+
+    block_on(async {
+        assert_eq!(
+            driver.initiate_eeprom_write_with_timeout(5).await,
+            Err(Error::EepromTimeout)
+        );
+        // The write itself carries on
+        assert_eq!(
+            driver.eeprom_programming_status().await.unwrap(),
+            EepromProgrammingStatus::Completed
+        );
+    });
+
+    assert_eq!(waited_ms.get(), 5);
+    assert_eq!(sim.eeprom_writes(), 1);
+}
+
+#[test]
+fn locked_eeprom_rejects_further_commits() {
+    let mut sim = SimulatedCdce913::new();
+    let waited_ms = Cell::new(0);
+
+    block_on(async {
+        let mut driver = CDCE913::new_with_delay(&mut sim, CountingDelay(&waited_ms));
+
+        driver
+            .lock_eeprom_permanently(EepromLockConfirmation::I_UNDERSTAND_THIS_IS_IRREVERSIBLE)
+            .await
+            .unwrap();
+        driver.set_y1_output_divider(u10::new(5)).await.unwrap();
+        driver.initiate_eeprom_write().await.unwrap();
+    });
+
+    assert_eq!(sim.eeprom_writes(), 1);
+    assert_eq!(sim.rejected_eeprom_writes(), 1);
+    assert_ne!(sim.eeprom().to_bytes()[0x03], 5);
+
+    sim.power_cycle();
+    let mut golden = sim.registers();
+    golden.set_byte(0x03, 5).unwrap();
+
+    let mut driver = CDCE913::new_with_delay(&mut sim, CountingDelay(&waited_ms));
+    assert_eq!(
+        block_on(driver.ensure_provisioned(&golden)),
+        Err(Error::EepromLocked)
+    );
+}
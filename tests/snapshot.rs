@@ -0,0 +1,47 @@
+//! Versioned binary snapshots of DeviceConfig.
+
+use cdcx913::{
+    compact::{DecodeError, SNAPSHOT_LEN, SNAPSHOT_VERSION},
+    config::{DeviceConfig, REGISTER_WINDOW_LEN},
+    family::DeviceKind,
+};
+
+fn config() -> DeviceConfig {
+    DeviceConfig::from_bytes(core::array::from_fn(|index| (index as u8).wrapping_mul(37)))
+}
+
+#[test]
+fn round_trips_every_register_and_the_kind() {
+    for kind in [DeviceKind::CDCEL913, DeviceKind::CDCE949] {
+        let snapshot = config().to_snapshot(kind);
+
+        assert_eq!(&snapshot[..4], b"CDCX");
+        assert_eq!(snapshot[4], SNAPSHOT_VERSION);
+        assert_eq!(snapshot[6] as usize, REGISTER_WINDOW_LEN);
+        assert_eq!(DeviceConfig::from_snapshot(&snapshot), Ok((kind, config())));
+    }
+}
+
+#[test]
+fn damaged_snapshots_are_rejected() {
+    let snapshot = config().to_snapshot(DeviceKind::CDCE913);
+
+    let mut corrupted = snapshot;
+    corrupted[20] ^= 0x01;
+    assert_eq!(
+        DeviceConfig::from_snapshot(&corrupted),
+        Err(DecodeError::Checksum)
+    );
+
+    let mut foreign = snapshot;
+    foreign[0] = b'X';
+    assert_eq!(
+        DeviceConfig::from_snapshot(&foreign),
+        Err(DecodeError::Magic)
+    );
+
+    assert_eq!(
+        DeviceConfig::from_snapshot(&snapshot[..SNAPSHOT_LEN - 1]),
+        Err(DecodeError::Length(SNAPSHOT_LEN - 1))
+    );
+}
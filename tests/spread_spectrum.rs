@@ -0,0 +1,146 @@
+//! Toggling spread spectrum modulation on the simulated device without
+//! re-specifying the amount.
+
+use cdcx913::{
+    CDCE913, Error,
+    control::ControlInputState,
+    registers::pll1_configuration::SscModulationAmountCenter,
+    sim::{SimError, SimulatedCdce913},
+    u3,
+};
+use embassy_futures::block_on;
+use embedded_hal_async::i2c::{ErrorType, I2c, Operation};
+
+#[test]
+fn re_enabling_restores_the_amount() {
+    let mut sim = SimulatedCdce913::new();
+    let mut driver = CDCE913::new(&mut sim);
+    // Spread over two registers, the awkward case
+    let state = ControlInputState::S010;
+
+    block_on(async {
+        driver
+            .set_spread_spectrum_clocking_selection_as_center(
+                state,
+                SscModulationAmountCenter::PlusMinus15Percent,
+            )
+            .await
+            .unwrap();
+
+        driver
+            .set_spread_spectrum_enabled(state, false)
+            .await
+            .unwrap();
+        assert!(!driver.spread_spectrum_enabled(state).await.unwrap());
+
+        driver
+            .set_spread_spectrum_enabled(state, true)
+            .await
+            .unwrap();
+        assert_eq!(
+            driver
+                .spread_spectrum_clocking_selection_as_center(state)
+                .await
+                .unwrap(),
+            SscModulationAmountCenter::PlusMinus15Percent
+        );
+    });
+}
+
+#[test]
+fn amount_found_on_the_device_is_remembered() {
+    let mut sim = SimulatedCdce913::new();
+    let state = ControlInputState::S101;
+
+    block_on(CDCE913::new(&mut sim).set_spread_spectrum_clocking_selection_raw(state, u3::new(5)))
+        .unwrap();
+
+    // A fresh driver only learns the amount when it disables modulation
+    let mut driver = CDCE913::new(&mut sim);
+
+    block_on(async {
+        driver
+            .set_spread_spectrum_enabled(state, false)
+            .await
+            .unwrap();
+        driver
+            .set_spread_spectrum_enabled(state, true)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            driver
+                .spread_spectrum_clocking_selection_raw(state)
+                .await
+                .unwrap(),
+            u3::new(5)
+        );
+    });
+}
+
+#[test]
+fn enabling_without_a_known_amount_fails() {
+    let mut sim = SimulatedCdce913::new();
+    let mut driver = CDCE913::new(&mut sim);
+    let state = ControlInputState::S000;
+
+    block_on(async {
+        driver
+            .set_spread_spectrum_clocking_selection_raw(state, u3::new(0))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            driver.set_spread_spectrum_enabled(state, true).await,
+            Err(Error::UnknownSscAmount)
+        );
+    });
+}
+
+/// Passes reads through to the simulated device and NACKs every write
+struct WriteProtectedBus<'a> {
+    sim: &'a mut SimulatedCdce913,
+}
+
+impl ErrorType for WriteProtectedBus<'_> {
+    type Error = SimError;
+}
+
+impl I2c for WriteProtectedBus<'_> {
+    async fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        // Anything beyond the command code is data to write
+        if operations
+            .iter()
+            .any(|operation| matches!(operation, Operation::Write(bytes) if bytes.len() > 1))
+        {
+            return Err(SimError::Nack);
+        }
+
+        self.sim.transaction(address, operations).await
+    }
+}
+
+#[test]
+fn failed_write_is_not_remembered() {
+    let mut sim = SimulatedCdce913::new();
+    let mut driver = CDCE913::new(WriteProtectedBus { sim: &mut sim });
+    let state = ControlInputState::S000;
+
+    block_on(async {
+        assert!(
+            driver
+                .set_spread_spectrum_clocking_selection_raw(state, u3::new(5))
+                .await
+                .is_err()
+        );
+
+        assert_eq!(
+            driver.set_spread_spectrum_enabled(state, true).await,
+            Err(Error::UnknownSscAmount)
+        );
+    });
+}
@@ -0,0 +1,53 @@
+//! Supply voltage hints and the limits they select.
+
+use cdcx913::{
+    CDCE913, Error,
+    planner::{ConfigRequest, Limits, PlanError, SupplyVoltage},
+    registers::generic_configuration::DeviceIdentification,
+    sim::SimulatedCdce913,
+};
+use embassy_futures::block_on;
+
+#[test]
+fn supply_matches_the_variant() {
+    for variant in [
+        DeviceIdentification::CDCE913,
+        DeviceIdentification::CDCEL913,
+    ] {
+        let supply = SupplyVoltage::for_variant(variant);
+        assert_eq!(supply.variant(), variant);
+        assert_eq!(Limits::for_supply(supply), Limits::for_variant(variant));
+    }
+}
+
+#[test]
+fn hint_overrides_the_detected_variant() {
+    let mut sim = SimulatedCdce913::new();
+    let mut driver = CDCE913::new(&mut sim);
+    driver.set_reference_frequency_hz(Some(27_000_000));
+
+    block_on(async {
+        // The simulated device reports the 3.3 V CDCE913
+        driver.set_output_frequency(1, 200_000_000).await.unwrap();
+
+        driver.set_supply_voltage(Some(SupplyVoltage::V1_8));
+        assert_eq!(
+            driver.set_output_frequency(1, 200_000_000).await,
+            Err(Error::FrequencyUnachievable(200_000_000))
+        );
+        driver.set_output_frequency(1, 100_000_000).await.unwrap();
+    });
+}
+
+#[test]
+fn requests_honour_the_supply() {
+    let request = ConfigRequest::new()
+        .with_reference_hz(27_000_000)
+        .with_y1_hz(200_000_000);
+
+    assert!(request.build().is_ok());
+    assert_eq!(
+        request.with_supply_voltage(SupplyVoltage::V1_8).build(),
+        Err(PlanError::OutputTooFast)
+    );
+}
@@ -0,0 +1,340 @@
+//! Frequency sweeps and ramps on the simulated device, checked through the
+//! output frequencies the resulting configuration decodes to.
+
+use core::cell::RefCell;
+
+use cdcx913::{
+    CDCE913, Error,
+    control::ControlInputState,
+    planner::{Limits, plan, plan_nearest},
+    registers::{
+        generic_configuration::{DeviceIdentification, Y1ClockSource},
+        pll1_configuration::{
+            Fs1Selection, OutputY2Multiplexer, Pll1ConfigurationRegister6, Pll1Multiplexer,
+        },
+    },
+    sim::{SimError, SimulatedCdce913},
+    u7,
+};
+use embassy_futures::block_on;
+use embedded_hal_async::{
+    delay::DelayNs,
+    i2c::{ErrorType, I2c, Operation},
+};
+
+const REFERENCE_HZ: u32 = 27_000_000;
+
+/// Records every dwell instead of waiting
+struct RecordingDelay<'a>(&'a RefCell<Vec<u32>>);
+
+impl DelayNs for RecordingDelay<'_> {
+    async fn delay_ns(&mut self, _ns: u32) {}
+
+    async fn delay_ms(&mut self, ms: u32) {
+        self.0.borrow_mut().push(ms);
+    }
+}
+
+fn y2_hz(sim: &SimulatedCdce913, state: ControlInputState) -> Option<u32> {
+    sim.registers()
+        .effective_config(state, Some(REFERENCE_HZ))
+        .frequencies
+        .and_then(|frequencies| frequencies.y2_hz)
+}
+
+#[test]
+fn sweep_ends_on_the_stop_frequency() {
+    let mut sim = SimulatedCdce913::new();
+    let dwells_ms = RefCell::new(Vec::new());
+    let mut driver = CDCE913::new_with_delay(&mut sim, RecordingDelay(&dwells_ms));
+    driver.set_reference_frequency_hz(Some(REFERENCE_HZ));
+
+    let skipped =
+        block_on(driver.sweep_output_frequency(2, 10_000_000, 10_250_000, 100_000, 5)).unwrap();
+
+    assert_eq!(skipped, 0);
+
+    // 10.0, 10.1, 10.2 and the shortened last step to 10.25 MHz
+    assert_eq!(dwells_ms.into_inner(), [5; 4]);
+
+    for state in ControlInputState::ALL {
+        assert_eq!(y2_hz(&sim, state), Some(10_250_000));
+    }
+}
+
+#[test]
+fn sweep_runs_downwards_too() {
+    let mut sim = SimulatedCdce913::new();
+    let dwells_ms = RefCell::new(Vec::new());
+    let mut driver = CDCE913::new_with_delay(&mut sim, RecordingDelay(&dwells_ms));
+    driver.set_reference_frequency_hz(Some(REFERENCE_HZ));
+
+    block_on(driver.sweep_output_frequency(3, 48_000_000, 24_000_000, 8_000_000, 1)).unwrap();
+
+    let frequencies = sim
+        .registers()
+        .effective_config(ControlInputState::ALL[0], Some(REFERENCE_HZ))
+        .frequencies
+        .unwrap();
+    assert_eq!(frequencies.y3_hz, Some(24_000_000));
+    assert_eq!(dwells_ms.into_inner().len(), 4);
+}
+
+#[test]
+fn unreachable_frequency_is_rejected_before_writing() {
+    let mut sim = SimulatedCdce913::new();
+    let before = sim.registers();
+    let mut driver = CDCE913::new(&mut sim);
+    driver.set_reference_frequency_hz(Some(REFERENCE_HZ));
+
+    assert_eq!(
+        block_on(driver.set_output_frequency(2, 300_000_000)),
+        Err(Error::FrequencyUnachievable(300_000_000))
+    );
+    assert_eq!(sim.registers(), before);
+}
+
+#[test]
+fn misrouted_outputs_are_rejected_before_writing() {
+    let mut sim = SimulatedCdce913::new();
+    let dwells_ms = RefCell::new(Vec::new());
+    let mut driver = CDCE913::new_with_delay(&mut sim, RecordingDelay(&dwells_ms));
+    driver.set_reference_frequency_hz(Some(REFERENCE_HZ));
+
+    block_on(async {
+        driver
+            .set_pll1_multiplexer(Pll1Multiplexer::Pll1Bypass)
+            .await
+            .unwrap();
+        driver
+            .set_y2_multiplexer(OutputY2Multiplexer::Pdiv1)
+            .await
+            .unwrap();
+        driver
+            .set_y1_clock_source(Y1ClockSource::InputClock)
+            .await
+            .unwrap();
+        let before = driver.read_config().await.unwrap().to_bytes();
+
+        for n in 1..=3 {
+            assert_eq!(
+                driver
+                    .sweep_output_frequency(n, 10_000_000, 12_000_000, 1_000_000, 0)
+                    .await,
+                Err(Error::OutputNotOnPll(n))
+            );
+        }
+
+        // Out of bypass, Y3 is on its own divider but Y2 still takes PDIV1
+        driver
+            .set_pll1_multiplexer(Pll1Multiplexer::Pll1)
+            .await
+            .unwrap();
+        assert_eq!(
+            driver.set_output_frequency(2, 10_000_000).await,
+            Err(Error::OutputNotOnPll(2))
+        );
+        driver.set_output_frequency(3, 10_000_000).await.unwrap();
+
+        // Nothing but MUX1 and what Y3 needs has changed
+        let after = driver.read_config().await.unwrap().to_bytes();
+        assert_eq!(after[..0x14], before[..0x14]);
+        assert_eq!(after[0x16], before[0x16]);
+    });
+
+    assert!(dwells_ms.into_inner().is_empty());
+}
+
+/// Counts the transfers that write to the device
+struct CountingBus<'a> {
+    sim: &'a mut SimulatedCdce913,
+    writes: u32,
+}
+
+impl ErrorType for CountingBus<'_> {
+    type Error = SimError;
+}
+
+impl I2c for CountingBus<'_> {
+    async fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        if let [Operation::Write(_)] = operations {
+            self.writes += 1;
+        }
+
+        self.sim.transaction(address, operations).await
+    }
+}
+
+#[test]
+fn y1_divider_and_banks_go_out_together() {
+    let mut sim = SimulatedCdce913::new();
+    let mut bus = CountingBus {
+        sim: &mut sim,
+        writes: 0,
+    };
+    let mut driver = CDCE913::new(&mut bus);
+    driver.set_reference_frequency_hz(Some(REFERENCE_HZ));
+
+    block_on(driver.set_output_frequency(1, 12_000_000)).unwrap();
+
+    assert_eq!(bus.writes, 1);
+    let frequencies = sim
+        .registers()
+        .effective_config(ControlInputState::ALL[0], Some(REFERENCE_HZ))
+        .frequencies
+        .unwrap();
+    assert_eq!(frequencies.y1_hz, Some(12_000_000));
+}
+
+#[test]
+fn ramp_takes_bounded_steps_and_lands_on_the_target() {
+    let mut sim = SimulatedCdce913::new();
+    let dwells_ms = RefCell::new(Vec::new());
+    let mut driver = CDCE913::new_with_delay(&mut sim, RecordingDelay(&dwells_ms));
+    driver.set_reference_frequency_hz(Some(REFERENCE_HZ));
+
+    let steps = block_on(async {
+        driver.set_output_frequency(2, 10_000_000).await.unwrap();
+        driver
+            .ramp_output_frequency(2, 10_100_000, 2_000, 3)
+            .await
+            .unwrap()
+    });
+
+    // 100 kHz in steps of at most 2000 ppm, i.e. about 20 kHz
+    assert!(steps >= 5, "{steps} steps");
+    assert_eq!(dwells_ms.into_inner(), vec![3; steps as usize - 1]);
+    assert_eq!(y2_hz(&sim, ControlInputState::ALL[0]), Some(10_100_000));
+}
+
+/// Records the exact frequency of Y2 after every write
+struct StepRecorder<'a> {
+    sim: &'a mut SimulatedCdce913,
+    y2_hz: Vec<f64>,
+}
+
+impl ErrorType for StepRecorder<'_> {
+    type Error = SimError;
+}
+
+impl I2c for StepRecorder<'_> {
+    async fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        let write = matches!(operations, [Operation::Write(_)]);
+        self.sim.transaction(address, operations).await?;
+
+        if write {
+            let registers = self.sim.registers();
+            let settings = registers.pll1_settings(Fs1Selection::Fvcxo0);
+            let pdiv2 = Pll1ConfigurationRegister6::from(registers.to_bytes()[0x16]).pdiv2();
+            self.y2_hz.push(
+                REFERENCE_HZ as f64 * settings.pllx_yn() as f64
+                    / (settings.m().unwrap() as f64 * pdiv2 as f64),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[test]
+fn ramp_steps_are_bounded_from_fractional_frequencies() {
+    let mut sim = SimulatedCdce913::new();
+    let mut recorder = StepRecorder {
+        sim: &mut sim,
+        y2_hz: Vec::new(),
+    };
+    let dwells_ms = RefCell::new(Vec::new());
+    let mut driver = CDCE913::new_with_delay(&mut recorder, RecordingDelay(&dwells_ms));
+    driver.set_reference_frequency_hz(Some(REFERENCE_HZ));
+
+    block_on(async {
+        driver.set_output_frequency(2, 10_000_000).await.unwrap();
+        driver
+            .ramp_output_frequency(2, 10_100_000, 500, 0)
+            .await
+            .unwrap();
+    });
+
+    let y2_hz = recorder.y2_hz;
+    assert!(y2_hz.iter().any(|hz| hz.fract() != 0.0), "{y2_hz:?}");
+    assert_eq!(*y2_hz.last().unwrap(), 10_100_000.0);
+
+    for step in y2_hz.windows(2) {
+        // 500 ppm of the current frequency, give or take its rounding
+        let max_step_hz = (step[0].round() * 500.0 / 1_000_000.0).floor() + 0.5;
+        assert!(
+            (step[1] - step[0]).abs() <= max_step_hz,
+            "{} Hz to {} Hz",
+            step[0],
+            step[1]
+        );
+    }
+}
+
+#[test]
+fn huge_ramp_steps_go_straight_to_the_target() {
+    let mut sim = SimulatedCdce913::new();
+    let dwells_ms = RefCell::new(Vec::new());
+    let mut driver = CDCE913::new_with_delay(&mut sim, RecordingDelay(&dwells_ms));
+    driver.set_reference_frequency_hz(Some(REFERENCE_HZ));
+
+    // 42_949_683 ppm of 100 MHz is just past u32::MAX Hz, which must not
+    // wrap around into a step of about 1 kHz
+    let steps = block_on(async {
+        driver.set_output_frequency(2, 100_000_000).await.unwrap();
+        driver
+            .ramp_output_frequency(2, 50_000_000, 42_949_683, 3)
+            .await
+            .unwrap()
+    });
+
+    assert_eq!(steps, 1);
+    assert!(dwells_ms.into_inner().is_empty());
+    assert_eq!(y2_hz(&sim, ControlInputState::ALL[0]), Some(50_000_000));
+}
+
+#[test]
+fn ramp_needs_a_running_output() {
+    let mut sim = SimulatedCdce913::new();
+    let dwells_ms = RefCell::new(Vec::new());
+    let mut driver = CDCE913::new_with_delay(&mut sim, RecordingDelay(&dwells_ms));
+    driver.set_reference_frequency_hz(Some(REFERENCE_HZ));
+
+    block_on(async {
+        driver.set_y2_output_divider(u7::new(0)).await.unwrap();
+        let before = driver.read_config().await.unwrap();
+
+        assert_eq!(
+            driver.ramp_output_frequency(2, 10_000_000, 2_000, 1).await,
+            Err(Error::OutputStopped(2))
+        );
+        assert_eq!(driver.read_config().await.unwrap(), before);
+    });
+}
+
+#[test]
+fn nearest_plan_stays_within_the_window() {
+    let limits = Limits::for_variant(DeviceIdentification::CDCE913);
+
+    // 10.001 MHz isn't reachable exactly from 27 MHz
+    assert!(plan(REFERENCE_HZ, 10_001_000, &limits).is_err());
+
+    let plan = plan_nearest(REFERENCE_HZ, 10_001_000, 10_000_001, 10_001_000, &limits).unwrap();
+    let hz = plan.output_hz;
+
+    assert!((10_000_001..=10_001_000).contains(&hz), "{hz} Hz");
+
+    // Rounded from the exact frequency, not truncated twice
+    let settings = plan.settings;
+    let exact = REFERENCE_HZ as f64 * settings.pllx_yn() as f64
+        / (settings.m().unwrap() as f64 * plan.pdiv as f64);
+    assert_eq!(hz, exact.round() as u32);
+}
@@ -0,0 +1,117 @@
+//! Planning configurations from TOML descriptions.
+
+use cdcx913::{
+    config::{DeviceConfig, ValidationError},
+    family::{Cdce913, Cdce925, Device},
+    planner::{ConfigRequest, PlanError, ssc_center},
+    toml_config::{TomlError, parse},
+};
+
+#[test]
+fn matches_the_equivalent_request() {
+    let config = parse(
+        r#"
+        reference = "25MHz"
+        ssc = "center 0.5%"
+
+        [y1]
+        frequency = "25 MHz"
+
+        [y2]
+        frequency = 50000000
+        "#,
+    )
+    .unwrap();
+
+    let request = ConfigRequest::new()
+        .with_reference_hz(25_000_000)
+        .with_ssc(ssc_center(50).unwrap())
+        .with_y1_hz(25_000_000)
+        .with_y2_hz(50_000_000);
+
+    assert_eq!(config, request.build().unwrap());
+}
+
+#[test]
+fn decimal_frequencies_are_exact() {
+    let config = parse(
+        r#"
+        reference = "27MHz"
+        y3 = { frequency = "13.5MHz" }
+        "#,
+    );
+    assert!(config.is_ok());
+
+    assert_eq!(
+        parse(r#"reference = "27.0000005MHz""#),
+        Err(TomlError::InvalidValue("reference".into()))
+    );
+}
+
+#[test]
+fn schema_errors_name_the_key() {
+    assert_eq!(
+        parse("[y2]\nfrequency = \"48MHz\""),
+        Err(TomlError::Missing("reference"))
+    );
+    assert_eq!(
+        parse("reference = \"25MHz\"\n[y2]\nfreq = \"48MHz\""),
+        Err(TomlError::UnknownKey("y2.freq".into()))
+    );
+    assert_eq!(
+        parse("reference = \"25MHz\"\n[y2]"),
+        Err(TomlError::Missing("y2.frequency"))
+    );
+    assert_eq!(
+        parse("reference = \"25MHz\"\nssc = \"center 0.3%\""),
+        Err(TomlError::InvalidValue("ssc".into()))
+    );
+    assert!(matches!(parse("reference = "), Err(TomlError::Syntax(_))));
+}
+
+#[test]
+fn variant_limits_apply() {
+    let input = r#"
+        reference = "25MHz"
+        variant = "CDCEL913"
+        y1 = { frequency = "200MHz" }
+    "#;
+
+    assert!(matches!(
+        parse(input),
+        Err(TomlError::Plan(PlanError::OutputTooFast)
+            | TomlError::Validation(ValidationError::OutputTooFast { .. }))
+    ));
+    assert!(parse(&input.replace("CDCEL913", "CDCE913")).is_ok());
+}
+
+#[test]
+fn device_keeps_its_default_target_address() {
+    const CONFIG: DeviceConfig = cdcx913::cdcx913_config! {
+        reference = 25 MHz,
+        y2 = 50 MHz,
+        device = Cdce925,
+    };
+
+    let config = parse(
+        r#"
+        reference = "25MHz"
+        device = "CDCE925"
+        y2 = { frequency = "50MHz" }
+        "#,
+    )
+    .unwrap();
+
+    let request = ConfigRequest::new()
+        .with_reference_hz(25_000_000)
+        .with_y2_hz(50_000_000);
+    let target_adr = |config: &DeviceConfig| config.to_bytes()[0x01] & 0b11;
+
+    assert_eq!(config, CONFIG);
+    assert_eq!(target_adr(&config), Cdce925::DEFAULT_ADDRESS & 0b11);
+    assert_eq!(config, request.with_device(Cdce925).build().unwrap());
+    assert_eq!(
+        target_adr(&request.build().unwrap()),
+        Cdce913::DEFAULT_ADDRESS & 0b11
+    );
+}
@@ -0,0 +1,84 @@
+//! Typed register access on the driver, against the simulated device.
+
+use cdcx913::{
+    CDCE913,
+    registers::{
+        Pll1Register, Pll1RegisterIndex,
+        generic_configuration::{CrystalLoadCapacitance, GenericConfigurationRegister5},
+        pll1_configuration::{Pll1ConfigurationRegister6, Pll1ConfigurationRegister7},
+    },
+    sim::SimulatedCdce913,
+    u7,
+};
+use embassy_futures::block_on;
+
+#[test]
+fn typed_access_matches_the_dedicated_methods() {
+    let mut sim = SimulatedCdce913::new();
+    let mut driver = CDCE913::new(&mut sim);
+
+    block_on(async {
+        driver
+            .set_crystal_load_capacitance(CrystalLoadCapacitance::from_pf(12).unwrap())
+            .await
+            .unwrap();
+        let reg = driver
+            .read_typed::<GenericConfigurationRegister5>()
+            .await
+            .unwrap();
+        assert_eq!(
+            reg.crystal_load_capacitance(),
+            driver.crystal_load_capacitance().await.unwrap()
+        );
+
+        let old = driver
+            .modify_typed(|reg: &mut Pll1ConfigurationRegister7| {
+                let old = reg.pdiv3();
+                reg.set_pdiv3(5);
+                old
+            })
+            .await
+            .unwrap();
+        assert_eq!(old, 0);
+        assert_eq!(driver.y3_output_divider().await.unwrap(), u7::new(5));
+
+        // Neighbouring registers stay as they were
+        let pdiv2 = driver.y2_output_divider().await.unwrap();
+        let reg = driver
+            .read_typed::<Pll1ConfigurationRegister6>()
+            .await
+            .unwrap();
+        assert_eq!(u7::new(reg.pdiv2()), pdiv2);
+    });
+}
+
+#[test]
+fn pll1_block_can_be_walked_by_index() {
+    let mut sim = SimulatedCdce913::new();
+    let config = sim.registers();
+    let mut driver = CDCE913::new(&mut sim);
+
+    block_on(async {
+        for index in Pll1RegisterIndex::ALL {
+            let register = driver.pll1_register(index).await.unwrap();
+
+            assert_eq!(register.index(), index);
+            assert_eq!(Ok(register.raw()), config.byte(index.offset().value()));
+        }
+
+        let Pll1Register::Register6(mut reg) = driver
+            .pll1_register(Pll1RegisterIndex::Register6)
+            .await
+            .unwrap()
+        else {
+            panic!("register 6 decoded as another register");
+        };
+        reg.set_pdiv2(9);
+        driver
+            .set_pll1_register(Pll1Register::Register6(reg))
+            .await
+            .unwrap();
+
+        assert_eq!(driver.y2_output_divider().await, Ok(u7::new(9)));
+    });
+}
@@ -0,0 +1,104 @@
+//! Strict and lenient handling of out-of-range values on the simulated device.
+
+use cdcx913::{
+    CDCE913, Error, ValidationPolicy,
+    control::ControlInputState,
+    registers::{
+        generic_configuration::CrystalLoadCapacitance, pll1_configuration::SscModulationAmountDown,
+    },
+    sim::SimulatedCdce913,
+    u10,
+};
+use embassy_futures::block_on;
+
+#[test]
+fn strict_rejects_without_writing() {
+    let mut sim = SimulatedCdce913::new();
+    let before = sim.registers();
+    let mut driver = CDCE913::new(&mut sim);
+    assert_eq!(driver.validation_policy(), ValidationPolicy::Strict);
+
+    block_on(async {
+        assert_eq!(
+            driver.set_crystal_load_capacitance_pf(21).await,
+            Err(Error::ValueOutOfRange)
+        );
+        assert_eq!(
+            driver.output(2).unwrap().set_divider(u10::new(128)).await,
+            Err(Error::DividerOutOfRange)
+        );
+        assert_eq!(
+            driver
+                .set_spread_spectrum_amount(ControlInputState::S000, 60)
+                .await,
+            Err(Error::ValueOutOfRange)
+        );
+    });
+
+    assert!(!driver.take_clamped());
+    assert_eq!(sim.registers(), before);
+}
+
+#[test]
+fn lenient_clamps_and_reports_it() {
+    let mut sim = SimulatedCdce913::new();
+    let mut driver = CDCE913::new(&mut sim);
+    driver.set_validation_policy(ValidationPolicy::Lenient);
+
+    block_on(async {
+        driver.set_crystal_load_capacitance_pf(31).await.unwrap();
+        assert!(driver.take_clamped());
+        assert_eq!(
+            driver.crystal_load_capacitance().await.unwrap(),
+            CrystalLoadCapacitance::from_pf(20).unwrap()
+        );
+
+        driver
+            .output(3)
+            .unwrap()
+            .set_divider(u10::new(1000))
+            .await
+            .unwrap();
+        assert!(driver.take_clamped());
+        assert_eq!(
+            driver.output(3).unwrap().divider().await.unwrap(),
+            u10::new(127)
+        );
+
+        driver
+            .set_spread_spectrum_amount(ControlInputState::S000, 60)
+            .await
+            .unwrap();
+        assert!(driver.take_clamped());
+        assert_eq!(
+            driver
+                .spread_spectrum_clocking_selection_as_down(ControlInputState::S000)
+                .await
+                .unwrap(),
+            SscModulationAmountDown::Minus05Percent
+        );
+    });
+}
+
+#[test]
+fn values_in_range_are_never_clamped() {
+    let mut sim = SimulatedCdce913::new();
+    let mut driver = CDCE913::new(&mut sim);
+    driver.set_validation_policy(ValidationPolicy::Lenient);
+
+    block_on(async {
+        driver.set_crystal_load_capacitance_pf(20).await.unwrap();
+        driver
+            .output(2)
+            .unwrap()
+            .set_divider(u10::new(127))
+            .await
+            .unwrap();
+        driver
+            .set_spread_spectrum_amount(ControlInputState::S111, 200)
+            .await
+            .unwrap();
+    });
+
+    assert!(!driver.take_clamped());
+}